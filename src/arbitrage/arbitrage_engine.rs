@@ -1,7 +1,15 @@
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+use serde::{Deserialize, Serialize};
 use tracing::{ info, debug, warn };
 
-use crate::{arbitrage::{detector::ArbitrageDetector, validator::OpportunityValidator}, event::processor::EventProcessor, execution::executor::TradeExecutor, types::{ArbitrageOpportunity, ExecutionResult, Result}};
-use std::time::Duration;
+use crate::{dex::manager::DexManager, arbitrage::{calculator::SharedArbitrageConfig, circuit_breaker::CircuitBreaker, detector::ArbitrageDetector, validator::OpportunityValidator}, event::processor::EventProcessor, execution::executor::TradeExecutor, event::processor::ProcessorStatus, sync::periodic::{SyncEvent, SyncOrchestrator, SyncScope}, types::{ArbitrageOpportunity, BotError, DexId, ExecutionResult, ExecutionStatus, PoolState, RawEvent, Result, now}, utils::{alerting::{AlertSeverity, Alerter}, config::{ArbitrageConfig, Config, DEFAULT_EXECUTION_BROADCAST_CAPACITY, SyncConfig, ValidationConfig}, metrics::MetricsRegistry, pnl::PnlTracker}};
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex, MutexGuard, atomic::{AtomicU64, Ordering}},
+    time::{Duration, Instant},
+};
+use tokio::sync::{RwLock, broadcast, mpsc, oneshot, watch};
 
 pub struct ArbitrageEngine {
     // Components
@@ -9,32 +17,314 @@ pub struct ArbitrageEngine {
     detector: Box<dyn ArbitrageDetector>,
     executor: Box<dyn TradeExecutor>,
     validator: Box<dyn OpportunityValidator>,
+    validation_config: ValidationConfig,
+    alerter: Alerter,
+    execution_sender: broadcast::Sender<ExecutionResult>,
+    dex_manager: Option<Arc<RwLock<DexManager>>>,
+    sync_orchestrator: Option<Arc<SyncOrchestrator>>,
+    sync_timeout: Duration,
+    config_summary: serde_json::Value,
+    metrics: Option<Arc<MetricsRegistry>>,
+    pnl_tracker: Option<Arc<RwLock<PnlTracker>>>,
+    pnl_report_interval: Duration,
+    circuit_breaker: CircuitBreaker,
+    config_updates: Option<watch::Receiver<Config>>,
+    arbitrage_config: Option<SharedArbitrageConfig>,
+    state_sender: mpsc::Sender<oneshot::Sender<BotState>>,
+    state_requests: Option<mpsc::Receiver<oneshot::Sender<BotState>>>,
     
     // State
     is_running: bool,
     stats: EngineStats,
+    last_sync_time: Option<Instant>,
+    recent_opportunities: VecDeque<ArbitrageOpportunity>,
 }
 
+/// Number of detected opportunities kept for `export_state`
+pub const RECENT_OPPORTUNITIES_LIMIT: usize = 50;
+
+/// State requests that can wait for the main loop before senders block
+const STATE_REQUEST_CAPACITY: usize = 8;
+
+/// Engine counters behind shared atomics. Clones share the same counters,
+/// so a monitoring reader can hold one while the engine keeps recording.
 #[derive(Debug, Clone)]
 pub struct EngineStats {
+    opportunities_found: Arc<AtomicU64>,
+    opportunities_executed: Arc<AtomicU64>,
+    opportunities_skipped: Arc<AtomicU64>,
+    execution_successes: Arc<AtomicU64>,
+    execution_failures: Arc<AtomicU64>,
+    /// Dry-run executions, kept out of the success count and profit
+    execution_simulations: Arc<AtomicU64>,
+    total_profit: Arc<Mutex<Decimal>>,
+    simulated_profit: Arc<Mutex<Decimal>>,
+    start_time: Arc<Mutex<Instant>>,
+    state: Arc<Mutex<EngineState>>,
+}
+
+/// Lifecycle state of the engine
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EngineState {
+    Idle,
+    Running,
+    Stopped,
+}
+
+impl Default for EngineStats {
+    fn default() -> Self {
+        Self {
+            opportunities_found: Arc::default(),
+            opportunities_executed: Arc::default(),
+            opportunities_skipped: Arc::default(),
+            execution_successes: Arc::default(),
+            execution_failures: Arc::default(),
+            execution_simulations: Arc::default(),
+            total_profit: Arc::default(),
+            simulated_profit: Arc::default(),
+            start_time: Arc::new(Mutex::new(Instant::now())),
+            state: Arc::new(Mutex::new(EngineState::Idle)),
+        }
+    }
+}
+
+/// Lock a stats value, recovering it if a recorder panicked mid-update
+fn locked<T>(value: &Mutex<T>) -> MutexGuard<'_, T> {
+    value.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+impl EngineStats {
+    pub fn record_opportunity(&self) {
+        self.opportunities_found.fetch_add(1, Ordering::Relaxed);
+    }
+    
+    /// An opportunity the validator rejected
+    pub fn record_skipped(&self) {
+        self.opportunities_skipped.fetch_add(1, Ordering::Relaxed);
+    }
+    
+    /// An opportunity handed to the executor
+    pub fn record_executed(&self) {
+        self.opportunities_executed.fetch_add(1, Ordering::Relaxed);
+    }
+    
+    pub fn record_execution_success(&self, profit: Decimal) {
+        self.execution_successes.fetch_add(1, Ordering::Relaxed);
+        *locked(&self.total_profit) += profit;
+    }
+    
+    pub fn record_execution_failure(&self) {
+        self.execution_failures.fetch_add(1, Ordering::Relaxed);
+    }
+    
+    /// A dry-run execution and the profit it would have made
+    pub fn record_execution_simulated(&self, profit: Decimal) {
+        self.execution_simulations.fetch_add(1, Ordering::Relaxed);
+        *locked(&self.simulated_profit) += profit;
+    }
+    
+    /// Enter `state`; entering `Running` restarts the uptime clock
+    pub fn set_state(&self, state: EngineState) {
+        if state == EngineState::Running {
+            *locked(&self.start_time) = Instant::now();
+        }
+        *locked(&self.state) = state;
+    }
+    
+    pub fn total_profit(&self) -> Decimal {
+        *locked(&self.total_profit)
+    }
+    
+    /// Current values of every counter
+    pub fn snapshot(&self) -> EngineStatsSnapshot {
+        EngineStatsSnapshot {
+            opportunities_found: self.opportunities_found.load(Ordering::Relaxed),
+            opportunities_executed: self.opportunities_executed.load(Ordering::Relaxed),
+            opportunities_skipped: self.opportunities_skipped.load(Ordering::Relaxed),
+            execution_successes: self.execution_successes.load(Ordering::Relaxed),
+            execution_failures: self.execution_failures.load(Ordering::Relaxed),
+            execution_simulations: self.execution_simulations.load(Ordering::Relaxed),
+            total_profit: self.total_profit(),
+            simulated_profit: *locked(&self.simulated_profit),
+            start_time: *locked(&self.start_time),
+            state: *locked(&self.state),
+        }
+    }
+}
+
+/// Engine counters read at one point in time
+#[derive(Debug, Clone, Serialize)]
+pub struct EngineStatsSnapshot {
     pub opportunities_found: u64,
     pub opportunities_executed: u64,
+    /// Opportunities rejected by the validator
+    pub opportunities_skipped: u64,
     pub execution_successes: u64,
     pub execution_failures: u64,
-    pub total_profit: f64,
-    pub start_time: std::time::Instant,
+    pub execution_simulations: u64,
+    pub total_profit: Decimal,
+    pub simulated_profit: Decimal,
+    #[serde(skip)]
+    pub start_time: Instant,
+    pub state: EngineState,
 }
 
-impl Default for EngineStats {
-    fn default() -> Self {
-        Self {
-            opportunities_found: 0,
-            opportunities_executed: 0,
-            execution_successes: 0,
-            execution_failures: 0,
-            total_profit: 0.0,
-            start_time: std::time::Instant::now(),
+impl EngineStatsSnapshot {
+    /// Percentage of executed opportunities that succeeded
+    pub fn success_rate(&self) -> f64 {
+        self.execution_successes as f64 / self.opportunities_executed.max(1) as f64 * 100.0
+    }
+    
+    /// Opportunities found per minute of uptime
+    pub fn scan_rate_per_minute(&self) -> f64 {
+        let minutes = self.start_time.elapsed().as_secs_f64() / 60.0;
+        if minutes <= 0.0 {
+            return 0.0;
         }
+        self.opportunities_found as f64 / minutes
+    }
+    
+    /// Pretty print report
+    pub fn to_report(&self) -> String {
+        let uptime = self.start_time.elapsed().as_secs();
+        format!(
+            "State:          {:?}\n\
+             Uptime:         {}h {:02}m {:02}s\n\
+             Opportunities:  {} found, {} executed, {} skipped\n\
+             Executions:     {} succeeded, {} failed, {} simulated\n\
+             Success rate:   {:.1}%\n\
+             Scan rate:      {:.1}/min\n\
+             Total profit:   {:.6} ({:.6} simulated)",
+            self.state,
+            uptime / 3600,
+            uptime % 3600 / 60,
+            uptime % 60,
+            self.opportunities_found,
+            self.opportunities_executed,
+            self.opportunities_skipped,
+            self.execution_successes,
+            self.execution_failures,
+            self.execution_simulations,
+            self.success_rate(),
+            self.scan_rate_per_minute(),
+            self.total_profit,
+            self.simulated_profit
+        )
+    }
+}
+
+/// Profit overview for a run of the engine
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfitSummary {
+    pub runtime_secs: u64,
+    pub opportunities_found: u64,
+    pub opportunities_executed: u64,
+    pub execution_successes: u64,
+    pub execution_failures: u64,
+    pub total_profit: f64,
+}
+
+impl ProfitSummary {
+    /// Pretty print summary
+    pub fn summary(&self) -> String {
+        format!(
+            "Run time: {}s\n\
+             Opportunities: {} found, {} executed\n\
+             Executions: {} succeeded, {} failed\n\
+             Total profit: {}",
+            self.runtime_secs,
+            self.opportunities_found,
+            self.opportunities_executed,
+            self.execution_successes,
+            self.execution_failures,
+            self.total_profit
+        )
+    }
+}
+
+/// Point-in-time dump of the engine for debugging
+#[derive(Debug, Clone, Serialize)]
+pub struct BotState {
+    pub config_summary: serde_json::Value,
+    pub pool_states: Vec<PoolState>,
+    pub recent_opportunities: Vec<ArbitrageOpportunity>,
+    pub stats: EngineStatsSnapshot,
+    pub sync_history: Vec<SyncEvent>,
+    pub processor_status: HashMap<DexId, ProcessorStatus>,
+}
+
+/// Requests `BotState` from a running engine, which answers between
+/// polls of its main loop
+#[derive(Debug, Clone)]
+pub struct StateHandle {
+    sender: mpsc::Sender<oneshot::Sender<BotState>>,
+}
+
+impl StateHandle {
+    pub async fn export(&self) -> Result<BotState> {
+        let (reply, state) = oneshot::channel();
+        self.sender
+            .send(reply)
+            .await
+            .map_err(|_| BotError::InvalidState("Engine is gone".into()))?;
+        state.await.map_err(|_| BotError::InvalidState("Engine stopped before exporting its state".into()))
+    }
+    
+    /// Export the state and write it to `path` as pretty JSON
+    pub async fn write_to(&self, path: &str) -> Result<()> {
+        let state = self.export().await?;
+        std::fs::write(path, serde_json::to_string_pretty(&state)?)?;
+        info!("State exported to {}", path);
+        Ok(())
+    }
+}
+
+/// Outcome of simulating an opportunity without executing it
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SimulationResult {
+    pub success: bool,
+    pub simulated_profit: Decimal,
+    pub gas_estimate: u64,
+    /// Expected output of each hop
+    pub expected_amounts: Vec<u64>,
+    /// Percent of the expected net profit lost in simulation
+    pub slippage_realized: Decimal,
+    pub warnings: Vec<String>,
+}
+
+/// Outcome of replaying recorded events through the engine
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BacktestReport {
+    pub total_events: usize,
+    pub opportunities_found: u64,
+    pub simulated_executions: u64,
+    pub total_simulated_profit: Decimal,
+    pub avg_detection_latency_ms: u64,
+    pub missed_opportunities: Vec<MissedOpportunity>,
+}
+
+/// Opportunity that was detected during a backtest but not executed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MissedOpportunity {
+    pub opportunity: ArbitrageOpportunity,
+    pub reason: String,
+}
+
+impl BacktestReport {
+    /// Pretty print summary
+    pub fn summary(&self) -> String {
+        format!(
+            "Backtest: {} events\n\
+             Opportunities: {} found, {} simulated, {} missed\n\
+             Simulated profit: {}\n\
+             Avg detection latency: {}ms",
+            self.total_events,
+            self.opportunities_found,
+            self.simulated_executions,
+            self.missed_opportunities.len(),
+            self.total_simulated_profit,
+            self.avg_detection_latency_ms
+        )
     }
 }
 
@@ -44,14 +334,37 @@ impl ArbitrageEngine {
         detector: Box<dyn ArbitrageDetector>,
         executor: Box<dyn TradeExecutor>,
         validator: Box<dyn OpportunityValidator>,
+        validation_config: ValidationConfig,
+        alerter: Alerter,
+        execution_broadcast_capacity: usize,
     ) -> Self {
+        let (execution_sender, _) = broadcast::channel(execution_broadcast_capacity);
+        let (state_sender, state_requests) = mpsc::channel(STATE_REQUEST_CAPACITY);
+        
         Self {
             event_processor,
             detector,
             executor,
             validator,
+            validation_config,
+            alerter,
+            execution_sender,
+            dex_manager: None,
+            sync_orchestrator: None,
+            sync_timeout: Duration::from_secs(SyncConfig::default().emergency_sync_interval_seconds),
+            config_summary: serde_json::Value::Null,
+            metrics: None,
+            pnl_tracker: None,
+            pnl_report_interval: Duration::from_secs(ArbitrageConfig::default().pnl_report_interval_secs),
+            circuit_breaker: CircuitBreaker::default(),
+            config_updates: None,
+            arbitrage_config: None,
+            state_sender,
+            state_requests: Some(state_requests),
             is_running: false,
             stats: EngineStats::default(),
+            last_sync_time: None,
+            recent_opportunities: VecDeque::with_capacity(RECENT_OPPORTUNITIES_LIMIT),
         }
     }
     
@@ -65,7 +378,7 @@ impl ArbitrageEngine {
         }
         
         self.is_running = true;
-        self.stats.start_time = std::time::Instant::now();
+        self.stats.set_state(EngineState::Running);
         
         self.event_processor.start().await?;
         info!("Event processor started");
@@ -78,13 +391,22 @@ impl ArbitrageEngine {
     
     async fn run_main_loop(&mut self) -> Result<()> {
         let mut interval = tokio::time::interval(Duration::from_millis(20)); // 50Hz
+        // A zero period would make tokio's interval panic
+        let report_every = self.pnl_report_interval.max(Duration::from_secs(1));
+        let mut pnl_report = tokio::time::interval_at(tokio::time::Instant::now() + report_every, report_every);
+        let mut arbitrage_updates = self.config_updates.clone();
+        let mut sync_updates = self.config_updates.clone();
+        let mut state_requests = self.state_requests.take();
         
         while self.is_running {
             tokio::select! {
-                _ = interval.tick() => {
-                    let opportunity = self.detector.next_opportunity().await;
-                    // TODO process opportunities
+                _ = interval.tick() => self.poll_once().await,
+                reply = next_state_request(&mut state_requests) => {
+                    let _ = reply.send(self.export_state().await);
                 }
+                _ = pnl_report.tick() => self.report_pnl().await,
+                config = next_config(&mut arbitrage_updates) => self.apply_arbitrage_config(config.arbitrage),
+                config = next_config(&mut sync_updates) => self.apply_sync_config(&config.sync),
                 _ = self.check_shutdown_signal() => {
                     self.stop().await?;
                     self.is_running = false;
@@ -92,18 +414,246 @@ impl ArbitrageEngine {
                 }
             }
         }
+        self.state_requests = state_requests;
         
         Ok(())
     }
     
-    /// Process batch of opportunities
+    /// Swap in reloaded arbitrage settings for the calculator's next scan
+    fn apply_arbitrage_config(&self, config: ArbitrageConfig) {
+        let Some(shared) = &self.arbitrage_config else {
+            return;
+        };
+        info!(
+            "Arbitrage config hot-reloaded: min profit {}, max price impact {}%",
+            config.min_profit_threshold, config.max_price_impact_percent
+        );
+        *shared.write().unwrap_or_else(|e| e.into_inner()) = config;
+    }
+    
+    /// Swap in reloaded sync settings for force syncs and the orchestrator
+    fn apply_sync_config(&mut self, config: &SyncConfig) {
+        self.sync_timeout = Duration::from_secs(config.emergency_sync_interval_seconds);
+        if let Some(sync_orchestrator) = &self.sync_orchestrator {
+            sync_orchestrator.apply_config(config);
+        }
+        info!(
+            "Sync config hot-reloaded: force sync bounded by {:?}, stale sync every {}s, {}s TTL, {} retries",
+            self.sync_timeout, config.stale_sync_interval_seconds, config.state_ttl_seconds, config.max_retries
+        );
+    }
+    
+    /// Log the running P&L, if the engine tracks one
+    async fn report_pnl(&self) {
+        if let Some(pnl_tracker) = &self.pnl_tracker {
+            info!("{}", pnl_tracker.read().await.summary());
+        }
+    }
+    
+    /// Run one detection and execution cycle
+    pub async fn poll_once(&mut self) {
+        let opportunity = self.detector.next_opportunity().await;
+        self.process_opportunity(opportunity).await;
+    }
+    
+    /// Validate and execute a detected opportunity
     async fn process_opportunity(&mut self, opportunity: Option<ArbitrageOpportunity>) {
+        let Some(opportunity) = opportunity else {
+            return;
+        };
+        self.stats.record_opportunity();
+        if let Some(metrics) = &self.metrics {
+            metrics.record_opportunity_found();
+        }
+        if self.recent_opportunities.len() == RECENT_OPPORTUNITIES_LIMIT {
+            self.recent_opportunities.pop_front();
+        }
+        self.recent_opportunities.push_back(opportunity.clone());
+        
+        if !self.is_worth_executing(&opportunity) {
+            self.observe_opportunity_age("skipped", &opportunity);
+            return;
+        }
+        
+        if !self.validator.validate(&opportunity).await {
+            debug!("Opportunity {} rejected by validator", opportunity.path.path_id);
+            self.stats.record_skipped();
+            self.observe_opportunity_age("rejected", &opportunity);
+            return;
+        }
+        
+        if self.circuit_breaker.check() {
+            warn!("Circuit breaker tripped, not executing {}", opportunity.path.path_id);
+            self.observe_opportunity_age("halted", &opportunity);
+            return;
+        }
+        
+        self.stats.record_executed();
+        self.observe_opportunity_age("executed", &opportunity);
+        let result = self.executor.execute(opportunity).await;
+        self.handle_execution_result(result).await;
+    }
+    
+    fn observe_opportunity_age(&self, outcome: &str, opportunity: &ArbitrageOpportunity) {
+        if let Some(metrics) = &self.metrics {
+            metrics.observe_opportunity_age(outcome, now().saturating_sub(opportunity.discovered_at));
+        }
+    }
+    
+    /// Skip opportunities that would expire before landing or are unlikely to be real
+    fn is_worth_executing(&self, opportunity: &ArbitrageOpportunity) -> bool {
+        let max_age_ms = self.validation_config.max_opportunity_age_ms;
+        let remaining_ms = opportunity.time_remaining_ms(max_age_ms).unwrap_or(0);
+        if remaining_ms < self.validation_config.estimated_execution_time_ms {
+            debug!(
+                "Skipping {}: {}ms remaining, execution takes ~{}ms",
+                opportunity.path.path_id, remaining_ms, self.validation_config.estimated_execution_time_ms
+            );
+            return false;
+        }
+        
+        let confidence = opportunity.confidence_score();
+        if confidence < self.validation_config.min_confidence {
+            debug!(
+                "Skipping {}: confidence {:.2} below {:.2}",
+                opportunity.path.path_id, confidence, self.validation_config.min_confidence
+            );
+            return false;
+        }
         
+        true
+    }
+    
+    /// Run a full sync of all DEXs now, bounded by the emergency sync interval.
+    /// Returns the number of pools synced.
+    pub async fn force_sync(&mut self) -> Result<usize> {
+        let sync_orchestrator = self.sync_orchestrator
+            .clone()
+            .ok_or_else(|| BotError::Config("Force sync requires a sync orchestrator".into()))?;
+        
+        let pools_synced = tokio::time::timeout(self.sync_timeout, sync_orchestrator.sync_pools(SyncScope::All))
+            .await
+            .map_err(|_| BotError::Sync("Force sync timed out".into()))??;
+        
+        self.last_sync_time = Some(Instant::now());
+        info!("Force sync completed: {} pools synced", pools_synced);
+        Ok(pools_synced)
+    }
+    
+    /// When the last successful forced sync finished
+    pub fn last_sync_time(&self) -> Option<Instant> {
+        self.last_sync_time
+    }
+    
+    /// Simulate an opportunity through validation and the executor without submitting it
+    pub async fn simulate_opportunity(&self, opportunity: &ArbitrageOpportunity) -> SimulationResult {
+        let mut result = SimulationResult {
+            expected_amounts: opportunity.path.hops.iter().map(|hop| hop.expected_amount_out).collect(),
+            ..Default::default()
+        };
+        
+        if !self.validator.validate(opportunity).await {
+            result.warnings.push("Opportunity failed validation".into());
+        }
+        if opportunity.time_remaining_ms(self.validation_config.max_opportunity_age_ms).is_none() {
+            result.warnings.push("Opportunity has expired".into());
+        }
+        let confidence = opportunity.confidence_score();
+        if confidence < self.validation_config.min_confidence {
+            result.warnings.push(format!("Low confidence score {:.2}", confidence));
+        }
+        
+        match self.executor.simulate(opportunity).await {
+            Ok((gas_used, simulated_profit)) => {
+                result.success = simulated_profit > Decimal::ZERO;
+                result.gas_estimate = gas_used;
+                result.simulated_profit = simulated_profit;
+                if opportunity.net_profit > Decimal::ZERO {
+                    result.slippage_realized = (opportunity.net_profit - simulated_profit)
+                        / opportunity.net_profit
+                        * Decimal::ONE_HUNDRED;
+                }
+            }
+            Err(e) => result.warnings.push(format!("Simulation failed: {}", e)),
+        }
+        
+        result
+    }
+    
+    /// Collect config, pool states, recent opportunities, stats, sync
+    /// history and event processor status
+    pub async fn export_state(&self) -> BotState {
+        let pool_states = match &self.dex_manager {
+            Some(dex_manager) => dex_manager.read().await.all_pools().cloned().collect(),
+            None => Vec::new(),
+        };
+        
+        BotState {
+            config_summary: self.config_summary.clone(),
+            pool_states,
+            recent_opportunities: self.recent_opportunities.iter().cloned().collect(),
+            stats: self.stats.snapshot(),
+            sync_history: self.sync_orchestrator.as_ref().map(|sync| sync.get_sync_history()).unwrap_or_default(),
+            processor_status: self.event_processor.get_status().await,
+        }
+    }
+    
+    /// Handle for reading the state while the engine runs
+    pub fn state_handle(&self) -> StateHandle {
+        StateHandle { sender: self.state_sender.clone() }
+    }
+    
+    /// Subscribe to execution results as they are handled
+    pub fn subscribe_executions(&self) -> broadcast::Receiver<ExecutionResult> {
+        self.execution_sender.subscribe()
     }
     
     /// Handle execution results
     async fn handle_execution_result(&mut self, result: ExecutionResult) {
+        if self.circuit_breaker.record(&result) {
+            let message = format!(
+                "{} consecutive failures, {} consecutive losses",
+                self.circuit_breaker.consecutive_failures, self.circuit_breaker.consecutive_losses
+            );
+            warn!("Circuit breaker tripped: {}", message);
+            self.alerter.alert("Circuit breaker tripped", &message, AlertSeverity::Critical).await;
+        }
+        
+        match result.status {
+            ExecutionStatus::Success => {
+                self.stats.record_execution_success(result.actual_profit);
+                if let Some(metrics) = &self.metrics {
+                    match self.profit_usd(&result).await {
+                        Some(profit_usd) => metrics.add_net_profit_usd(profit_usd.to_f64().unwrap_or(0.0)),
+                        None => warn!(
+                            "Cannot value profit of {} in USD, leaving it out of the profit metric",
+                            result.opportunity.path.path_id
+                        ),
+                    }
+                }
+            }
+            ExecutionStatus::Simulated => self.stats.record_execution_simulated(result.actual_profit),
+            ExecutionStatus::Failed => {
+                self.stats.record_execution_failure();
+                self.alerter
+                    .alert("Execution failed", &result.summary(), AlertSeverity::Warning)
+                    .await;
+            }
+            ExecutionStatus::Pending => {}
+        }
+        
         info!("Execution result: {}", result.summary());
+        
+        // No subscribers is not an error
+        let _ = self.execution_sender.send(result);
+    }
+    
+    /// Profit of `result`, earned in the path's start token, valued in USD
+    /// at current pool prices
+    async fn profit_usd(&self, result: &ExecutionResult) -> Option<Decimal> {
+        let snapshot = self.dex_manager.as_ref()?.read().await.get_state_snapshot().ok()?;
+        let price = snapshot.usd_price(&result.opportunity.path.start_token)?;
+        Some(result.actual_profit * price)
     }
     
     /// Check for shutdown signal
@@ -124,13 +674,106 @@ impl ArbitrageEngine {
         // Stop components
         self.event_processor.stop().await?;
         
-        info!("Final stats: {:?}", self.stats);
+        // Apply events that arrived just before shutdown so pool state stays current
+        let pending = self.event_processor.drain_pending_events();
+        if !pending.is_empty() {
+            info!("Processing {} pending events before shutdown", pending.len());
+        }
+        for (dex_id, event) in pending {
+            if let Err(e) = self.event_processor.process_event(event).await {
+                warn!("Failed to process pending event for DEX {}: {}", dex_id, e);
+            }
+        }
+        
+        self.stats.set_state(EngineState::Stopped);
+        info!("Final stats:\n{}", self.stats.snapshot().to_report());
         Ok(())
     }
     
+    /// Replay recorded events without a WebSocket connection.
+    /// The executor must be configured with `dry_run = true`.
+    pub async fn backtest(&mut self, events: Vec<RawEvent>) -> BacktestReport {
+        info!("Starting backtest with {} events", events.len());
+        
+        let mut report = BacktestReport {
+            total_events: events.len(),
+            ..Default::default()
+        };
+        let mut total_latency_ms = 0u64;
+        
+        for event in events {
+            let event_timestamp = event.timestamp;
+            
+            if let Err(e) = self.event_processor.process_event(event).await {
+                debug!("Skipping event during backtest: {}", e);
+                continue;
+            }
+            
+            while let Some(opportunity) = self.detector.next_opportunity().await {
+                report.opportunities_found += 1;
+                total_latency_ms += opportunity.discovered_at.saturating_sub(event_timestamp);
+                
+                if !self.validator.validate(&opportunity).await {
+                    report.missed_opportunities.push(MissedOpportunity {
+                        opportunity,
+                        reason: "Rejected by validator".into(),
+                    });
+                    continue;
+                }
+                
+                let result = self.executor.execute(opportunity).await;
+                match result.status {
+                    ExecutionStatus::Simulated => {
+                        report.simulated_executions += 1;
+                        report.total_simulated_profit += result.actual_profit;
+                    }
+                    _ => report.missed_opportunities.push(MissedOpportunity {
+                        reason: result.error.clone().unwrap_or_else(|| format!("{:?}", result.status)),
+                        opportunity: result.opportunity,
+                    }),
+                }
+            }
+        }
+        
+        report.avg_detection_latency_ms = total_latency_ms
+            .checked_div(report.opportunities_found)
+            .unwrap_or(0);
+        
+        info!("Backtest complete: {} opportunities found", report.opportunities_found);
+        report
+    }
+    
     /// Get engine statistics
-    pub fn get_stats(&self) -> &EngineStats {
-        &self.stats
+    pub fn get_stats(&self) -> EngineStatsSnapshot {
+        self.stats.snapshot()
+    }
+    
+    /// Shared handle to the live counters, readable while the engine runs
+    pub fn stats_handle(&self) -> EngineStats {
+        self.stats.clone()
+    }
+    
+    /// Summarize profit since the engine was started
+    pub fn profit_summary(&self) -> ProfitSummary {
+        let stats = self.stats.snapshot();
+        ProfitSummary {
+            runtime_secs: stats.start_time.elapsed().as_secs(),
+            opportunities_found: stats.opportunities_found,
+            opportunities_executed: stats.opportunities_executed,
+            execution_successes: stats.execution_successes,
+            execution_failures: stats.execution_failures,
+            total_profit: stats.total_profit.to_f64().unwrap_or(0.0),
+        }
+    }
+    
+    /// Whether execution is halted by the circuit breaker
+    pub fn is_circuit_tripped(&mut self) -> bool {
+        self.circuit_breaker.check()
+    }
+    
+    /// Resume execution after the circuit breaker tripped
+    pub fn reset_circuit_breaker(&mut self) {
+        self.circuit_breaker.reset();
     }
     
     /// Check if engine is running
@@ -145,6 +788,17 @@ pub struct ArbitrageEngineBuilder {
     detector: Option<Box<dyn ArbitrageDetector>>,
     executor: Option<Box<dyn TradeExecutor>>,
     validator: Option<Box<dyn OpportunityValidator>>,
+    validation_config: ValidationConfig,
+    alerter: Alerter,
+    execution_broadcast_capacity: usize,
+    dex_manager: Option<Arc<RwLock<DexManager>>>,
+    sync_orchestrator: Option<Arc<SyncOrchestrator>>,
+    sync_config: SyncConfig,
+    config_summary: serde_json::Value,
+    metrics: Option<Arc<MetricsRegistry>>,
+    pnl_tracker: Option<(Arc<RwLock<PnlTracker>>, Duration)>,
+    circuit_breaker: CircuitBreaker,
+    config_updates: Option<(watch::Receiver<Config>, SharedArbitrageConfig)>,
 }
 
 impl ArbitrageEngineBuilder {
@@ -154,6 +808,17 @@ impl ArbitrageEngineBuilder {
             detector: None,
             executor: None,
             validator: None,
+            validation_config: ValidationConfig::default(),
+            alerter: Alerter::new(),
+            execution_broadcast_capacity: DEFAULT_EXECUTION_BROADCAST_CAPACITY,
+            dex_manager: None,
+            sync_orchestrator: None,
+            sync_config: SyncConfig::default(),
+            config_summary: serde_json::Value::Null,
+            metrics: None,
+            pnl_tracker: None,
+            circuit_breaker: CircuitBreaker::default(),
+            config_updates: None,
         }
     }
     
@@ -177,14 +842,120 @@ impl ArbitrageEngineBuilder {
         self
     }
     
+    pub fn with_validation_config(mut self, config: ValidationConfig) -> Self {
+        self.validation_config = config;
+        self
+    }
+    
+    pub fn with_alerter(mut self, alerter: Alerter) -> Self {
+        self.alerter = alerter;
+        self
+    }
+    
+    pub fn with_execution_broadcast_capacity(mut self, capacity: usize) -> Self {
+        self.execution_broadcast_capacity = capacity;
+        self
+    }
+    
+    pub fn with_dex_manager(mut self, dex_manager: Arc<RwLock<DexManager>>) -> Self {
+        self.dex_manager = Some(dex_manager);
+        self
+    }
+    
+    /// Orchestrator `force_sync` refreshes pools through
+    pub fn with_sync_orchestrator(mut self, sync_orchestrator: Arc<SyncOrchestrator>) -> Self {
+        self.sync_orchestrator = Some(sync_orchestrator);
+        self
+    }
+    
+    pub fn with_sync_config(mut self, config: SyncConfig) -> Self {
+        self.sync_config = config;
+        self
+    }
+    
+    /// Config to include in `export_state`; callers must redact secrets
+    pub fn with_config_summary(mut self, summary: serde_json::Value) -> Self {
+        self.config_summary = summary;
+        self
+    }
+    
+    pub fn with_metrics(mut self, metrics: Arc<MetricsRegistry>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+    
+    /// Log the tracker's summary every `report_interval`
+    pub fn with_pnl_tracker(mut self, pnl_tracker: Arc<RwLock<PnlTracker>>, report_interval: Duration) -> Self {
+        self.pnl_tracker = Some((pnl_tracker, report_interval));
+        self
+    }
+    
+    pub fn with_circuit_breaker(mut self, circuit_breaker: CircuitBreaker) -> Self {
+        self.circuit_breaker = circuit_breaker;
+        self
+    }
+    
+    /// Apply configs received on `config_updates` while running, writing
+    /// arbitrage settings into the calculator's `arbitrage_config`
+    pub fn with_config_updates(
+        mut self,
+        config_updates: watch::Receiver<Config>,
+        arbitrage_config: SharedArbitrageConfig,
+    ) -> Self {
+        self.config_updates = Some((config_updates, arbitrage_config));
+        self
+    }
+    
     pub fn build(self) -> Result<ArbitrageEngine> {
-        Ok(ArbitrageEngine::new(
-            self.event_processor.expect("Event processor is required"),
-            self.detector.expect("Detector is required"),
-            self.executor.expect("Executor is required"),
-            self.validator.expect("Validator is required"),
-        ))
+        let mut engine = ArbitrageEngine::new(
+            self.event_processor.ok_or_else(|| BotError::Config("Event processor is required".into()))?,
+            self.detector.ok_or_else(|| BotError::Config("Detector is required".into()))?,
+            self.executor.ok_or_else(|| BotError::Config("Executor is required".into()))?,
+            self.validator.ok_or_else(|| BotError::Config("Validator is required".into()))?,
+            self.validation_config,
+            self.alerter,
+            self.execution_broadcast_capacity,
+        );
+        engine.dex_manager = self.dex_manager;
+        engine.sync_orchestrator = self.sync_orchestrator;
+        engine.sync_timeout = Duration::from_secs(self.sync_config.emergency_sync_interval_seconds);
+        engine.config_summary = self.config_summary;
+        engine.metrics = self.metrics;
+        engine.circuit_breaker = self.circuit_breaker;
+        if let Some((config_updates, arbitrage_config)) = self.config_updates {
+            engine.config_updates = Some(config_updates);
+            engine.arbitrage_config = Some(arbitrage_config);
+        }
+        if let Some((pnl_tracker, report_interval)) = self.pnl_tracker {
+            engine.pnl_tracker = Some(pnl_tracker);
+            engine.pnl_report_interval = report_interval;
+        }
+        Ok(engine)
+    }
+}
+
+/// Next config sent on `updates`; pends forever once the sender is gone
+async fn next_config(updates: &mut Option<watch::Receiver<Config>>) -> Config {
+    if let Some(receiver) = updates {
+        if receiver.changed().await.is_ok() {
+            return receiver.borrow_and_update().clone();
+        }
+        *updates = None;
+    }
+    std::future::pending().await
+}
+
+/// Next state request; pends forever once every handle is gone
+async fn next_state_request(
+    requests: &mut Option<mpsc::Receiver<oneshot::Sender<BotState>>>,
+) -> oneshot::Sender<BotState> {
+    if let Some(receiver) = requests {
+        if let Some(reply) = receiver.recv().await {
+            return reply;
+        }
+        *requests = None;
     }
+    std::future::pending().await
 }
 
 impl Default for ArbitrageEngineBuilder {
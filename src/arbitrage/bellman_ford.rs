@@ -0,0 +1,275 @@
+use std::collections::{HashMap, HashSet};
+
+use rust_decimal::prelude::ToPrimitive;
+
+use crate::{arbitrage::graph::{PoolEdge, TokenGraph, build_path, token_key}, types::{ArbitragePath, TokenInfo}};
+
+/// Relaxations smaller than this are float noise, not a real improvement
+const RELAXATION_EPSILON: f64 = 1e-12;
+
+/// Find profitable cycles through `start_token` with Bellman-Ford on the
+/// log-price graph. Each edge weighs `-ln(rate)`, so a negative cycle is a
+/// loop whose exchange rates multiply to more than one. Cycles that skip
+/// `start_token` or are longer than `max_hops` are dropped.
+pub fn find_negative_cycles(graph: &TokenGraph, start_token: &TokenInfo, max_hops: usize) -> Vec<ArbitragePath> {
+    let nodes: HashMap<&String, usize> = graph.token_keys().enumerate().map(|(i, key)| (key, i)).collect();
+    let Some(&start) = nodes.get(&token_key(start_token)) else {
+        return Vec::new();
+    };
+
+    let edges: Vec<(usize, usize, f64, &PoolEdge)> = graph
+        .all_edges()
+        .filter_map(|edge| {
+            let from = *nodes.get(&token_key(edge.token_in()))?;
+            let to = *nodes.get(&token_key(edge.token_out()))?;
+            let rate = edge.rate().to_f64().filter(|rate| *rate > 0.0)?;
+            Some((from, to, -rate.ln(), edge))
+        })
+        .collect();
+
+    let node_count = nodes.len();
+    let mut dist = vec![f64::INFINITY; node_count];
+    let mut pred: Vec<Option<usize>> = vec![None; node_count];
+    dist[start] = 0.0;
+
+    for _ in 1..node_count {
+        if !relax(&edges, &mut dist, &mut pred) {
+            return Vec::new();
+        }
+    }
+
+    // Anything still relaxing after V-1 rounds sits on or behind a negative cycle
+    let mut cycles = Vec::new();
+    let mut seen = HashSet::new();
+    for (i, &(from, to, weight, _)) in edges.iter().enumerate() {
+        if dist[from] + weight >= dist[to] - RELAXATION_EPSILON {
+            continue;
+        }
+        dist[to] = dist[from] + weight;
+        pred[to] = Some(i);
+
+        let Some(cycle) = trace_cycle(&edges, &pred, to, node_count) else {
+            continue;
+        };
+        let Some(offset) = cycle.iter().position(|&edge| edges[edge].0 == start) else {
+            continue;
+        };
+        if cycle.len() > max_hops {
+            continue;
+        }
+
+        let mut pool_ids: Vec<&str> = cycle.iter().map(|&edge| edges[edge].3.pool.pool_id.as_str()).collect();
+        pool_ids.sort_unstable();
+        if !seen.insert(pool_ids) {
+            continue;
+        }
+
+        let path_edges: Vec<PoolEdge> = cycle[offset..]
+            .iter()
+            .chain(&cycle[..offset])
+            .map(|&edge| *edges[edge].3)
+            .collect();
+        cycles.push(build_path(start_token, &path_edges));
+    }
+
+    cycles
+}
+
+/// One relaxation round, returns whether any distance improved
+fn relax(edges: &[(usize, usize, f64, &PoolEdge)], dist: &mut [f64], pred: &mut [Option<usize>]) -> bool {
+    let mut relaxed = false;
+    for (i, &(from, to, weight, _)) in edges.iter().enumerate() {
+        if dist[from].is_finite() && dist[from] + weight < dist[to] - RELAXATION_EPSILON {
+            dist[to] = dist[from] + weight;
+            pred[to] = Some(i);
+            relaxed = true;
+        }
+    }
+    relaxed
+}
+
+/// Follow predecessors from `node` into the cycle behind it and return
+/// the cycle's edge indices in swap order
+fn trace_cycle(edges: &[(usize, usize, f64, &PoolEdge)], pred: &[Option<usize>], node: usize, node_count: usize) -> Option<Vec<usize>> {
+    // Walking back V times is guaranteed to land inside the cycle
+    let mut on_cycle = node;
+    for _ in 0..node_count {
+        on_cycle = edges[pred[on_cycle]?].0;
+    }
+
+    let mut cycle = Vec::new();
+    let mut current = on_cycle;
+    loop {
+        let edge = pred[current]?;
+        cycle.push(edge);
+        current = edges[edge].0;
+        if current == on_cycle {
+            break;
+        }
+        if cycle.len() > node_count {
+            return None;
+        }
+    }
+
+    cycle.reverse();
+    Some(cycle)
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use rust_decimal::Decimal;
+
+    use crate::{
+        arbitrage::calculator::{ArbitrageCalculator, DefaultArbitrageCalculator},
+        testing::{snapshot_with, test_pool, test_token, triangle_snapshot},
+        types::{DexId, PoolState, StateSnapshot},
+        utils::config::{ArbitrageConfig, PathAlgorithm},
+    };
+
+    use super::*;
+
+    /// Hub token USDC quoted against a fairly priced ring T1..Tn (Turbos).
+    /// Each USDC/Ti pool (Cetus) prices Ti 1% higher than the last, so every
+    /// profitable loop has to pass through USDC.
+    fn hub(size: usize) -> StateSnapshot {
+        let tokens: Vec<TokenInfo> = (0..=size)
+            .map(|i| if i == 0 { test_token("USDC") } else { test_token(&format!("T{}", i)) })
+            .collect();
+        let mut pools = Vec::new();
+        for i in 1..=size {
+            let next = i % size + 1;
+            pools.push(test_pool(&format!("ring{}", i), DexId::Turbos, &tokens[i], &tokens[next], 1_000_000, 1_000_000));
+            pools.push(test_pool(&format!("hub{}", i), DexId::Cetus, &tokens[0], &tokens[i], 1_000_000, 1_000_000 + 10_000 * i as u64));
+        }
+        snapshot_with(pools)
+    }
+
+    fn calculator(path_algorithm: PathAlgorithm) -> DefaultArbitrageCalculator {
+        DefaultArbitrageCalculator::new(ArbitrageConfig {
+            path_algorithm,
+            ..ArbitrageConfig::default()
+        })
+    }
+    
+    #[tokio::test]
+    async fn test_single_dex_loops_filtered() {
+        let (sui, usdc) = (test_token("SUI"), test_token("USDC"));
+        let snapshot = snapshot_with(vec![
+            test_pool("p1", DexId::Cetus, &sui, &usdc, 1_000_000, 2_000_000),
+            test_pool("p2", DexId::Cetus, &sui, &usdc, 1_000_000, 2_100_000),
+            test_pool("p3", DexId::Turbos, &sui, &usdc, 1_000_000, 2_200_000),
+        ]);
+
+        let paths = calculator(PathAlgorithm::Dfs).find_paths(&sui, 2, &snapshot).await;
+
+        // p1 <-> p2 stays on Cetus, leaving both directions through p3
+        assert_eq!(paths.len(), 4);
+        assert!(paths.iter().all(|path| path.dex_ids().len() == 2));
+    }
+
+    #[tokio::test]
+    async fn test_whitelist_excludes_other_dexes() {
+        let snapshot = triangle_snapshot();
+        let calculator = DefaultArbitrageCalculator::new(ArbitrageConfig {
+            whitelist_dexes: vec![DexId::Cetus],
+            min_dex_count_per_path: 1,
+            ..ArbitrageConfig::default()
+        });
+
+        // The only loop runs through the Turbos pool
+        assert!(calculator.find_paths(&test_token("SUI"), 4, &snapshot).await.is_empty());
+    }
+
+    #[test]
+    fn test_finds_profitable_triangle() {
+        let snapshot = triangle_snapshot();
+        let graph = TokenGraph::from_snapshot(&snapshot, &ArbitrageConfig::default());
+
+        let cycles = find_negative_cycles(&graph, &test_token("SUI"), 4);
+
+        assert_eq!(cycles.len(), 1);
+        let path = &cycles[0];
+        assert!(path.validate().is_ok());
+        assert!(path.is_triangular());
+        assert_eq!(path.path_description(), "SUI → USDC (Cetus) → DEEP (Turbos) → SUI (Cetus)");
+    }
+
+    #[test]
+    fn test_no_cycles_without_mispricing() {
+        let (sui, usdc) = (test_token("SUI"), test_token("USDC"));
+        let snapshot = snapshot_with(vec![
+            test_pool("p1", DexId::Cetus, &sui, &usdc, 1_000_000, 2_000_000),
+            test_pool("p2", DexId::Turbos, &sui, &usdc, 1_000_000, 2_000_000),
+        ]);
+        let graph = TokenGraph::from_snapshot(&snapshot, &ArbitrageConfig::default());
+
+        assert!(find_negative_cycles(&graph, &sui, 4).is_empty());
+    }
+
+    #[test]
+    fn test_cycle_longer_than_max_hops_is_dropped() {
+        let snapshot = triangle_snapshot();
+        let graph = TokenGraph::from_snapshot(&snapshot, &ArbitrageConfig::default());
+
+        assert!(find_negative_cycles(&graph, &test_token("SUI"), 2).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_bellman_ford_cycles_are_among_dfs_paths() {
+        let snapshot = hub(8);
+        let start = test_token("USDC");
+
+        let dfs_paths = calculator(PathAlgorithm::Dfs).find_paths(&start, 9, &snapshot).await;
+        let bf_paths = calculator(PathAlgorithm::BellmanFord).find_paths(&start, 9, &snapshot).await;
+
+        // DFS enumerates every loop, so each negative cycle must be among them
+        assert!(!bf_paths.is_empty());
+        assert!(bf_paths.iter().all(|path| marginal_return(path, &snapshot) > Decimal::ONE));
+        assert!(dfs_paths.len() >= bf_paths.len());
+        for path in &bf_paths {
+            assert!(dfs_paths.iter().any(|candidate| candidate.path_id == path.path_id));
+        }
+    }
+
+    /// Product of the after-fee spot rates along `path`; above one means the
+    /// loop returns more than it started with at the margin
+    fn marginal_return(path: &ArbitragePath, snapshot: &StateSnapshot) -> Decimal {
+        path.hops
+            .iter()
+            .filter_map(|hop| {
+                let pool = snapshot.pools.get(&hop.pool_id)?;
+                let price = if hop.sell_base { pool.spot_price_a_to_b() } else { pool.spot_price_b_to_a() };
+                Some(price * (Decimal::ONE - pool.fee_rate))
+            })
+            .product()
+    }
+
+    proptest! {
+        #[test]
+        fn prop_every_cycle_has_positive_gross_profit(
+            reserves in proptest::collection::vec((100_000..10_000_000u64, 100_000..10_000_000u64), 6),
+            start in 0..4usize,
+        ) {
+            let tokens: Vec<TokenInfo> = ["SUI", "USDC", "DEEP", "CETUS"].iter().map(|symbol| test_token(symbol)).collect();
+            let pairs = [(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)];
+            let pools: Vec<PoolState> = pairs
+                .iter()
+                .zip(&reserves)
+                .enumerate()
+                .map(|(i, (&(a, b), &(reserve_a, reserve_b)))| {
+                    let dex_id = if i % 2 == 0 { DexId::Cetus } else { DexId::Turbos };
+                    test_pool(&format!("p{}", i), dex_id, &tokens[a], &tokens[b], reserve_a, reserve_b)
+                })
+                .collect();
+            let snapshot = snapshot_with(pools);
+
+            let calculator = DefaultArbitrageCalculator::new(ArbitrageConfig::default());
+            for path in calculator.find_negative_cycles(&snapshot, &tokens[start]) {
+                prop_assert!(path.validate().is_ok());
+                prop_assert!(path.is_closed_loop());
+                prop_assert!(marginal_return(&path, &snapshot) > Decimal::ONE);
+            }
+        }
+    }
+}
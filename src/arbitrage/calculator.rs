@@ -1,6 +1,11 @@
+use std::{collections::HashSet, sync::{Arc, Mutex, RwLock, RwLockReadGuard, atomic::{AtomicU64, Ordering}}, time::Duration};
+
 use async_trait::async_trait;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+use tracing::debug;
 
-use crate::{types::{ArbitrageOpportunity, ArbitragePath, Result, StateSnapshot, TokenInfo}, utils::config::ArbitrageConfig};
+use crate::{arbitrage::{bellman_ford, graph::{PoolEdge, TokenGraph, build_path, token_key}}, types::{ArbitrageOpportunity, ArbitragePath, BotError, Result, StateSnapshot, TokenInfo, now}, utils::{config::{ArbitrageConfig, DEFAULT_BASE_GAS_PER_SWAP, PathAlgorithm}, math::{BPS_DENOMINATOR, calculate_optimal_input_amount, max_input_within_impact, simulate_multi_hop}}};
 
 #[async_trait]
 pub trait ArbitrageCalculator: Send + Sync {
@@ -10,33 +15,426 @@ pub trait ArbitrageCalculator: Send + Sync {
     async fn calculate_profitability(&self, path: &ArbitragePath, snapshot: &StateSnapshot) -> Result<ArbitrageOpportunity>;
     async fn find_paths(&self, start_token: &TokenInfo, max_hops: usize, snapshot: &StateSnapshot) -> Vec<ArbitragePath>;
     async fn validate_opportunity(&self, opportunity: &ArbitrageOpportunity, snapshot: &StateSnapshot) -> bool;
+    
+    /// Number of scans cut short by the scanner timeout
+    fn scanner_timeouts(&self) -> u64 {
+        0
+    }
 }
 
+/// Arbitrage settings shared with whoever hot-reloads them
+pub type SharedArbitrageConfig = Arc<RwLock<ArbitrageConfig>>;
+
 pub struct DefaultArbitrageCalculator {
-    config: ArbitrageConfig,
+    config: SharedArbitrageConfig,
+    scanner_timeouts: AtomicU64,
+    /// Gas cost of one swap hop in MIST
+    base_gas_per_swap: u64,
 }
 
 impl DefaultArbitrageCalculator {
     pub fn new(config: ArbitrageConfig) -> Self {
-        Self { config }
+        Self::with_shared_config(Arc::new(RwLock::new(config)))
+    }
+    
+    /// Calculator reading its settings from `config` on every scan, so
+    /// updates take effect without rebuilding it
+    pub fn with_shared_config(config: SharedArbitrageConfig) -> Self {
+        Self {
+            config,
+            scanner_timeouts: AtomicU64::new(0),
+            base_gas_per_swap: DEFAULT_BASE_GAS_PER_SWAP,
+        }
+    }
+    
+    pub fn config_handle(&self) -> SharedArbitrageConfig {
+        self.config.clone()
+    }
+    
+    /// Current settings; never hold across an await
+    fn config(&self) -> RwLockReadGuard<'_, ArbitrageConfig> {
+        self.config.read().unwrap_or_else(|e| e.into_inner())
+    }
+    
+    /// Price gas at `base_gas_per_swap` MIST per hop
+    pub fn with_base_gas_per_swap(mut self, base_gas_per_swap: u64) -> Self {
+        self.base_gas_per_swap = base_gas_per_swap;
+        self
+    }
+    
+    /// Gas for `hop_count` swaps, in units of `token`. Tokens other than SUI
+    /// are converted at the spot price of the deepest SUI pool they trade in.
+    fn estimate_gas_cost(&self, hop_count: usize, token: &TokenInfo, snapshot: &StateSnapshot) -> Result<Decimal> {
+        let sui = TokenInfo::sui();
+        let gas_sui = sui.to_decimal(self.base_gas_per_swap.saturating_mul(hop_count as u64));
+        if token.matches(&sui) {
+            return Ok(gas_sui);
+        }
+        
+        let sui_price = snapshot
+            .price_in(&sui, token)
+            .ok_or_else(|| BotError::Execution(format!("No SUI/{} pool to price gas", token.symbol)))?;
+        
+        Ok(gas_sui * sui_price)
+    }
+    
+    /// Profitable cycles through `start_token` found with Bellman-Ford,
+    /// regardless of the configured `path_algorithm`. O(V·E) rather than
+    /// the exponential DFS, at the cost of one cycle per relaxing edge.
+    pub fn find_negative_cycles(&self, snapshot: &StateSnapshot, start_token: &TokenInfo) -> Vec<ArbitragePath> {
+        let config = self.config();
+        let graph = TokenGraph::from_snapshot(snapshot, &config);
+        bellman_ford::find_negative_cycles(&graph, start_token, config.max_hops)
+    }
+    
+    /// Walk the path graph, pushing each profitable opportunity into `found`
+    /// as soon as it is confirmed so a timed-out scan keeps partial results
+    async fn scan_opportunities(&self, snapshot: &StateSnapshot, found: &Mutex<Vec<ArbitrageOpportunity>>) {
+        let (max_hops, min_profit_threshold, min_profit_percent) = {
+            let config = self.config();
+            (config.max_hops, config.min_profit_threshold, config.min_profit_percent)
+        };
+        for token in snapshot.tokens.values() {
+            for path in self.find_paths(token, max_hops, snapshot).await {
+                let opportunity = match self.calculate_profitability(&path, snapshot).await {
+                    Ok(opportunity) => opportunity,
+                    Err(e) => {
+                        debug!("Skipping path {}: {}", path.path_id, e);
+                        continue;
+                    }
+                };
+                if opportunity.net_profit < min_profit_threshold
+                    || opportunity.net_profit_percent < min_profit_percent
+                {
+                    continue;
+                }
+                
+                let mut found = found.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+                insert_deduplicated(&mut found, opportunity);
+            }
+        }
+    }
+    
+    /// `(reserve_in, reserve_out, fee_bps)` of each hop's pool in swap direction
+    fn hop_reserves(&self, path: &ArbitragePath, snapshot: &StateSnapshot) -> Result<Vec<(u64, u64, u32)>> {
+        path.hops
+            .iter()
+            .map(|hop| {
+                let pool = snapshot.pools.get(&hop.pool_id).ok_or_else(|| BotError::InsufficientLiquidity {
+                    pool_id: hop.pool_id.clone(),
+                })?;
+                Ok(pool.swap_params(hop.sell_base))
+            })
+            .collect()
+    }
+    
+    /// Slippage allowed on each hop, from the profit tier the opportunity falls into
+    fn slippage_for(&self, profit_percent: Decimal) -> Decimal {
+        self.config()
+            .profit_target_for(profit_percent)
+            .map(|target| target.max_slippage_percent)
+            .unwrap_or(Decimal::ZERO)
     }
 }
 
 #[async_trait]
 impl ArbitrageCalculator for DefaultArbitrageCalculator {
-    async fn find_opportunities(&self, _snapshot: &StateSnapshot) -> Vec<ArbitrageOpportunity> {
-        todo!("Implement opportunity finding logic")
+    async fn find_opportunities(&self, snapshot: &StateSnapshot) -> Vec<ArbitrageOpportunity> {
+        let found = Mutex::new(Vec::new());
+        let timeout_ms = self.config().simulate_timeout_ms;
+        
+        let scan = self.scan_opportunities(snapshot, &found);
+        let timed_out = tokio::time::timeout(Duration::from_millis(timeout_ms), scan).await.is_err();
+        
+        let mut opportunities = found.into_inner().unwrap_or_else(|poisoned| poisoned.into_inner());
+        opportunities.sort_by_key(|opportunity| std::cmp::Reverse(opportunity.net_profit));
+        if timed_out {
+            self.scanner_timeouts.fetch_add(1, Ordering::Relaxed);
+            debug!(
+                "Scanner timed out after {}ms, returning {} partial opportunities",
+                timeout_ms, opportunities.len()
+            );
+        }
+        opportunities
     }
     
-    async fn calculate_profitability(&self, _path: &ArbitragePath, _snapshot: &StateSnapshot) -> Result<ArbitrageOpportunity> {
-        todo!("Calculate fees, gas, slippage for exact profit numbers")
+    async fn calculate_profitability(&self, path: &ArbitragePath, snapshot: &StateSnapshot) -> Result<ArbitrageOpportunity> {
+        let reserves = self.hop_reserves(path, snapshot)?;
+        let first_reserve_in = reserves.first().map(|(reserve_in, _, _)| *reserve_in).unwrap_or(0);
+        
+        let max_price_impact_percent = self.config().max_price_impact_percent;
+        let max_amount = max_input_within_impact(&reserves, first_reserve_in, max_price_impact_percent);
+        let initial_amount = calculate_optimal_input_amount(&reserves, 1, max_amount);
+        let final_amount = simulate_multi_hop(&reserves, initial_amount, max_price_impact_percent)?;
+        
+        let initial = path.start_token.to_decimal(initial_amount);
+        let gross_profit = path.end_token.to_decimal(final_amount) - initial;
+        let profit_percent = if initial.is_zero() {
+            Decimal::ZERO
+        } else {
+            gross_profit / initial * Decimal::ONE_HUNDRED
+        };
+        
+        let mut priced = path.with_optimal_amounts(initial_amount, &snapshot.pools, self.slippage_for(profit_percent))?;
+        priced.validate_amounts()?;
+        priced.calculated_at = snapshot.timestamp;
+        
+        // Swap outputs are already net of DEX fees, so only gas is deducted;
+        // the fees are reported for the breakdown
+        let total_dex_fees = priced.total_fees();
+        let estimated_gas_cost = self.estimate_gas_cost(priced.hop_count(), &priced.start_token, snapshot)?;
+        let net_profit = gross_profit - estimated_gas_cost;
+        if net_profit < Decimal::ZERO {
+            return Err(BotError::Execution(format!(
+                "Path {} loses {} {} after gas", path.path_id, -net_profit, path.start_token.symbol
+            )));
+        }
+        
+        Ok(ArbitrageOpportunity {
+            path: priced,
+            gross_profit,
+            estimated_gas_cost,
+            total_dex_fees,
+            net_profit,
+            net_profit_percent: net_profit.checked_div(initial).unwrap_or(Decimal::ZERO) * Decimal::ONE_HUNDRED,
+            discovered_at: now(),
+        })
     }
     
-    async fn find_paths(&self, _start_token: &TokenInfo, _max_hops: usize, _snapshot: &StateSnapshot) -> Vec<ArbitragePath> {
-        todo!("Explore all possible paths from starting token using snapshot")
+    async fn find_paths(&self, start_token: &TokenInfo, max_hops: usize, snapshot: &StateSnapshot) -> Vec<ArbitragePath> {
+        let config = self.config();
+        let graph = TokenGraph::from_snapshot(snapshot, &config);
+        
+        let paths = match config.path_algorithm {
+            PathAlgorithm::Dfs => find_paths_dfs(&graph, start_token, max_hops),
+            PathAlgorithm::BellmanFord => bellman_ford::find_negative_cycles(&graph, start_token, max_hops),
+        };
+        
+        paths
+            .into_iter()
+            .filter(|path| path.dex_ids().len() >= config.min_dex_count_per_path)
+            .collect()
     }
     
     async fn validate_opportunity(&self, _opportunity: &ArbitrageOpportunity, _snapshot: &StateSnapshot) -> bool {
         todo!("Check if opportunity still exists with current prices in snapshot")
     }
-}
\ No newline at end of file
+    
+    fn scanner_timeouts(&self) -> u64 {
+        self.scanner_timeouts.load(Ordering::Relaxed)
+    }
+}
+
+/// Add `opportunity` unless a path over the same pools is already known,
+/// keeping whichever of the two is more profitable
+fn insert_deduplicated(found: &mut Vec<ArbitrageOpportunity>, opportunity: ArbitrageOpportunity) {
+    let signature = opportunity.path.pool_signature();
+    match found.iter_mut().find(|known| known.path.pool_signature() == signature) {
+        Some(known) if known.net_profit < opportunity.net_profit => *known = opportunity,
+        Some(_) => {}
+        None => found.push(opportunity),
+    }
+}
+
+/// Enumerate every closed loop from `start_token` of at most `max_hops`
+/// hops, never revisiting a token or reusing a pool along the way
+fn find_paths_dfs(graph: &TokenGraph, start_token: &TokenInfo, max_hops: usize) -> Vec<ArbitragePath> {
+    let start_key = token_key(start_token);
+    let mut search = DfsSearch {
+        graph,
+        start_token,
+        max_hops,
+        visited: HashSet::from([start_key.clone()]),
+        stack: Vec::new(),
+        paths: Vec::new(),
+        start_key,
+    };
+    
+    search.extend(start_token);
+    search.paths
+}
+
+/// State of one depth-first path search
+struct DfsSearch<'g, 'a> {
+    graph: &'g TokenGraph<'a>,
+    start_token: &'g TokenInfo,
+    start_key: String,
+    max_hops: usize,
+    visited: HashSet<String>,
+    stack: Vec<PoolEdge<'a>>,
+    paths: Vec<ArbitragePath>,
+}
+
+impl<'g, 'a> DfsSearch<'g, 'a> {
+    fn extend(&mut self, current: &TokenInfo) {
+        for edge in self.graph.edges_from(current) {
+            if self.stack.iter().any(|hop| hop.pool.pool_id == edge.pool.pool_id) {
+                continue;
+            }
+            
+            let next_key = token_key(edge.token_out());
+            self.stack.push(*edge);
+            if next_key == self.start_key {
+                self.paths.push(build_path(self.start_token, &self.stack));
+            } else if self.stack.len() < self.max_hops && self.visited.insert(next_key.clone()) {
+                self.extend(edge.token_out());
+                self.visited.remove(&next_key);
+            }
+            self.stack.pop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal::Decimal;
+
+    use crate::{testing::{snapshot_with, test_pool, test_token, triangle_snapshot}, types::DexId};
+
+    use super::*;
+
+    fn calculator() -> DefaultArbitrageCalculator {
+        DefaultArbitrageCalculator::new(ArbitrageConfig::default())
+    }
+
+    #[tokio::test]
+    async fn test_dfs_finds_triangle_both_ways() {
+        let snapshot = triangle_snapshot();
+        let sui = test_token("SUI");
+
+        let paths = calculator().find_paths(&sui, 3, &snapshot).await;
+
+        // The triangle in either direction, each a valid closed loop
+        assert_eq!(paths.len(), 2);
+        for path in &paths {
+            assert!(path.validate().is_ok());
+            assert!(path.is_triangular());
+            assert!(path.hops.iter().all(|hop| hop.amount_in == 0));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dfs_respects_max_hops() {
+        let snapshot = triangle_snapshot();
+        assert!(calculator().find_paths(&test_token("SUI"), 2, &snapshot).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_shallow_pools_are_skipped() {
+        let mut snapshot = triangle_snapshot();
+        if let Some(pool) = snapshot.pools.get_mut("p2") {
+            // 5_000 USDC, below the 10_000 default
+            pool.reserve_a = Decimal::from(5_000_000_000_000u64);
+        }
+
+        assert!(calculator().find_paths(&test_token("SUI"), 3, &snapshot).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_liquidity_valued_in_usd() {
+        let (sui, usdc, wbtc) = (test_token("SUI"), test_token("USDC"), test_token("WBTC"));
+        // SUI at 2 USDC and WBTC at 30_000 SUI: 40 WBTC is worth 2.4M USD
+        let snapshot = snapshot_with(vec![
+            test_pool("p1", DexId::Cetus, &sui, &usdc, 1_000_000, 2_000_000),
+            test_pool("p2", DexId::Turbos, &usdc, &wbtc, 2_400_000, 40),
+            test_pool("p3", DexId::Cetus, &wbtc, &sui, 40, 1_200_000),
+        ]);
+
+        assert_eq!(calculator().find_paths(&sui, 3, &snapshot).await.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_unpriced_pools_are_skipped() {
+        // No stablecoin to value DEEP or SUI against
+        let (sui, deep) = (test_token("SUI"), test_token("DEEP"));
+        let snapshot = snapshot_with(vec![test_pool("p1", DexId::Cetus, &sui, &deep, 1_000_000, 2_000_000)]);
+
+        assert!(calculator().find_paths(&sui, 2, &snapshot).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_find_opportunities_surfaces_mispriced_triangle() {
+        let snapshot = triangle_snapshot();
+
+        let opportunities = calculator().find_opportunities(&snapshot).await;
+
+        // Found from each of the three tokens, kept once
+        assert_eq!(opportunities.len(), 1);
+        let opportunity = &opportunities[0];
+        assert!(opportunity.net_profit > Decimal::ZERO);
+        assert!(opportunity.path.is_triangular());
+        assert!(opportunity.path.hops.iter().all(|hop| hop.amount_in > 0));
+        assert_eq!(opportunity.path.initial_amount, opportunity.path.hops[0].amount_in);
+    }
+
+    #[tokio::test]
+    async fn test_find_opportunities_empty_when_fairly_priced() {
+        let mut snapshot = triangle_snapshot();
+        if let Some(pool) = snapshot.pools.get_mut("p3") {
+            // DEEP/SUI back in line with SUI -> USDC -> DEEP
+            pool.reserve_b = Decimal::from(1_000_000_000_000_000u64);
+        }
+
+        assert!(calculator().find_opportunities(&snapshot).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_opportunities_sorted_by_net_profit() {
+        let mut snapshot = triangle_snapshot();
+        let (sui, usdc) = (test_token("SUI"), test_token("USDC"));
+        // A second, smaller SUI/USDC mispricing against p1
+        snapshot.pools.insert("p4".into(), test_pool("p4", DexId::Turbos, &sui, &usdc, 1_000_000, 2_050_000));
+
+        let opportunities = calculator().find_opportunities(&snapshot).await;
+
+        assert!(opportunities.len() > 1);
+        assert!(opportunities.windows(2).all(|pair| pair[0].net_profit >= pair[1].net_profit));
+    }
+
+    async fn sui_triangle_path(calculator: &DefaultArbitrageCalculator, snapshot: &StateSnapshot) -> Option<ArbitragePath> {
+        let paths = calculator.find_paths(&test_token("SUI"), 3, snapshot).await;
+        // The profitable direction starts through p1
+        paths.into_iter().find(|path| path.hops[0].pool_id == "p1")
+    }
+
+    #[tokio::test]
+    async fn test_profitability_deducts_gas() -> Result<()> {
+        let snapshot = triangle_snapshot();
+        let calculator = calculator();
+        let path = sui_triangle_path(&calculator, &snapshot).await
+            .ok_or_else(|| BotError::NotFound("triangle path".into()))?;
+
+        let opportunity = calculator.calculate_profitability(&path, &snapshot).await?;
+
+        // Three hops at 500_000 MIST
+        assert_eq!(opportunity.estimated_gas_cost, Decimal::new(15, 4));
+        assert_eq!(opportunity.net_profit, opportunity.gross_profit - opportunity.estimated_gas_cost);
+        assert_eq!(opportunity.total_dex_fees, opportunity.path.total_fees());
+        assert!(opportunity.gross_profit > Decimal::ZERO);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_gas_priced_in_start_token() -> Result<()> {
+        let snapshot = triangle_snapshot();
+
+        // p1 prices SUI at 2 USDC
+        let gas = calculator().estimate_gas_cost(2, &test_token("USDC"), &snapshot)?;
+        assert_eq!(gas, Decimal::new(2, 3));
+
+        assert!(calculator().estimate_gas_cost(2, &test_token("WAL"), &snapshot).is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_unprofitable_after_gas_is_error() -> Result<()> {
+        let snapshot = triangle_snapshot();
+        let calculator = calculator().with_base_gas_per_swap(u64::MAX / 4);
+        let path = sui_triangle_path(&calculator, &snapshot).await
+            .ok_or_else(|| BotError::NotFound("triangle path".into()))?;
+
+        let result = calculator.calculate_profitability(&path, &snapshot).await;
+        assert!(matches!(result, Err(BotError::Execution(_))));
+        Ok(())
+    }
+}
@@ -0,0 +1,144 @@
+//! Halts execution after a run of failed or losing trades
+
+use std::time::{Duration, Instant};
+
+use rust_decimal::Decimal;
+
+use crate::{
+    types::{ExecutionResult, ExecutionStatus},
+    utils::config::ExecutionConfig,
+};
+
+#[derive(Debug, Clone)]
+pub struct CircuitBreaker {
+    pub consecutive_failures: u32,
+    pub consecutive_losses: u32,
+    /// Consecutive failed executions that trip the breaker
+    pub max_consecutive_failures: u32,
+    /// Consecutive losing executions that trip the breaker
+    pub max_consecutive_losses: u32,
+    pub is_tripped: bool,
+    /// Close again on its own after this long; `None` waits for `reset`
+    pub cooldown: Option<Duration>,
+    tripped_at: Option<Instant>,
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        Self::from_config(&ExecutionConfig::default())
+    }
+}
+
+impl CircuitBreaker {
+    pub fn new(max_consecutive_failures: u32, max_consecutive_losses: u32) -> Self {
+        Self {
+            consecutive_failures: 0,
+            consecutive_losses: 0,
+            max_consecutive_failures,
+            max_consecutive_losses,
+            is_tripped: false,
+            cooldown: None,
+            tripped_at: None,
+        }
+    }
+
+    pub fn from_config(config: &ExecutionConfig) -> Self {
+        let breaker = Self::new(config.max_consecutive_failures, config.max_consecutive_losses);
+        match config.circuit_breaker_cooldown_secs {
+            0 => breaker,
+            secs => breaker.with_cooldown(Duration::from_secs(secs)),
+        }
+    }
+
+    pub fn with_cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = Some(cooldown);
+        self
+    }
+
+    /// Count an execution outcome. Returns true when this result tripped the breaker.
+    pub fn record(&mut self, result: &ExecutionResult) -> bool {
+        match result.status {
+            ExecutionStatus::Failed => self.consecutive_failures += 1,
+            ExecutionStatus::Success | ExecutionStatus::Simulated => self.consecutive_failures = 0,
+            ExecutionStatus::Pending => return false,
+        }
+        if result.actual_profit < Decimal::ZERO {
+            self.consecutive_losses += 1;
+        } else if result.status != ExecutionStatus::Failed {
+            self.consecutive_losses = 0;
+        }
+
+        let should_trip = self.consecutive_failures >= self.max_consecutive_failures
+            || self.consecutive_losses >= self.max_consecutive_losses;
+        if should_trip && !self.is_tripped {
+            self.is_tripped = true;
+            self.tripped_at = Some(Instant::now());
+            return true;
+        }
+        false
+    }
+
+    /// Whether execution is halted, closing the breaker first if its cooldown has passed
+    pub fn check(&mut self) -> bool {
+        if let (Some(cooldown), Some(tripped_at)) = (self.cooldown, self.tripped_at)
+            && self.is_tripped
+            && tripped_at.elapsed() >= cooldown
+        {
+            self.reset();
+        }
+        self.is_tripped
+    }
+
+    pub fn reset(&mut self) {
+        self.consecutive_failures = 0;
+        self.consecutive_losses = 0;
+        self.is_tripped = false;
+        self.tripped_at = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{testing::sample_opportunity, types::BotError};
+
+    use super::*;
+
+    fn loss() -> ExecutionResult {
+        ExecutionResult::new(sample_opportunity()).simulated(Default::default(), 0, Decimal::NEGATIVE_ONE)
+    }
+
+    fn failure() -> ExecutionResult {
+        ExecutionResult::new(sample_opportunity()).failure(BotError::Execution("reverted".into()))
+    }
+
+    fn win() -> ExecutionResult {
+        ExecutionResult::new(sample_opportunity()).simulated(Default::default(), 0, Decimal::ONE)
+    }
+
+    #[test]
+    fn test_trips_on_consecutive_losses() {
+        let mut breaker = CircuitBreaker::new(5, 2);
+
+        assert!(!breaker.record(&loss()));
+        assert!(!breaker.record(&win()));
+        assert!(!breaker.record(&loss()));
+        assert!(breaker.record(&loss()));
+        assert!(breaker.check());
+
+        breaker.reset();
+        assert!(!breaker.check());
+    }
+
+    #[test]
+    fn test_trips_on_consecutive_failures_and_cools_down() {
+        let mut breaker = CircuitBreaker::new(2, 5).with_cooldown(Duration::ZERO);
+
+        breaker.record(&failure());
+        assert!(breaker.record(&failure()));
+        assert!(breaker.is_tripped);
+
+        // A zero cooldown closes on the next check
+        assert!(!breaker.check());
+        assert_eq!(breaker.consecutive_failures, 0);
+    }
+}
@@ -1,10 +1,10 @@
-use std::sync::{Arc};
-use tokio::sync::{mpsc, RwLock};
+use std::{collections::{HashMap, HashSet}, sync::Arc, time::{Duration, Instant}};
+use tokio::sync::{broadcast, mpsc, RwLock};
 
 use async_trait::async_trait;
-use tracing::info;
+use tracing::{info, warn};
 
-use crate::{arbitrage::calculator::ArbitrageCalculator, dex::manager::DexManager, types::{ArbitrageOpportunity, Result}};
+use crate::{arbitrage::calculator::ArbitrageCalculator, dex::manager::DexManager, types::{ArbitrageOpportunity, BotError, PoolEvent, PoolId, Result, StateSnapshot, Timestamp, now}, utils::config::ArbitrageConfig};
 
 #[async_trait]
 pub trait ArbitrageDetector: Send + Sync {
@@ -18,6 +18,31 @@ pub struct DetectionStats {
     pub opportunities_found: u64,
     pub avg_scan_duration_ms: u64,
     pub last_scan_timestamp: u64,
+    pub snapshot_cache_hits: u64,
+    pub snapshot_cache_misses: u64,
+    pub scanner_timeouts: u64,
+    /// Opportunities dropped as repeats within the duplicate window
+    pub deduplicated_count: u64,
+}
+
+/// Opportunities waiting between the event processor and the detector
+pub const OPPORTUNITY_CHANNEL_CAPACITY: usize = 256;
+
+/// Sending side of the detector's opportunity queue, handed to whatever
+/// discovers opportunities (the event processor after each swap)
+#[derive(Clone)]
+pub struct ArbitrageDetectorHandle {
+    sender: mpsc::Sender<ArbitrageOpportunity>,
+}
+
+impl ArbitrageDetectorHandle {
+    /// Queue an opportunity without waiting; fails when the detector is
+    /// gone or its queue is full
+    pub fn try_send(&self, opportunity: ArbitrageOpportunity) -> Result<()> {
+        self.sender
+            .try_send(opportunity)
+            .map_err(|e| BotError::InvalidState(format!("Cannot queue opportunity: {}", e)))
+    }
 }
 
 pub struct DefaultArbitrageDetector {
@@ -25,14 +50,28 @@ pub struct DefaultArbitrageDetector {
     calculator: Box<dyn ArbitrageCalculator>,
     is_running: bool,
     stats: DetectionStats,
+    previous_snapshot: Option<StateSnapshot>,
+    pool_events: broadcast::Receiver<PoolEvent>,
+    pending_rescan: HashSet<PoolId>,
+    full_rescan_needed: bool,
+    last_snapshot: Option<(Instant, StateSnapshot)>,
+    max_snapshot_age: Duration,
+    opportunities: mpsc::Receiver<ArbitrageOpportunity>,
+    /// Pool signature of each recently returned opportunity, with when it was seen
+    seen_opportunities: HashMap<String, Timestamp>,
+    duplicate_window_ms: u64,
 }
 
 impl DefaultArbitrageDetector {
+    /// Detector plus the handle used to feed it opportunities
     pub fn new(
         dex_manager: Arc<RwLock<DexManager>>,
         calculator: Box<dyn ArbitrageCalculator>,
-    ) -> Self {        
-        Self {
+        pool_events: broadcast::Receiver<PoolEvent>,
+        config: &ArbitrageConfig,
+    ) -> (Self, ArbitrageDetectorHandle) {
+        let (sender, opportunities) = mpsc::channel(OPPORTUNITY_CHANNEL_CAPACITY);
+        let detector = Self {
             dex_manager,
             calculator,
             is_running: false,
@@ -41,19 +80,171 @@ impl DefaultArbitrageDetector {
                 opportunities_found: 0,
                 avg_scan_duration_ms: 0,
                 last_scan_timestamp: 0,
+                snapshot_cache_hits: 0,
+                snapshot_cache_misses: 0,
+                scanner_timeouts: 0,
+                deduplicated_count: 0,
             },
+            previous_snapshot: None,
+            pool_events,
+            pending_rescan: HashSet::new(),
+            full_rescan_needed: true,
+            last_snapshot: None,
+            max_snapshot_age: Duration::from_millis(config.max_snapshot_age_ms),
+            opportunities,
+            seen_opportunities: HashMap::new(),
+            duplicate_window_ms: config.duplicate_window_ms,
+        };
+        (detector, ArbitrageDetectorHandle { sender })
+    }
+    
+    /// Return the cached snapshot while fresh, otherwise take a new one
+    /// from the DexManager. Avoids taking the read lock on every tick.
+    pub async fn prefetch_snapshot(&mut self) -> Result<&StateSnapshot> {
+        let is_fresh = self.last_snapshot
+            .as_ref()
+            .is_some_and(|(taken_at, _)| taken_at.elapsed() < self.max_snapshot_age);
+        
+        if is_fresh {
+            self.stats.snapshot_cache_hits += 1;
+        } else {
+            self.stats.snapshot_cache_misses += 1;
+            let snapshot = self.dex_manager.read().await.get_state_snapshot()?;
+            self.last_snapshot = Some((Instant::now(), snapshot));
         }
+        
+        self.last_snapshot
+            .as_ref()
+            .map(|(_, snapshot)| snapshot)
+            .ok_or_else(|| BotError::InvalidState("Snapshot cache empty".into()))
+    }
+    
+    /// Drop the cached snapshot so the next scan sees the latest state
+    fn invalidate_snapshot(&mut self) {
+        self.last_snapshot = None;
+    }
+    
+    /// Collect pools updated since the last scan from the pool event channel
+    fn drain_pool_events(&mut self) {
+        loop {
+            match self.pool_events.try_recv() {
+                Ok(PoolEvent::StateUpdated(delta)) => {
+                    if delta.is_significant() {
+                        self.pending_rescan.insert(delta.pool_id);
+                        self.invalidate_snapshot();
+                    }
+                }
+                Ok(PoolEvent::Registered(..) | PoolEvent::Removed(_)) => {
+                    self.full_rescan_needed = true;
+                    self.invalidate_snapshot();
+                }
+                Ok(PoolEvent::MarkedStale(_)) => {}
+                Err(broadcast::error::TryRecvError::Lagged(skipped)) => {
+                    warn!("Detector lagged behind {} pool events, scheduling full rescan", skipped);
+                    self.full_rescan_needed = true;
+                    self.invalidate_snapshot();
+                }
+                Err(_) => break,
+            }
+        }
+    }
+    
+    /// Whether an opportunity over the same pools was returned within the
+    /// duplicate window; otherwise remember this one
+    fn is_duplicate(&mut self, opportunity: &ArbitrageOpportunity) -> bool {
+        let now = now();
+        let window = self.duplicate_window_ms;
+        self.seen_opportunities.retain(|_, seen_at| now.saturating_sub(*seen_at) < window);
+        
+        let signature = opportunity.path.pool_signature();
+        if self.seen_opportunities.contains_key(&signature) {
+            return true;
+        }
+        self.seen_opportunities.insert(signature, now);
+        false
+    }
+    
+    /// Pools that changed since the previous scan, so only paths
+    /// touching them need to be re-evaluated. Returns `None` when
+    /// there is no previous snapshot and a full scan is required.
+    fn changed_pools(&mut self, snapshot: &StateSnapshot) -> Option<HashSet<PoolId>> {
+        let changed = self.previous_snapshot
+            .as_ref()
+            .map(|previous| previous.diff(snapshot).affected_pools().into_iter().collect());
+        
+        self.previous_snapshot = Some(snapshot.clone());
+        changed
     }
 }
 
 #[async_trait]
 impl ArbitrageDetector for DefaultArbitrageDetector {
     async fn next_opportunity(&mut self) -> Option<ArbitrageOpportunity> {
-        // todo!("Receive next opportunity from channel");
+        self.drain_pool_events();
+        while let Ok(opportunity) = self.opportunities.try_recv() {
+            if self.is_duplicate(&opportunity) {
+                self.stats.deduplicated_count += 1;
+                continue;
+            }
+            self.stats.opportunities_found += 1;
+            return Some(opportunity);
+        }
         None
     }
     
     fn get_stats(&self) -> DetectionStats {
-        self.stats.clone()
+        DetectionStats {
+            scanner_timeouts: self.calculator.scanner_timeouts(),
+            ..self.stats.clone()
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+    use crate::{
+        arbitrage::calculator::DefaultArbitrageCalculator,
+        testing::sample_opportunity,
+    };
+
+    use super::*;
+
+    fn detector() -> (DefaultArbitrageDetector, ArbitrageDetectorHandle) {
+        let config = ArbitrageConfig::default();
+        let manager = DexManager::default();
+        let pool_events = manager.subscribe_pool_events();
+        DefaultArbitrageDetector::new(
+            Arc::new(RwLock::new(manager)),
+            Box::new(DefaultArbitrageCalculator::new(config.clone())),
+            pool_events,
+            &config,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_receives_queued_opportunities() -> Result<()> {
+        let (mut detector, handle) = detector();
+        assert!(detector.next_opportunity().await.is_none());
+
+        handle.try_send(sample_opportunity())?;
+
+        assert!(detector.next_opportunity().await.is_some());
+        assert!(detector.next_opportunity().await.is_none());
+        assert_eq!(detector.get_stats().opportunities_found, 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_drops_repeat_within_duplicate_window() -> Result<()> {
+        let (mut detector, handle) = detector();
+
+        handle.try_send(sample_opportunity())?;
+        assert!(detector.next_opportunity().await.is_some());
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        handle.try_send(sample_opportunity())?;
+        assert!(detector.next_opportunity().await.is_none());
+
+        let stats = detector.get_stats();
+        assert_eq!((stats.opportunities_found, stats.deduplicated_count), (1, 1));
+        Ok(())
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+
+use rust_decimal::Decimal;
+
+use crate::{types::{ArbitrageHop, ArbitragePath, PoolState, StateSnapshot, TokenInfo, TokenPair, now}, utils::config::ArbitrageConfig};
+
+/// One swap direction through a pool
+#[derive(Debug, Clone, Copy)]
+pub struct PoolEdge<'a> {
+    pub pool: &'a PoolState,
+    /// Swap token A for token B when true, B for A otherwise
+    pub a_to_b: bool,
+}
+
+impl<'a> PoolEdge<'a> {
+    pub fn token_in(&self) -> &'a TokenInfo {
+        if self.a_to_b { &self.pool.token_a } else { &self.pool.token_b }
+    }
+
+    pub fn token_out(&self) -> &'a TokenInfo {
+        if self.a_to_b { &self.pool.token_b } else { &self.pool.token_a }
+    }
+
+    /// Spot exchange rate after the pool fee
+    pub fn rate(&self) -> Decimal {
+        let price = if self.a_to_b {
+            self.pool.spot_price_a_to_b()
+        } else {
+            self.pool.spot_price_b_to_a()
+        };
+        price * (Decimal::ONE - self.pool.fee_rate)
+    }
+
+    /// Hop with placeholder amounts, filled in by the profitability calculation
+    fn to_hop(self) -> ArbitrageHop {
+        ArbitrageHop {
+            dex_id: self.pool.dex_id,
+            pool_id: self.pool.pool_id.clone(),
+            pair: TokenPair::new(self.pool.token_a.clone(), self.pool.token_b.clone()),
+            sell_base: self.a_to_b,
+            token_in: self.token_in().clone(),
+            token_out: self.token_out().clone(),
+            amount_in: 0,
+            expected_amount_out: 0,
+            min_amount_out: 0,
+            price_impact: Decimal::ZERO,
+            fee_rate: self.pool.fee_rate,
+        }
+    }
+}
+
+/// Directed token graph of a snapshot: tokens are nodes and every
+/// tradable pool contributes one edge in each direction
+#[derive(Debug, Default)]
+pub struct TokenGraph<'a> {
+    edges: HashMap<String, Vec<PoolEdge<'a>>>,
+}
+
+impl<'a> TokenGraph<'a> {
+    /// Build the graph, skipping paused pools, pools on DEXes outside a
+    /// non-empty `whitelist_dexes`, and pools with either reserve worth
+    /// less than `min_liquidity_per_pool_usd`
+    pub fn from_snapshot(snapshot: &'a StateSnapshot, config: &ArbitrageConfig) -> Self {
+        let mut graph = Self::default();
+        let usd_prices = usd_prices(snapshot);
+
+        for pool in snapshot.pools.values() {
+            if !pool.is_active() {
+                continue;
+            }
+            if !config.whitelist_dexes.is_empty() && !config.whitelist_dexes.contains(&pool.dex_id) {
+                continue;
+            }
+            if !has_min_liquidity(pool, &usd_prices, config.min_liquidity_per_pool_usd) {
+                continue;
+            }
+            for a_to_b in [true, false] {
+                let edge = PoolEdge { pool, a_to_b };
+                graph.edges.entry(token_key(edge.token_in())).or_default().push(edge);
+            }
+        }
+
+        graph
+    }
+
+    /// Edges leaving `token`
+    pub fn edges_from(&self, token: &TokenInfo) -> &[PoolEdge<'a>] {
+        self.edges.get(&token_key(token)).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Every edge in the graph
+    pub fn all_edges(&self) -> impl Iterator<Item = &PoolEdge<'a>> {
+        self.edges.values().flatten()
+    }
+
+    /// Keys of every token with at least one outgoing edge
+    pub fn token_keys(&self) -> impl Iterator<Item = &String> {
+        self.edges.keys()
+    }
+}
+
+/// USD price of every token in `snapshot` that can be priced, keyed by `token_key`
+pub fn usd_prices(snapshot: &StateSnapshot) -> HashMap<String, Decimal> {
+    snapshot.tokens
+        .values()
+        .filter_map(|token| Some((token_key(token), snapshot.usd_price(token)?)))
+        .collect()
+}
+
+/// Both reserves are worth at least `min_usd` and neither is empty. A token
+/// missing from `usd_prices` is valued like the other side of the pool, since
+/// both sides of an AMM pool hold equal value; pools with neither token
+/// priced cannot be judged and fail.
+pub fn has_min_liquidity(pool: &PoolState, usd_prices: &HashMap<String, Decimal>, min_usd: Decimal) -> bool {
+    if pool.reserve_a.is_zero() || pool.reserve_b.is_zero() {
+        return false;
+    }
+    let value = |reserve: Decimal, token: &TokenInfo| {
+        let price = usd_prices.get(&token_key(token))?;
+        Some(reserve / Decimal::from(10u64.pow(token.decimals as u32)) * price)
+    };
+
+    match (value(pool.reserve_a, &pool.token_a), value(pool.reserve_b, &pool.token_b)) {
+        (Some(a), Some(b)) => a >= min_usd && b >= min_usd,
+        (Some(side), None) | (None, Some(side)) => side >= min_usd,
+        (None, None) => false,
+    }
+}
+
+/// Graph node key of a token: its lowercased address, or the symbol when unknown
+pub fn token_key(token: &TokenInfo) -> String {
+    match &token.address {
+        Some(address) => address.trim().to_lowercase(),
+        None => token.symbol.clone(),
+    }
+}
+
+/// Assemble a closed-loop path from `start_token` through `edges`
+pub fn build_path(start_token: &TokenInfo, edges: &[PoolEdge]) -> ArbitragePath {
+    let pool_ids: Vec<&str> = edges.iter().map(|edge| edge.pool.pool_id.as_str()).collect();
+
+    ArbitragePath {
+        path_id: format!("{}:{}", start_token.symbol, pool_ids.join("-")),
+        start_token: start_token.clone(),
+        end_token: edges.last().map(|edge| edge.token_out().clone()).unwrap_or_else(|| start_token.clone()),
+        hops: edges.iter().map(|edge| edge.to_hop()).collect(),
+        initial_amount: 0,
+        expected_final_amount: 0,
+        min_final_amount: 0,
+        calculated_at: now(),
+        networks: Vec::new(),
+    }
+}
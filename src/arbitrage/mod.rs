@@ -1,4 +1,7 @@
 pub mod arbitrage_engine;
+pub mod bellman_ford;
 pub mod calculator;
+pub mod circuit_breaker;
 pub mod detector;
+pub mod graph;
 pub mod validator;
\ No newline at end of file
@@ -1,18 +1,258 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use async_trait::async_trait;
+use rust_decimal::Decimal;
 use tokio::sync::RwLock;
+use tracing::debug;
 
-use crate::{dex::manager::DexManager, types::ArbitrageOpportunity, utils::config::ValidationConfig};
+use crate::{arbitrage::graph::{has_min_liquidity, usd_prices}, dex::manager::DexManager, types::{ArbitrageHop, ArbitrageOpportunity, PoolState, now}, utils::config::ValidationConfig};
 
 #[async_trait]
 pub trait OpportunityValidator: Send + Sync {
     async fn validate(&self, opportunity: &ArbitrageOpportunity) -> bool;
 }
 
-pub struct DefaultOpportunityValidator {
+/// Outcome of a single validation check
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CheckResult {
+    Pass,
+    Fail(String),
+}
+
+/// One step of the validation pipeline
+#[async_trait]
+pub trait SingleCheck: Send + Sync {
+    fn name(&self) -> &'static str;
+    
+    async fn check(&self, opportunity: &ArbitrageOpportunity) -> CheckResult;
+}
+
+/// Runs checks in order and stops at the first failure
+#[derive(Default)]
+pub struct ValidatorChain {
+    checks: Vec<Box<dyn SingleCheck>>,
+}
+
+impl ValidatorChain {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    pub fn with_check(mut self, check: Box<dyn SingleCheck>) -> Self {
+        self.checks.push(check);
+        self
+    }
+    
+    pub fn len(&self) -> usize {
+        self.checks.len()
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.checks.is_empty()
+    }
+    
+    pub async fn run(&self, opportunity: &ArbitrageOpportunity) -> bool {
+        for check in &self.checks {
+            if let CheckResult::Fail(reason) = check.check(opportunity).await {
+                debug!(
+                    "Opportunity {} failed {}: {}",
+                    opportunity.path.path_id, check.name(), reason
+                );
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Rejects opportunities discovered, or computed from data, more than
+/// `max_age_ms` ago
+pub struct FreshnessCheck {
+    pub max_age_ms: u64,
+}
+
+#[async_trait]
+impl SingleCheck for FreshnessCheck {
+    fn name(&self) -> &'static str {
+        "freshness"
+    }
+    
+    async fn check(&self, opportunity: &ArbitrageOpportunity) -> CheckResult {
+        if opportunity.expires_at(self.max_age_ms) < now() {
+            return CheckResult::Fail(format!("discovered more than {}ms ago", self.max_age_ms));
+        }
+        if opportunity.path.is_stale(self.max_age_ms) {
+            return CheckResult::Fail(format!("path older than {}ms", self.max_age_ms));
+        }
+        CheckResult::Pass
+    }
+}
+
+/// Requires every hop's pool to cover the expected output and to hold at
+/// least `min_usd` of liquidity, valued at USD prices derived from the
+/// tracked pools like `TokenGraph::from_snapshot` does. Pools whose tokens
+/// cannot be priced fail.
+pub struct LiquidityCheck {
+    dex_manager: Arc<RwLock<DexManager>>,
+    min_usd: Decimal,
+}
+
+impl LiquidityCheck {
+    pub fn new(dex_manager: Arc<RwLock<DexManager>>, min_usd: Decimal) -> Self {
+        Self { dex_manager, min_usd }
+    }
+}
+
+#[async_trait]
+impl SingleCheck for LiquidityCheck {
+    fn name(&self) -> &'static str {
+        "liquidity"
+    }
+    
+    async fn check(&self, opportunity: &ArbitrageOpportunity) -> CheckResult {
+        let manager = self.dex_manager.read().await;
+        let usd_prices = match manager.get_state_snapshot() {
+            Ok(snapshot) => usd_prices(&snapshot),
+            Err(e) => return CheckResult::Fail(format!("no snapshot to price pools: {}", e)),
+        };
+        for hop in &opportunity.path.hops {
+            let Some(pool) = manager.get_pool(&hop.dex_id, &hop.pool_id) else {
+                return CheckResult::Fail(format!("pool {} not tracked", hop.pool_id));
+            };
+            
+            let (_, reserve_out) = hop_reserves(hop, pool);
+            if reserve_out <= Decimal::from(hop.expected_amount_out) {
+                return CheckResult::Fail(format!("pool {} cannot cover output", hop.pool_id));
+            }
+            
+            if !has_min_liquidity(pool, &usd_prices, self.min_usd) {
+                return CheckResult::Fail(format!(
+                    "pool {} liquidity below ${} or not priced in USD", hop.pool_id, self.min_usd
+                ));
+            }
+        }
+        CheckResult::Pass
+    }
+}
+
+/// Re-quotes each hop against current reserves and rejects when the
+/// output moved more than `max_percent` from what was expected
+pub struct PriceDivergenceCheck {
+    dex_manager: Arc<RwLock<DexManager>>,
+    max_percent: Decimal,
+}
+
+impl PriceDivergenceCheck {
+    pub fn new(dex_manager: Arc<RwLock<DexManager>>, max_percent: Decimal) -> Self {
+        Self { dex_manager, max_percent }
+    }
+}
+
+#[async_trait]
+impl SingleCheck for PriceDivergenceCheck {
+    fn name(&self) -> &'static str {
+        "price divergence"
+    }
+    
+    async fn check(&self, opportunity: &ArbitrageOpportunity) -> CheckResult {
+        let manager = self.dex_manager.read().await;
+        for hop in &opportunity.path.hops {
+            if hop.expected_amount_out == 0 {
+                continue;
+            }
+            let Some(pool) = manager.get_pool(&hop.dex_id, &hop.pool_id) else {
+                return CheckResult::Fail(format!("pool {} not tracked", hop.pool_id));
+            };
+            
+            let (reserve_in, reserve_out) = hop_reserves(hop, pool);
+            let amount_in = Decimal::from(hop.amount_in) * (Decimal::ONE - pool.fee_rate);
+            let denominator = reserve_in + amount_in;
+            if denominator.is_zero() {
+                return CheckResult::Fail(format!("pool {} is empty", hop.pool_id));
+            }
+            
+            let current_out = reserve_out * (amount_in / denominator);
+            let expected_out = Decimal::from(hop.expected_amount_out);
+            let divergence = ((current_out - expected_out) / expected_out * Decimal::ONE_HUNDRED).abs();
+            if divergence > self.max_percent {
+                return CheckResult::Fail(format!(
+                    "pool {} output diverged {:.4}% (max {}%)", hop.pool_id, divergence, self.max_percent
+                ));
+            }
+        }
+        CheckResult::Pass
+    }
+}
+
+/// Rejects trades where gas eats more than `max_gas_percent` of gross profit
+pub struct GasCostCheck {
+    pub max_gas_percent: Decimal,
+}
+
+#[async_trait]
+impl SingleCheck for GasCostCheck {
+    fn name(&self) -> &'static str {
+        "gas cost"
+    }
+    
+    async fn check(&self, opportunity: &ArbitrageOpportunity) -> CheckResult {
+        if opportunity.gross_profit <= Decimal::ZERO {
+            return CheckResult::Fail("no gross profit".into());
+        }
+        
+        let gas_percent = opportunity.estimated_gas_cost / opportunity.gross_profit * Decimal::ONE_HUNDRED;
+        if gas_percent > self.max_gas_percent {
+            return CheckResult::Fail(format!(
+                "gas is {:.2}% of profit (max {}%)", gas_percent, self.max_gas_percent
+            ));
+        }
+        CheckResult::Pass
+    }
+}
+
+/// Rejects paths that route through a paused pool
+pub struct PoolPausedCheck {
     dex_manager: Arc<RwLock<DexManager>>,
-    config: ValidationConfig,
+}
+
+impl PoolPausedCheck {
+    pub fn new(dex_manager: Arc<RwLock<DexManager>>) -> Self {
+        Self { dex_manager }
+    }
+}
+
+#[async_trait]
+impl SingleCheck for PoolPausedCheck {
+    fn name(&self) -> &'static str {
+        "pool paused"
+    }
+    
+    async fn check(&self, opportunity: &ArbitrageOpportunity) -> CheckResult {
+        let manager = self.dex_manager.read().await;
+        let paused = opportunity.path.hops
+            .iter()
+            .find(|hop| manager
+                .get_pool(&hop.dex_id, &hop.pool_id)
+                .is_some_and(|pool| !pool.is_active()));
+        
+        match paused {
+            Some(hop) => CheckResult::Fail(format!("pool {} is paused", hop.pool_id)),
+            None => CheckResult::Pass,
+        }
+    }
+}
+
+/// (reserve_in, reserve_out) of the pool in the hop's swap direction
+fn hop_reserves(hop: &ArbitrageHop, pool: &PoolState) -> (Decimal, Decimal) {
+    if hop.token_in.matches(&pool.token_a) {
+        (pool.reserve_a, pool.reserve_b)
+    } else {
+        (pool.reserve_b, pool.reserve_a)
+    }
+}
+
+pub struct DefaultOpportunityValidator {
+    chain: ValidatorChain,
 }
 
 impl DefaultOpportunityValidator {
@@ -20,16 +260,138 @@ impl DefaultOpportunityValidator {
         dex_manager: Arc<RwLock<DexManager>>,
         config: ValidationConfig,
     ) -> Self {
-        Self { dex_manager, config }
+        let mut chain = ValidatorChain::new()
+            .with_check(Box::new(FreshnessCheck { max_age_ms: config.max_opportunity_age_ms }));
+        
+        // Checks against live pool state
+        if config.revalidate_before_execution {
+            chain = chain
+                .with_check(Box::new(PoolPausedCheck::new(dex_manager.clone())))
+                .with_check(Box::new(LiquidityCheck::new(dex_manager.clone(), config.min_pool_liquidity_usd)))
+                .with_check(Box::new(PriceDivergenceCheck::new(dex_manager, config.max_price_divergence_percent)));
+        }
+        
+        Self {
+            chain: chain.with_check(Box::new(GasCostCheck { max_gas_percent: config.max_gas_cost_percent })),
+        }
+    }
+    
+    /// Validator with a custom check pipeline
+    pub fn with_chain(chain: ValidatorChain) -> Self {
+        Self { chain }
     }
 }
 
 #[async_trait]
 impl OpportunityValidator for DefaultOpportunityValidator {
-    async fn validate(&self, _opportunity: &ArbitrageOpportunity) -> bool {
-        // Check if prices haven't changed significantly
-        // Check if pools have sufficient liquidity
-        // Check if opportunity isn't too stale
-        todo!("Quick validation logic")
+    async fn validate(&self, opportunity: &ArbitrageOpportunity) -> bool {
+        self.chain.run(opportunity).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        arbitrage::calculator::{ArbitrageCalculator, DefaultArbitrageCalculator},
+        testing::{manager_with, triangle_snapshot},
+        types::{BotError, DexId, Result},
+        utils::config::ArbitrageConfig,
+    };
+
+    use super::*;
+
+    /// Most profitable triangle opportunity and a manager tracking its pools
+    async fn setup() -> Result<(Arc<RwLock<DexManager>>, ArbitrageOpportunity)> {
+        let snapshot = triangle_snapshot();
+        let opportunity = DefaultArbitrageCalculator::new(ArbitrageConfig::default())
+            .find_opportunities(&snapshot)
+            .await
+            .into_iter()
+            .next()
+            .ok_or_else(|| BotError::NotFound("triangle opportunity".into()))?;
+
+        Ok((Arc::new(RwLock::new(manager_with(&snapshot)?)), opportunity))
+    }
+
+    fn validator(manager: &Arc<RwLock<DexManager>>, config: ValidationConfig) -> DefaultOpportunityValidator {
+        DefaultOpportunityValidator::new(manager.clone(), config)
+    }
+
+    #[tokio::test]
+    async fn test_accepts_current_opportunity() -> Result<()> {
+        let (manager, opportunity) = setup().await?;
+        assert!(validator(&manager, ValidationConfig::default()).validate(&opportunity).await);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_rejects_expired_opportunity() -> Result<()> {
+        let (manager, mut opportunity) = setup().await?;
+        opportunity.discovered_at -= 10_000;
+
+        assert!(!validator(&manager, ValidationConfig::default()).validate(&opportunity).await);
+        Ok(())
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_rejects_shallow_pool() -> Result<()> {
+        let (manager, opportunity) = setup().await?;
+        let config = ValidationConfig {
+            // p1 holds 1M SUI worth 2M USDC
+            min_pool_liquidity_usd: Decimal::from(5_000_000),
+            ..ValidationConfig::default()
+        };
+
+        assert!(!validator(&manager, config).validate(&opportunity).await);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_rejects_pool_without_usd_price() -> Result<()> {
+        let (manager, opportunity) = setup().await?;
+        // Same pools, but no token is a stablecoin to price them against
+        let mut snapshot = triangle_snapshot();
+        for pool in snapshot.pools.values_mut() {
+            for token in [&mut pool.token_a, &mut pool.token_b] {
+                if token.is_usd_stablecoin() {
+                    token.symbol = "USDX".into();
+                }
+            }
+        }
+        let unpriced = Arc::new(RwLock::new(manager_with(&snapshot)?));
+
+        assert_eq!(LiquidityCheck::new(manager, Decimal::ONE).check(&opportunity).await, CheckResult::Pass);
+        assert!(matches!(
+            LiquidityCheck::new(unpriced, Decimal::ONE).check(&opportunity).await,
+            CheckResult::Fail(reason) if reason.contains("not priced")
+        ));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_rejects_diverged_price() -> Result<()> {
+        let (manager, opportunity) = setup().await?;
+        {
+            let mut manager = manager.write().await;
+            let mut pool = manager
+                .get_pool(&DexId::Turbos, &"p2".to_string())
+                .cloned()
+                .ok_or_else(|| BotError::NotFound("p2".into()))?;
+            pool.reserve_b *= Decimal::new(9, 1);
+            pool.version += 1;
+            manager.update_pool_state(pool)?;
+        }
+
+        assert!(!validator(&manager, ValidationConfig::default()).validate(&opportunity).await);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_rejects_gas_heavy_opportunity() -> Result<()> {
+        let (manager, mut opportunity) = setup().await?;
+        opportunity.estimated_gas_cost = opportunity.gross_profit * Decimal::new(6, 1);
+
+        assert!(!validator(&manager, ValidationConfig::default()).validate(&opportunity).await);
+        Ok(())
+    }
+}
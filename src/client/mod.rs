@@ -0,0 +1 @@
+pub mod sui_rpc;
@@ -0,0 +1,510 @@
+//! Minimal Sui JSON-RPC client for reading on-chain objects
+
+use std::{
+    future::Future,
+    sync::{Arc, atomic::{AtomicU64, AtomicUsize, Ordering}},
+    time::Instant,
+};
+
+use serde::Serialize;
+use serde_json::{Value, json};
+use tracing::{info, warn};
+
+use crate::{
+    types::{BotError, Network, Result, normalize_address},
+    utils::metrics::MetricsRegistry,
+};
+
+/// Which parts of an object the fullnode should return
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SuiObjectDataOptions {
+    pub show_type: bool,
+    pub show_content: bool,
+    pub show_owner: bool,
+}
+
+impl SuiObjectDataOptions {
+    /// Type and parsed Move fields, enough to rebuild a pool state
+    pub fn full_content() -> Self {
+        Self {
+            show_type: true,
+            show_content: true,
+            show_owner: false,
+        }
+    }
+}
+
+/// An object as returned by `sui_multiGetObjects`
+#[derive(Debug, Clone)]
+pub struct SuiObjectData {
+    pub object_id: String,
+    pub version: u64,
+    pub object_type: Option<String>,
+    /// Parsed Move fields, `Null` when content was not requested
+    pub fields: Value,
+}
+
+impl SuiObjectData {
+    fn from_response(entry: &Value) -> Result<Self> {
+        if let Some(error) = entry.get("error") {
+            return Err(BotError::NotFound(format!("Object: {}", error)));
+        }
+        let data = entry
+            .get("data")
+            .ok_or_else(|| BotError::Rpc(format!("Object response has no data: {}", entry)))?;
+        let object_id = data
+            .get("objectId")
+            .and_then(Value::as_str)
+            .ok_or_else(|| BotError::Rpc("Object response has no objectId".into()))?;
+        // Sui encodes u64 values as strings
+        let version = data
+            .get("version")
+            .and_then(|version| version.as_u64().or_else(|| version.as_str()?.parse().ok()))
+            .ok_or_else(|| BotError::Rpc(format!("Object {} has no version", object_id)))?;
+
+        Ok(Self {
+            object_id: object_id.to_string(),
+            version,
+            object_type: data.get("type").and_then(Value::as_str).map(str::to_string),
+            fields: data.pointer("/content/fields").cloned().unwrap_or(Value::Null),
+        })
+    }
+}
+
+/// Successful requests on a fallback endpoint before retrying the primary
+const FALLBACK_REQUESTS_BEFORE_PRIMARY: usize = 5;
+
+/// Share of failed requests above which every further failure is logged as a warning
+const ERROR_RATE_WARN_THRESHOLD: f64 = 0.01;
+
+/// Request counts and latencies since the client was created
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RpcStats {
+    pub total: u64,
+    pub failed: u64,
+    pub avg_latency_us: u64,
+    pub max_latency_us: u64,
+    pub error_rate: f64,
+}
+
+pub struct SuiRpcClient {
+    http: reqwest::Client,
+    /// Primary endpoint first, then fallbacks in the order they are tried
+    endpoints: Vec<String>,
+    current_index: AtomicUsize,
+    fallback_successes: AtomicUsize,
+    next_request_id: AtomicU64,
+    total_requests: AtomicU64,
+    failed_requests: AtomicU64,
+    total_latency_us: AtomicU64,
+    max_latency_us: AtomicU64,
+    metrics: Option<Arc<MetricsRegistry>>,
+}
+
+impl SuiRpcClient {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            endpoints: vec![url.into()],
+            current_index: AtomicUsize::new(0),
+            fallback_successes: AtomicUsize::new(0),
+            next_request_id: AtomicU64::new(1),
+            total_requests: AtomicU64::new(0),
+            failed_requests: AtomicU64::new(0),
+            total_latency_us: AtomicU64::new(0),
+            max_latency_us: AtomicU64::new(0),
+            metrics: None,
+        }
+    }
+
+    /// Also report request latencies and outcomes to Prometheus
+    pub fn with_metrics(mut self, metrics: Arc<MetricsRegistry>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Endpoints to switch to, in order, when the current one is unreachable
+    pub fn with_fallback_urls(mut self, urls: impl IntoIterator<Item = String>) -> Self {
+        self.endpoints.extend(urls);
+        self
+    }
+
+    /// Client for a `mainnet`/`testnet` alias or an explicit http(s) URL
+    pub fn new_with_endpoint(endpoint: &str) -> Result<Self> {
+        match endpoint.trim().to_lowercase().as_str() {
+            "mainnet" => Ok(Self::new_mainnet()),
+            "testnet" => Ok(Self::new_testnet()),
+            url if url.starts_with("http://") || url.starts_with("https://") => Ok(Self::new(endpoint.trim())),
+            _ => Err(BotError::Config(format!("Invalid RPC endpoint: {}", endpoint))),
+        }
+    }
+
+    pub fn new_mainnet() -> Self {
+        Self::new(Network::SuiMainnet.rpc_url())
+    }
+
+    pub fn new_testnet() -> Self {
+        Self::new(Network::SuiTestnet.rpc_url())
+    }
+
+    /// The primary endpoint
+    pub fn url(&self) -> &str {
+        self.endpoint(0)
+    }
+
+    /// The endpoint requests currently go to
+    pub fn current_endpoint(&self) -> &str {
+        self.endpoint(self.current_index.load(Ordering::Relaxed))
+    }
+
+    fn endpoint(&self, index: usize) -> &str {
+        self.endpoints.get(index % self.endpoints.len()).map(String::as_str).unwrap_or_default()
+    }
+
+    /// Run `op` against the current endpoint, moving on to the next one
+    /// whenever it fails with a network or RPC error (an unreachable endpoint
+    /// or a malformed response), until every endpoint was tried. Any other
+    /// error is returned straight away.
+    /// After a few successes on a fallback the primary gets another chance.
+    pub async fn execute_with_failover<T, F, Fut>(&self, op: F) -> Result<T>
+    where
+        F: Fn(String) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let start = self.current_index.load(Ordering::Relaxed) % self.endpoints.len();
+        let mut last_error = None;
+        for attempt in 0..self.endpoints.len() {
+            let index = (start + attempt) % self.endpoints.len();
+            match op(self.endpoint(index).to_string()).await {
+                Ok(value) => {
+                    self.record_success(index);
+                    return Ok(value);
+                }
+                Err(e @ (BotError::Network(_) | BotError::Rpc(_))) => {
+                    let next_index = (index + 1) % self.endpoints.len();
+                    if attempt + 1 < self.endpoints.len() {
+                        warn!("Switching to fallback RPC: {}", self.endpoint(next_index));
+                    }
+                    self.current_index.store(next_index, Ordering::Relaxed);
+                    self.fallback_successes.store(0, Ordering::Relaxed);
+                    last_error = Some(e);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Err(last_error.unwrap_or_else(|| BotError::Network("No RPC endpoints configured".into())))
+    }
+
+    pub fn get_stats(&self) -> RpcStats {
+        let total = self.total_requests.load(Ordering::Relaxed);
+        let failed = self.failed_requests.load(Ordering::Relaxed);
+        RpcStats {
+            total,
+            failed,
+            avg_latency_us: self.total_latency_us.load(Ordering::Relaxed).checked_div(total).unwrap_or(0),
+            max_latency_us: self.max_latency_us.load(Ordering::Relaxed),
+            error_rate: if total == 0 { 0.0 } else { failed as f64 / total as f64 },
+        }
+    }
+
+    /// Count one finished request, failovers included in its latency
+    fn record_request(&self, method: &str, started: Instant, succeeded: bool) {
+        let latency_us = started.elapsed().as_micros().try_into().unwrap_or(u64::MAX);
+        self.total_requests.fetch_add(1, Ordering::Relaxed);
+        self.total_latency_us.fetch_add(latency_us, Ordering::Relaxed);
+        self.max_latency_us.fetch_max(latency_us, Ordering::Relaxed);
+        if !succeeded {
+            self.failed_requests.fetch_add(1, Ordering::Relaxed);
+        }
+        if let Some(metrics) = &self.metrics {
+            metrics.observe_rpc_request(method, latency_us, succeeded);
+        }
+
+        let stats = self.get_stats();
+        if !succeeded && stats.error_rate > ERROR_RATE_WARN_THRESHOLD {
+            warn!(
+                "RPC error rate at {:.1}% ({} of {} requests failed)",
+                stats.error_rate * 100.0, stats.failed, stats.total
+            );
+        }
+    }
+
+    fn record_success(&self, index: usize) {
+        if index == 0 {
+            return;
+        }
+        let successes = self.fallback_successes.fetch_add(1, Ordering::Relaxed) + 1;
+        if successes >= FALLBACK_REQUESTS_BEFORE_PRIMARY {
+            info!("Returning to primary RPC: {}", self.url());
+            self.current_index.store(0, Ordering::Relaxed);
+            self.fallback_successes.store(0, Ordering::Relaxed);
+        }
+    }
+
+    /// Fetch several objects in one request, in the order of `object_ids`.
+    /// Fails if any object is missing or deleted, or if the response does
+    /// not hold exactly the requested objects in order.
+    pub async fn batch_get_objects(
+        &self,
+        object_ids: Vec<String>,
+        options: Option<SuiObjectDataOptions>,
+    ) -> Result<Vec<SuiObjectData>> {
+        if object_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let result = self
+            .call("sui_multiGetObjects", json!([object_ids, options.unwrap_or_default()]))
+            .await?;
+
+        let entries = result
+            .as_array()
+            .ok_or_else(|| BotError::Rpc(format!("sui_multiGetObjects returned {}", result)))?;
+        if entries.len() != object_ids.len() {
+            return Err(BotError::Rpc(format!(
+                "sui_multiGetObjects returned {} objects for {} IDs", entries.len(), object_ids.len()
+            )));
+        }
+
+        entries
+            .iter()
+            .zip(&object_ids)
+            .map(|(entry, object_id)| {
+                let object = SuiObjectData::from_response(entry)?;
+                if normalize_address(&object.object_id) != normalize_address(object_id) {
+                    return Err(BotError::Rpc(format!("Requested object {} but got {}", object_id, object.object_id)));
+                }
+                Ok(object)
+            })
+            .collect()
+    }
+
+    /// Check that some endpoint, the primary or a fallback, answers requests
+    pub async fn check_node_health(&self) -> Result<()> {
+        self.get_latest_checkpoint()
+            .await
+            .map(|_| ())
+            .map_err(|_| BotError::Config(format!("RPC endpoint unreachable: {}", self.url())))
+    }
+
+    /// Sequence number of the newest checkpoint the fullnode has executed
+    pub async fn get_latest_checkpoint(&self) -> Result<u64> {
+        let result = self.call("sui_getLatestCheckpointSequenceNumber", json!([])).await?;
+        // Sui encodes u64 values as strings
+        result
+            .as_u64()
+            .or_else(|| result.as_str()?.parse().ok())
+            .ok_or_else(|| BotError::Rpc(format!("sui_getLatestCheckpointSequenceNumber returned {}", result)))
+    }
+
+    /// Send one JSON-RPC request and return its `result`
+    async fn call(&self, method: &str, params: Value) -> Result<Value> {
+        let started = Instant::now();
+        let result = self.send(method, params).await;
+        self.record_request(method, started, result.is_ok());
+        result
+    }
+
+    async fn send(&self, method: &str, params: Value) -> Result<Value> {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": self.next_request_id.fetch_add(1, Ordering::Relaxed),
+            "method": method,
+            "params": params,
+        });
+
+        let request = &request;
+        let mut response: Value = self
+            .execute_with_failover(|url| async move {
+                self.http
+                    .post(&url)
+                    .json(request)
+                    .send()
+                    .await
+                    .and_then(|response| response.error_for_status())
+                    .map_err(|e| BotError::Network(format!("{} to {} failed: {}", method, url, e)))?
+                    .json::<Value>()
+                    .await
+                    .map_err(|e| BotError::Rpc(format!("{} returned invalid JSON: {}", method, e)))
+                    .and_then(|response| {
+                        if response.get("result").is_none() && response.get("error").is_none() {
+                            return Err(BotError::Rpc(format!("{} returned no result", method)));
+                        }
+                        Ok(response)
+                    })
+            })
+            .await?;
+
+        // Checked after failover: another endpoint would reject the request too
+        if let Some(error) = response.get("error") {
+            return Err(BotError::Rpc(format!("{} failed: {}", method, error)));
+        }
+        Ok(response["result"].take())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::{cetus_pool_objects, rpc_server};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_batch_get_objects() -> Result<()> {
+        let url = rpc_server(|request| {
+            assert_eq!(request["method"], "sui_multiGetObjects");
+            assert_eq!(request["params"][1]["showContent"], true);
+            json!([
+                {
+                    "data": {
+                        "objectId": "0xp1",
+                        "version": "42",
+                        "type": "0xcetus::pool::Pool<0x2::sui::SUI, 0xusdc::usdc::USDC>",
+                        "content": { "dataType": "moveObject", "fields": { "coin_a": "10" } }
+                    }
+                }
+            ])
+        })
+        .await?;
+
+        let objects = SuiRpcClient::new(url)
+            .batch_get_objects(vec!["0xp1".into()], Some(SuiObjectDataOptions::full_content()))
+            .await?;
+
+        assert_eq!(objects.len(), 1);
+        assert_eq!(objects[0].version, 42);
+        assert_eq!(objects[0].fields["coin_a"], "10");
+        Ok(())
+    }
+
+    #[test]
+    fn test_endpoint_aliases() -> Result<()> {
+        assert_eq!(SuiRpcClient::new_with_endpoint("mainnet")?.url(), Network::SuiMainnet.rpc_url());
+        assert_eq!(SuiRpcClient::new_with_endpoint("Testnet")?.url(), Network::SuiTestnet.rpc_url());
+        assert_eq!(SuiRpcClient::new_with_endpoint("http://127.0.0.1:9000")?.url(), "http://127.0.0.1:9000");
+        assert!(matches!(SuiRpcClient::new_with_endpoint("devnet"), Err(BotError::Config(_))));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_short_batch_is_rejected() -> Result<()> {
+        let url = rpc_server(cetus_pool_objects).await?;
+        let short = rpc_server(move |mut request| {
+            request["params"][0] = json!(["0xp1"]);
+            cetus_pool_objects(request)
+        })
+        .await?;
+
+        assert_eq!(SuiRpcClient::new(url).batch_get_objects(vec!["0xp1".into(), "0xp2".into()], None).await?.len(), 2);
+        let result = SuiRpcClient::new(short).batch_get_objects(vec!["0xp1".into(), "0xp2".into()], None).await;
+        assert!(matches!(result, Err(BotError::Rpc(_))));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_unrequested_object_is_rejected() -> Result<()> {
+        let url = rpc_server(|mut request| {
+            request["params"][0] = json!(["0xother"]);
+            cetus_pool_objects(request)
+        })
+        .await?;
+
+        let result = SuiRpcClient::new(url).batch_get_objects(vec!["0xp1".into()], None).await;
+
+        assert!(matches!(result, Err(BotError::Rpc(_))));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_fails_over_and_returns_to_primary() -> Result<()> {
+        let fallback = rpc_server(cetus_pool_objects).await?;
+        let client = SuiRpcClient::new("http://127.0.0.1:9").with_fallback_urls([fallback.clone()]);
+
+        client.batch_get_objects(vec!["0xp1".into()], None).await?;
+        assert_eq!(client.current_endpoint(), fallback);
+
+        for _ in 1..FALLBACK_REQUESTS_BEFORE_PRIMARY {
+            client.batch_get_objects(vec!["0xp1".into()], None).await?;
+        }
+        assert_eq!(client.current_endpoint(), "http://127.0.0.1:9");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_invalid_json_fails_over() -> Result<()> {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let primary = format!("http://{}", listener.local_addr()?);
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut request = [0u8; 4096];
+                let _ = tokio::io::AsyncReadExt::read(&mut stream, &mut request).await;
+                let body = "<html>Bad Gateway</html>";
+                let response = format!("HTTP/1.1 200 OK\r\ncontent-length: {}\r\n\r\n{}", body.len(), body);
+                let _ = tokio::io::AsyncWriteExt::write_all(&mut stream, response.as_bytes()).await;
+            }
+        });
+        let fallback = rpc_server(cetus_pool_objects).await?;
+        let client = SuiRpcClient::new(primary).with_fallback_urls([fallback.clone()]);
+
+        let objects = client.batch_get_objects(vec!["0xp1".into()], None).await?;
+
+        assert_eq!(objects[0].object_id, "0xp1");
+        assert_eq!(client.current_endpoint(), fallback);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_rpc_errors_do_not_fail_over() -> Result<()> {
+        let primary = rpc_server(|_| json!([{ "error": { "code": "notExists", "object_id": "0xp1" } }])).await?;
+        let client = SuiRpcClient::new(primary.clone()).with_fallback_urls([rpc_server(|_| json!([])).await?]);
+
+        let result = client.batch_get_objects(vec!["0xp1".into()], None).await;
+
+        assert!(matches!(result, Err(BotError::NotFound(_))));
+        assert_eq!(client.current_endpoint(), primary);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_missing_object_is_not_found() -> Result<()> {
+        let url = rpc_server(|_| json!([{ "error": { "code": "notExists", "object_id": "0xp1" } }])).await?;
+
+        let result = SuiRpcClient::new(url).batch_get_objects(vec!["0xp1".into()], None).await;
+
+        assert!(matches!(result, Err(BotError::NotFound(_))));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_stats_count_requests_and_failures() -> Result<()> {
+        let metrics = crate::utils::metrics::register_metrics()?;
+        let client = SuiRpcClient::new(rpc_server(cetus_pool_objects).await?).with_metrics(metrics.clone());
+        let unreachable = SuiRpcClient::new("http://127.0.0.1:9").with_metrics(metrics.clone());
+
+        client.batch_get_objects(vec!["0xp1".into()], None).await?;
+        client.batch_get_objects(vec!["0xp1".into()], None).await?;
+        assert!(unreachable.batch_get_objects(vec!["0xp1".into()], None).await.is_err());
+
+        let stats = client.get_stats();
+        assert_eq!((stats.total, stats.failed, stats.error_rate), (2, 0, 0.0));
+        assert!(stats.max_latency_us >= stats.avg_latency_us);
+        let stats = unreachable.get_stats();
+        assert_eq!((stats.total, stats.failed, stats.error_rate), (1, 1, 1.0));
+        let rendered = metrics.render()?;
+        assert!(rendered.contains(r#"rpc_requests_total{method="sui_multiGetObjects",status="success"} 2"#));
+        assert!(rendered.contains(r#"rpc_requests_total{method="sui_multiGetObjects",status="failed"} 1"#));
+        assert!(rendered.contains(r#"rpc_request_latency_seconds_count{method="sui_multiGetObjects"} 3"#));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_node_health_uses_fallbacks() -> Result<()> {
+        let healthy = rpc_server(|_| json!("1234")).await?;
+
+        SuiRpcClient::new("http://127.0.0.1:9").with_fallback_urls([healthy]).check_node_health().await?;
+        let result = SuiRpcClient::new("http://127.0.0.1:9").check_node_health().await;
+
+        assert!(matches!(result, Err(BotError::Config(message)) if message == "RPC endpoint unreachable: http://127.0.0.1:9"));
+        Ok(())
+    }
+}
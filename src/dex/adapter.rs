@@ -1,12 +1,17 @@
 use async_trait::async_trait;
 
-use crate::{dex::state::DexState, types::{DexId, HealthStatus, PoolId, PoolState, Price, PriceUpdate, RawEvent, Result, SwapEvent, SyncResult, TokenPair}};
+use crate::{dex::state::DexState, types::{DexId, HealthStatus, Network, PoolId, PoolState, Price, PriceUpdate, RawEvent, Result, SwapEvent, SyncResult, TokenPair}};
 
 #[async_trait]
 pub trait DexAdapter: Send + Sync {
     // ========== IDENTITY ==========
     fn dex_id(&self) -> DexId;
     
+    /// Chain the DEX's pools live on
+    fn network(&self) -> Network {
+        Network::SuiMainnet
+    }
+    
     // ========== STATE ACCESS ==========
     fn state(&self) -> &DexState;
     fn state_mut(&mut self) -> &mut DexState;
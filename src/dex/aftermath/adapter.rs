@@ -0,0 +1,257 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tracing::{debug, warn};
+
+use crate::{
+    client::sui_rpc::{SuiObjectData, SuiObjectDataOptions, SuiRpcClient},
+    dex::{adapter::DexAdapter, aftermath::pools, state::{DexConfig, DexState}},
+    types::{
+        BotError, DexId, HealthStatus, Network, PoolId, PoolState, Price, PriceSource, PriceUpdate, RawEvent, Result,
+        SwapEvent, SyncResult, TokenPair, UpdateTrigger, now,
+    },
+    utils::config,
+};
+
+/// Pools not updated for this long count as stale in heartbeats
+const STALE_POOL_AGE_MS: u64 = 3_600_000;
+
+/// Aftermath stable-swap adapter keeping pool states current through RPC sync
+pub struct AftermathAdapter {
+    state: DexState,
+    rpc: Arc<SuiRpcClient>,
+    network: Network,
+}
+
+impl AftermathAdapter {
+    pub fn new(config: DexConfig, rpc: Arc<SuiRpcClient>) -> Self {
+        Self {
+            state: DexState::new(config),
+            rpc,
+            network: Network::SuiMainnet,
+        }
+    }
+
+    /// Network the RPC client and package belong to, mainnet by default
+    pub fn with_network(mut self, network: Network) -> Self {
+        self.network = network;
+        self
+    }
+
+    /// Adapter monitoring the pools listed in the DEX's config entry
+    pub fn from_config(dex: &config::DexConfig, rpc: Arc<SuiRpcClient>) -> Self {
+        let mut config = DexConfig::new(DexId::Aftermath, dex.package_id.clone());
+        for pool in &dex.pools {
+            let pair = TokenPair::new(pool.token_a.clone(), pool.token_b.clone());
+            config.monitored_pairs.push(pair.clone());
+            config.pool_addresses.insert(pair, pool.address.clone());
+        }
+        Self::new(config, rpc)
+    }
+
+    pub fn with_pool(mut self, pool: PoolState) -> Self {
+        self.state.pools.insert(pool.pool_id.clone(), pool);
+        self
+    }
+
+    fn parse_object(&self, object: SuiObjectData) -> Result<PoolState> {
+        let (token_a, token_b) = self.state.pool_tokens(&object.object_id)?;
+        pools::parse_pool(object.object_id, token_a, token_b, &object.fields, object.version)
+    }
+}
+
+#[async_trait]
+impl DexAdapter for AftermathAdapter {
+    fn dex_id(&self) -> DexId {
+        DexId::Aftermath
+    }
+
+    fn network(&self) -> Network {
+        self.network
+    }
+
+    fn state(&self) -> &DexState {
+        &self.state
+    }
+
+    fn state_mut(&mut self) -> &mut DexState {
+        &mut self.state
+    }
+
+    /// Load every monitored pool from RPC
+    async fn initialize(&mut self) -> Result<()> {
+        let fetched = self.fetch_all_pools().await?;
+        let loaded = self.state.reconcile(fetched).len();
+        self.state.record_full_sync();
+        debug!("Aftermath adapter initialized with {} pools", loaded);
+        Ok(())
+    }
+
+    async fn fetch_pool_state(&self, pool_id: &PoolId) -> Result<PoolState> {
+        // Resolve tokens first so unmonitored pools cost no request
+        self.state.pool_tokens(pool_id)?;
+        let object = self.rpc
+            .batch_get_objects(vec![pool_id.clone()], Some(SuiObjectDataOptions::full_content()))
+            .await?
+            .pop()
+            .ok_or_else(|| BotError::NotFound(format!("Pool {}", pool_id)))?;
+        self.parse_object(object)
+    }
+
+    async fn fetch_all_pools(&self) -> Result<Vec<PoolState>> {
+        self.rpc
+            .batch_get_objects(self.state.pool_ids(), Some(SuiObjectDataOptions::full_content()))
+            .await?
+            .into_iter()
+            .map(|object| self.parse_object(object))
+            .collect()
+    }
+
+    /// Aftermath swap events are not decoded yet; its pools are refreshed
+    /// by sync only
+    fn parse_event(&self, raw: RawEvent) -> Result<SwapEvent> {
+        Err(BotError::Parse(format!("Aftermath event {} is not supported", raw.event_type)))
+    }
+
+    fn process_swap_event(&mut self, event: SwapEvent) -> Result<PriceUpdate> {
+        let pool = self.state.pools
+            .get_mut(&event.pool_id)
+            .ok_or_else(|| BotError::NotFound(format!("Pool {}", event.pool_id)))?;
+
+        let old_price = pool
+            .is_initialized()
+            .then(|| Price::new(pool.spot_price_a_to_b(), PriceSource::Calculated));
+        pool.apply_swap(&event)?;
+        let new_price = Price::new(
+            pool.spot_price_a_to_b(),
+            PriceSource::Event {
+                block_height: event.block_height.unwrap_or(0),
+                transaction_digest: event.transaction_digest.clone(),
+            },
+        );
+        let pair = TokenPair::new(pool.token_a.clone(), pool.token_b.clone());
+
+        self.state.store_price(&pair, new_price.clone());
+        self.state.health.last_event = Some(event.timestamp);
+        self.state.stats.events_processed += 1;
+
+        Ok(PriceUpdate::new(
+            DexId::Aftermath,
+            pair,
+            old_price,
+            new_price,
+            UpdateTrigger::SwapEvent {
+                transaction_digest: event.transaction_digest,
+                block_height: event.block_height,
+            },
+        ))
+    }
+
+    fn calculate_price(&self, pool: &PoolState) -> Result<Price> {
+        pools::calculate_price(pool)
+    }
+
+    fn get_price(&self, pair: &TokenPair) -> Option<Price> {
+        self.state.price(pair)
+    }
+
+    /// Fetch one monitored pool to prove the RPC is reachable
+    async fn heartbeat(&mut self) -> Result<HealthStatus> {
+        let Some(pool_id) = self.state.pool_ids().into_iter().next() else {
+            return Ok(HealthStatus::healthy("No pools monitored"));
+        };
+
+        let started = now();
+        let probe = self.fetch_pool_state(&pool_id).await.map(|_| ());
+        Ok(self.state.record_heartbeat(probe, started, STALE_POOL_AGE_MS))
+    }
+
+    /// Refetch every pool, keeping cached states that are already current
+    async fn periodic_sync(&mut self) -> Result<SyncResult> {
+        let started = now();
+        let fetched = match self.fetch_all_pools().await {
+            Ok(fetched) => fetched,
+            Err(e) => {
+                warn!("Aftermath periodic sync failed: {}", e);
+                self.state.record_error(&e);
+                return Ok(SyncResult::failure(vec![e.to_string()]));
+            }
+        };
+
+        let updated_pools = self.state.reconcile(fetched);
+        self.state.record_full_sync();
+
+        let mut result = SyncResult::success(updated_pools.len(), now().saturating_sub(started));
+        result.updated_pools = updated_pools;
+        Ok(result)
+    }
+
+    fn is_healthy(&self) -> bool {
+        self.state.health.is_healthy
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal::Decimal;
+    use serde_json::{Value, json};
+
+    use crate::testing::{rpc_server, test_token};
+
+    use super::*;
+
+    /// `rpc_server` handler answering with a stable pool object for each requested ID
+    fn aftermath_pool_objects(request: Value) -> Value {
+        let ids = request["params"][0].as_array().cloned().unwrap_or_default();
+        ids.iter()
+            .map(|id| json!({
+                "data": {
+                    "objectId": id,
+                    "version": "6",
+                    "content": {
+                        "fields": {
+                            "normalized_balances": ["2000000000000000000000", "1000000000000000000000"],
+                            "fees_swap_in": ["100000000000000", "100000000000000"],
+                            "amp_factor": "100"
+                        }
+                    }
+                }
+            }))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_sync_over_rpc() -> Result<()> {
+        let rpc = Arc::new(SuiRpcClient::new(rpc_server(aftermath_pool_objects).await?));
+        let dex = config::DexConfig {
+            id: DexId::Aftermath,
+            package_id: String::new(),
+            event_type: String::new(),
+            enabled: true,
+            pools: vec![config::PoolConfig { address: "0xaf1".into(), token_a: test_token("USDC"), token_b: test_token("USDT") }],
+            event_buffer_size: None,
+        };
+        let mut adapter = AftermathAdapter::from_config(&dex, rpc);
+
+        adapter.initialize().await?;
+
+        let pool = adapter.state.pools.get("0xaf1").ok_or_else(|| BotError::NotFound("0xaf1".into()))?;
+        assert_eq!(pool.reserve_a, Decimal::from(2_000_000_000_000u64));
+        assert_eq!(pool.amp_factor, Some(100));
+        // Token A is the more plentiful coin, so it trades below par
+        assert!(adapter.calculate_price(pool)?.value < Decimal::ONE);
+        assert_eq!(adapter.periodic_sync().await?.pools_synced, 0);
+        assert!(adapter.heartbeat().await?.is_healthy);
+        Ok(())
+    }
+
+    #[test]
+    fn test_events_are_rejected() {
+        let rpc = Arc::new(SuiRpcClient::new("http://127.0.0.1:9"));
+        let adapter = AftermathAdapter::new(DexConfig::new(DexId::Aftermath, ""), rpc);
+
+        let result = adapter.parse_event(RawEvent::new(Value::Null, String::new(), "0xaf::pool::SwapEvent".into()));
+
+        assert!(matches!(result, Err(BotError::Parse(_))));
+    }
+}
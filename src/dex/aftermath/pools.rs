@@ -0,0 +1,166 @@
+use rust_decimal::Decimal;
+use serde_json::Value;
+
+use crate::{
+    types::{BotError, DexId, PoolId, PoolState, Price, PriceSource, Result, TokenInfo, now},
+    utils::math::calculate_stable_swap_output,
+};
+
+/// Aftermath balances and fees are fixed point with 18 decimals
+const FIXED_ONE_DECIMALS: u32 = 18;
+
+/// Type name infix of Aftermath pool objects
+pub const POOL_TYPE_IDENTIFIER: &str = "::pool::Pool<";
+
+/// Check if an object is an Aftermath pool. Cetus pools share the type name,
+/// so the normalized balances field tells the two apart.
+pub fn is_pool_object(object_type: &str, fields: &Value) -> bool {
+    object_type.contains(POOL_TYPE_IDENTIFIER) && fields.get("normalized_balances").is_some()
+}
+
+/// Spot price of token A in token B for an Aftermath stable pool, quoted
+/// from a one-token swap along the StableSwap curve without fees
+pub fn calculate_price(pool: &PoolState) -> Result<Price> {
+    let amp_factor = pool.amp_factor
+        .ok_or_else(|| BotError::dex(DexId::Aftermath, format!("Pool {} has no amp factor", pool.pool_id)))?;
+    let (reserve_in, reserve_out, _) = pool.swap_params(true);
+    let amount_in = 10u64.pow(pool.token_a.decimals as u32);
+    let amount_out = calculate_stable_swap_output(amount_in, reserve_in, reserve_out, amp_factor, 0);
+    if amount_out == 0 {
+        return Err(BotError::dex(DexId::Aftermath, format!("Pool {} has no liquidity", pool.pool_id)));
+    }
+
+    Ok(Price {
+        value: pool.token_b.to_decimal(amount_out),
+        timestamp: pool.block_timestamp,
+        source: PriceSource::Calculated,
+    })
+}
+
+/// Build a pool state from the `fields` map of a two-coin Aftermath pool.
+/// `normalized_balances` hold each coin's balance scaled to 18 decimals,
+/// in the same order as the pool's coin types; `token_a` is the first coin.
+/// The fee is the swap-in fee of the first coin.
+pub fn parse_pool(
+    pool_id: PoolId,
+    token_a: TokenInfo,
+    token_b: TokenInfo,
+    fields: &Value,
+    version: u64,
+) -> Result<PoolState> {
+    let balances = integer_array(fields, "normalized_balances")?;
+    let [balance_a, balance_b] = balances[..] else {
+        return Err(BotError::Parse(format!(
+            "Aftermath pool {} has {} coins, only two-coin pools are supported", pool_id, balances.len()
+        )));
+    };
+    let fee = integer_array(fields, "fees_swap_in")?
+        .first()
+        .copied()
+        .ok_or_else(|| BotError::Parse("Aftermath pool has no swap fee".into()))?;
+    let amp_factor = integer_value(
+        fields.get("amp_factor").ok_or_else(|| BotError::Parse("Aftermath pool is missing field amp_factor".into()))?,
+        "amp_factor",
+    )?;
+
+    Ok(PoolState {
+        dex_id: DexId::Aftermath,
+        reserve_a: denormalize(balance_a, &token_a)?,
+        reserve_b: denormalize(balance_b, &token_b)?,
+        pool_id,
+        token_a,
+        token_b,
+        fee_rate: Decimal::from_i128_with_scale(fee as i128, FIXED_ONE_DECIMALS),
+        block_timestamp: now(),
+        is_active: true,
+        version,
+        sqrt_price_x64: 0,
+        current_tick_index: 0,
+        amp_factor: Some(amp_factor.try_into().map_err(|_| BotError::Parse("Aftermath amp_factor exceeds u64".into()))?),
+    })
+}
+
+/// Raw amount of `token` for a balance scaled to 18 decimals
+fn denormalize(balance: u128, token: &TokenInfo) -> Result<Decimal> {
+    let scale = 10u128.pow(FIXED_ONE_DECIMALS.saturating_sub(token.decimals as u32));
+    let raw: u64 = (balance / scale)
+        .try_into()
+        .map_err(|_| BotError::Parse(format!("Aftermath {} balance exceeds u64", token.symbol)))?;
+    Ok(Decimal::from(raw))
+}
+
+fn integer_array(fields: &Value, name: &str) -> Result<Vec<u128>> {
+    fields
+        .get(name)
+        .and_then(Value::as_array)
+        .ok_or_else(|| BotError::Parse(format!("Aftermath pool is missing field {}", name)))?
+        .iter()
+        .map(|value| integer_value(value, name))
+        .collect()
+}
+
+/// Read an integer, which Sui JSON encodes either as a number or a string
+fn integer_value(value: &Value, name: &str) -> Result<u128> {
+    value
+        .as_u64()
+        .map(u128::from)
+        .or_else(|| value.as_str().and_then(|s| s.parse().ok()))
+        .ok_or_else(|| BotError::Parse(format!("Aftermath pool field {} is not an integer: {}", name, value)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POOL_FIXTURE: &str = r#"{
+        "name": "USDC/USDT",
+        "type_names": ["5d4b::coin::COIN", "c060::coin::COIN"],
+        "normalized_balances": ["1250000000000000000000000", "1180000000000000000000000"],
+        "fees_swap_in": ["100000000000000", "100000000000000"],
+        "fees_swap_out": ["0", "0"],
+        "amp_factor": "200"
+    }"#;
+
+    fn parse(fields: &Value) -> Result<PoolState> {
+        parse_pool(
+            "0xaf1".into(),
+            TokenInfo::new("USDC", "0x5d4b::coin::COIN", 6),
+            TokenInfo::new("USDT", "0xc060::coin::COIN", 6),
+            fields,
+            11,
+        )
+    }
+
+    #[test]
+    fn test_parse_pool_fixture() -> Result<()> {
+        let fields: Value = serde_json::from_str(POOL_FIXTURE)?;
+        let pool = parse(&fields)?;
+
+        assert_eq!(pool.dex_id, DexId::Aftermath);
+        assert_eq!(pool.reserve_a, Decimal::from(1_250_000_000_000u64));
+        assert_eq!(pool.reserve_b, Decimal::from(1_180_000_000_000u64));
+        assert_eq!(pool.fee_rate, Decimal::new(1, 4));
+        assert_eq!(pool.amp_factor, Some(200));
+        assert_eq!(pool.version, 11);
+        Ok(())
+    }
+
+    #[test]
+    fn test_stable_price_stays_near_peg() -> Result<()> {
+        let fields: Value = serde_json::from_str(POOL_FIXTURE)?;
+        let price = calculate_price(&parse(&fields)?)?.value;
+
+        // Constant product would quote 0.944 for these reserves
+        assert!(price > Decimal::new(99, 2) && price < Decimal::ONE, "price {}", price);
+        Ok(())
+    }
+
+    #[test]
+    fn test_three_coin_pool_is_parse_error() -> Result<()> {
+        let mut fields: Value = serde_json::from_str(POOL_FIXTURE)?;
+        fields["normalized_balances"] = serde_json::json!(["1", "2", "3"]);
+
+        assert!(matches!(parse(&fields), Err(BotError::Parse(_))));
+        Ok(())
+    }
+}
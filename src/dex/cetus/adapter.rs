@@ -0,0 +1,364 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tracing::{debug, warn};
+
+use crate::{
+    client::sui_rpc::{SuiObjectData, SuiObjectDataOptions, SuiRpcClient},
+    dex::{adapter::DexAdapter, cetus::pools, state::{DexConfig, DexState}},
+    event::parsers::cetus::CetusEventParser,
+    types::{
+        BotError, DexId, HealthStatus, Network, PoolId, PoolState, Price, PriceSource, PriceUpdate, RawEvent,
+        Result, SwapEvent, SyncResult, TokenPair, UpdateTrigger, now,
+    },
+    utils::config,
+};
+
+/// Pools not updated for this long count as stale in heartbeats
+const STALE_POOL_AGE_MS: u64 = 3_600_000;
+
+/// Cetus CLMM adapter keeping pool states current from swap events
+pub struct CetusAdapter {
+    state: DexState,
+    rpc: Arc<SuiRpcClient>,
+    network: Network,
+}
+
+impl CetusAdapter {
+    pub fn new(config: DexConfig, rpc: Arc<SuiRpcClient>) -> Self {
+        Self {
+            state: DexState::new(config),
+            rpc,
+            network: Network::SuiMainnet,
+        }
+    }
+
+    /// Network the RPC client and package belong to, mainnet by default
+    pub fn with_network(mut self, network: Network) -> Self {
+        self.network = network;
+        self
+    }
+
+    /// Adapter monitoring the pools listed in the DEX's config entry
+    pub fn from_config(dex: &config::DexConfig, rpc: Arc<SuiRpcClient>) -> Self {
+        let mut config = DexConfig::new(DexId::Cetus, dex.package_id.clone());
+        for pool in &dex.pools {
+            let pair = TokenPair::new(pool.token_a.clone(), pool.token_b.clone());
+            config.monitored_pairs.push(pair.clone());
+            config.pool_addresses.insert(pair, pool.address.clone());
+        }
+        Self::new(config, rpc)
+    }
+
+    pub fn with_pool(mut self, pool: PoolState) -> Self {
+        self.state.pools.insert(pool.pool_id.clone(), pool);
+        self
+    }
+
+    fn parse_object(&self, object: SuiObjectData) -> Result<PoolState> {
+        let (token_a, token_b) = self.state.pool_tokens(&object.object_id)?;
+        pools::parse_pool(object.object_id, token_a, token_b, &object.fields, object.version)
+    }
+}
+
+#[async_trait]
+impl DexAdapter for CetusAdapter {
+    fn dex_id(&self) -> DexId {
+        DexId::Cetus
+    }
+
+    fn network(&self) -> Network {
+        self.network
+    }
+
+    fn state(&self) -> &DexState {
+        &self.state
+    }
+
+    fn state_mut(&mut self) -> &mut DexState {
+        &mut self.state
+    }
+
+    /// Load every monitored pool from RPC
+    async fn initialize(&mut self) -> Result<()> {
+        let fetched = self.fetch_all_pools().await?;
+        let loaded = self.state.reconcile(fetched).len();
+        self.state.record_full_sync();
+        debug!("Cetus adapter initialized with {} pools", loaded);
+        Ok(())
+    }
+
+    async fn fetch_pool_state(&self, pool_id: &PoolId) -> Result<PoolState> {
+        // Resolve tokens first so unmonitored pools cost no request
+        self.state.pool_tokens(pool_id)?;
+        let object = self.rpc
+            .batch_get_objects(vec![pool_id.clone()], Some(SuiObjectDataOptions::full_content()))
+            .await?
+            .pop()
+            .ok_or_else(|| BotError::NotFound(format!("Pool {}", pool_id)))?;
+        self.parse_object(object)
+    }
+
+    async fn fetch_all_pools(&self) -> Result<Vec<PoolState>> {
+        self.rpc
+            .batch_get_objects(self.state.pool_ids(), Some(SuiObjectDataOptions::full_content()))
+            .await?
+            .into_iter()
+            .map(|object| self.parse_object(object))
+            .collect()
+    }
+
+    fn parse_event(&self, raw: RawEvent) -> Result<SwapEvent> {
+        CetusEventParser::parse(&raw)
+    }
+
+    fn process_swap_event(&mut self, event: SwapEvent) -> Result<PriceUpdate> {
+        let pool = self.state.pools
+            .get_mut(&event.pool_id)
+            .ok_or_else(|| BotError::NotFound(format!("Pool {}", event.pool_id)))?;
+
+        let old_price = pool
+            .is_initialized()
+            .then(|| Price::new(pool.spot_price_a_to_b(), PriceSource::Calculated));
+        pool.apply_swap(&event)?;
+        let new_price = Price::new(
+            pool.spot_price_a_to_b(),
+            PriceSource::Event {
+                block_height: event.block_height.unwrap_or(0),
+                transaction_digest: event.transaction_digest.clone(),
+            },
+        );
+        let pair = TokenPair::new(pool.token_a.clone(), pool.token_b.clone());
+
+        self.state.store_price(&pair, new_price.clone());
+        self.state.health.last_event = Some(event.timestamp);
+        self.state.stats.events_processed += 1;
+
+        Ok(PriceUpdate::new(
+            DexId::Cetus,
+            pair,
+            old_price,
+            new_price,
+            UpdateTrigger::SwapEvent {
+                transaction_digest: event.transaction_digest,
+                block_height: event.block_height,
+            },
+        ))
+    }
+
+    fn calculate_price(&self, pool: &PoolState) -> Result<Price> {
+        pools::calculate_price(pool)
+    }
+
+    /// Prices are stored under the canonical pair and inverted on the way
+    /// out when asked for the reverse orientation
+    fn get_price(&self, pair: &TokenPair) -> Option<Price> {
+        self.state.price(pair)
+    }
+
+    /// Fetch one monitored pool to prove the RPC is reachable
+    async fn heartbeat(&mut self) -> Result<HealthStatus> {
+        let Some(pool_id) = self.state.pool_ids().into_iter().next() else {
+            return Ok(HealthStatus::healthy("No pools monitored"));
+        };
+
+        let started = now();
+        let probe = self.fetch_pool_state(&pool_id).await.map(|_| ());
+        Ok(self.state.record_heartbeat(probe, started, STALE_POOL_AGE_MS))
+    }
+
+    /// Refetch every pool, keeping cached states that are already current
+    async fn periodic_sync(&mut self) -> Result<SyncResult> {
+        let started = now();
+        let fetched = match self.fetch_all_pools().await {
+            Ok(fetched) => fetched,
+            Err(e) => {
+                warn!("Cetus periodic sync failed: {}", e);
+                self.state.record_error(&e);
+                return Ok(SyncResult::failure(vec![e.to_string()]));
+            }
+        };
+
+        let updated_pools = self.state.reconcile(fetched);
+        self.state.record_full_sync();
+
+        let mut result = SyncResult::success(updated_pools.len(), now().saturating_sub(started));
+        result.updated_pools = updated_pools;
+        Ok(result)
+    }
+
+    fn is_healthy(&self) -> bool {
+        self.state.health.is_healthy
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal::Decimal;
+    use serde_json::{Value, json};
+
+    use crate::{
+        dex::cetus::CETUS_PACKAGE_ID,
+        testing::{cetus_pool_objects, rpc_server, test_pool, test_token},
+        utils::config::PoolConfig,
+    };
+
+    use super::*;
+
+    fn adapter() -> CetusAdapter {
+        let pool = test_pool("p1", DexId::Cetus, &test_token("SUI"), &test_token("USDC"), 1_000, 2_000);
+        let rpc = Arc::new(SuiRpcClient::new("http://127.0.0.1:9"));
+        CetusAdapter::new(DexConfig::new(DexId::Cetus, CETUS_PACKAGE_ID), rpc).with_pool(pool)
+    }
+
+    fn swap(base_to_quote: bool, amount_in: u64, amount_out: u64) -> SwapEvent {
+        SwapEvent {
+            dex_id: DexId::Cetus,
+            pool_id: "p1".into(),
+            amount_in,
+            amount_out,
+            base_to_quote,
+            timestamp: now(),
+            transaction_digest: "0xd1gest".into(),
+            sender: None,
+            block_height: Some(7),
+            sequence: None,
+            fee_amount: None,
+            sqrt_price_before: None,
+            sqrt_price_after: None,
+        }
+    }
+
+    fn reserves(adapter: &CetusAdapter) -> Result<(Decimal, Decimal)> {
+        let pool = adapter.state.pools.get("p1").ok_or_else(|| BotError::NotFound("p1".into()))?;
+        Ok((pool.reserve_a, pool.reserve_b))
+    }
+
+    #[test]
+    fn test_base_to_quote_swap_updates_reserves() -> Result<()> {
+        let mut adapter = adapter();
+        let (reserve_a, reserve_b) = reserves(&adapter)?;
+
+        let update = adapter.process_swap_event(swap(true, 1_000_000, 1_500_000))?;
+
+        assert_eq!(reserves(&adapter)?, (reserve_a + Decimal::from(1_000_000), reserve_b - Decimal::from(1_500_000)));
+        assert!(update.is_decrease());
+        assert!(update.trigger.is_realtime());
+        assert_eq!(adapter.get_price(&update.pair).map(|price| price.value), Some(update.new_price.value));
+        let reversed = TokenPair::new(update.pair.quote.clone(), update.pair.base.clone());
+        assert_eq!(adapter.get_price(&reversed).map(|price| price.value), update.new_price.inverse().map(|price| price.value));
+        Ok(())
+    }
+
+    #[test]
+    fn test_quote_to_base_swap_updates_reserves() -> Result<()> {
+        let mut adapter = adapter();
+        let (reserve_a, reserve_b) = reserves(&adapter)?;
+
+        let update = adapter.process_swap_event(swap(false, 3_000_000, 1_000_000))?;
+
+        assert_eq!(reserves(&adapter)?, (reserve_a - Decimal::from(1_000_000), reserve_b + Decimal::from(3_000_000)));
+        assert!(update.is_increase());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_fetch_pools_over_rpc() -> Result<()> {
+        let rpc = Arc::new(SuiRpcClient::new(rpc_server(cetus_pool_objects).await?));
+        let dex = config::DexConfig {
+            id: DexId::Cetus,
+            package_id: CETUS_PACKAGE_ID.into(),
+            event_type: String::new(),
+            enabled: true,
+            pools: vec![PoolConfig { address: "0xp2".into(), token_a: test_token("SUI"), token_b: test_token("USDC") }],
+            event_buffer_size: None,
+        };
+        let pool = test_pool("0xp1", DexId::Cetus, &test_token("DEEP"), &test_token("SUI"), 1, 1);
+        let adapter = CetusAdapter::from_config(&dex, rpc).with_pool(pool);
+
+        let fetched = adapter.fetch_pool_state(&"0xp2".to_string()).await?;
+        assert_eq!(fetched.reserve_a, Decimal::from(5_000));
+        assert_eq!(fetched.version, 9);
+        assert_eq!(fetched.token_b.symbol, "USDC");
+
+        let mut all = adapter.fetch_all_pools().await?;
+        all.sort_by(|a, b| a.pool_id.cmp(&b.pool_id));
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].token_a.symbol, "DEEP");
+        assert!(adapter.fetch_pool_state(&"0xp9".to_string()).await.is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_heartbeat_tracks_failures() -> Result<()> {
+        // Nothing listens on the discard port, so every fetch fails
+        let mut adapter = adapter();
+
+        let status = adapter.heartbeat().await?;
+        assert!(!status.is_healthy);
+        assert!(status.message.starts_with("Fetch failed"));
+        let status = adapter.heartbeat().await?;
+        assert_eq!(status.consecutive_failures, 2);
+        assert!(!adapter.is_healthy());
+
+        adapter.rpc = Arc::new(SuiRpcClient::new(rpc_server(cetus_pool_objects).await?));
+        if let Some(pool) = adapter.state.pools.get_mut("p1") {
+            pool.block_timestamp = now() - STALE_POOL_AGE_MS - 1;
+        }
+        let status = adapter.heartbeat().await?;
+        assert!(status.is_healthy);
+        assert_eq!(status.message, "Connected");
+        assert_eq!(adapter.state.health.consecutive_failures, 0);
+        assert_eq!(status.details.map(|details| details.stale_pools), Some(1));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_initialize_loads_pools_and_prices() -> Result<()> {
+        let mut adapter = adapter();
+        adapter.rpc = Arc::new(SuiRpcClient::new(rpc_server(cetus_pool_objects).await?));
+
+        adapter.initialize().await?;
+
+        let pool = adapter.state.pools.get("p1").ok_or_else(|| BotError::NotFound("p1".into()))?;
+        assert_eq!(pool.version, 9);
+        let pair = TokenPair::new(pool.token_a.clone(), pool.token_b.clone());
+        assert_eq!(adapter.get_price(&pair).map(|price| price.value), Some(pool.spot_price_a_to_b()));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_periodic_sync_skips_current_pools() -> Result<()> {
+        let mut adapter = adapter();
+        adapter.rpc = Arc::new(SuiRpcClient::new(rpc_server(cetus_pool_objects).await?));
+
+        let result = adapter.periodic_sync().await?;
+        assert!(result.success);
+        assert_eq!(result.updated_pools, vec!["p1".to_string()]);
+
+        let result = adapter.periodic_sync().await?;
+        assert_eq!(result.pools_synced, 0);
+        assert_eq!(adapter.state.stats.syncs_completed, 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_failed_periodic_sync_is_reported() -> Result<()> {
+        let mut adapter = adapter();
+
+        let result = adapter.periodic_sync().await?;
+
+        assert!(!result.success);
+        assert_eq!(adapter.state.stats.errors_encountered, 1);
+        assert_eq!(reserves(&adapter)?.0, Decimal::from(1_000_000_000_000u64));
+        Ok(())
+    }
+
+    #[test]
+    fn test_unknown_pool_is_not_found() {
+        let mut event = swap(true, 1, 1);
+        event.pool_id = "p9".into();
+
+        assert!(matches!(adapter().process_swap_event(event), Err(BotError::NotFound(_))));
+    }
+}
@@ -1,3 +1,9 @@
 pub mod adapter;
 pub mod events;
-pub mod pools;
\ No newline at end of file
+pub mod pools;
+
+/// Cetus CLMM package on Sui mainnet
+pub const CETUS_PACKAGE_ID: &str = "0x1eabed72c53feb3805120a081dc15963c204dc8d091542592abaf7a35689b2fb";
+
+/// Cetus CLMM package on Sui testnet
+pub const CETUS_TESTNET_PACKAGE_ID: &str = "0x0868b71c0cba55bf0faf6c40df8c179c67a4d0ba0e79965b68b3d72d7dfbf666";
@@ -0,0 +1,127 @@
+use rust_decimal::Decimal;
+use serde_json::Value;
+
+use crate::{
+    types::{BotError, DexId, PoolId, PoolState, Price, PriceSource, Result, TokenInfo, now},
+    utils::clmm::sqrt_price_x64_to_price,
+};
+
+/// Type name infix of Cetus CLMM pool objects
+pub const POOL_TYPE_IDENTIFIER: &str = "::pool::Pool<";
+
+/// Check if an object is a Cetus pool. Aftermath pools share the type name,
+/// so the square root price field tells the two apart.
+pub fn is_pool_object(object_type: &str, fields: &Value) -> bool {
+    object_type.contains(POOL_TYPE_IDENTIFIER) && fields.get("current_sqrt_price").is_some()
+}
+
+/// Spot price of token A in token B for a Cetus CLMM pool, derived from
+/// its square root price rather than reserves
+pub fn calculate_price(pool: &PoolState) -> Result<Price> {
+    if pool.sqrt_price_x64 == 0 {
+        return Err(BotError::dex(DexId::Cetus, format!("Pool {} has no sqrt price", pool.pool_id)));
+    }
+
+    let value = sqrt_price_x64_to_price(pool.sqrt_price_x64, pool.token_a.decimals, pool.token_b.decimals)
+        .ok_or_else(|| BotError::dex(DexId::Cetus, format!("Pool {} sqrt price out of range", pool.pool_id)))?;
+
+    Ok(Price {
+        value,
+        timestamp: pool.block_timestamp,
+        source: PriceSource::Calculated,
+    })
+}
+
+/// Cetus fee rates are expressed in units of 1/1_000_000
+const FEE_DENOMINATOR: u64 = 1_000_000;
+
+/// Build a pool state from the `fields` map of a Cetus pool object.
+/// Reserves are the pool's coin balances.
+pub fn parse_pool(
+    pool_id: PoolId,
+    token_a: TokenInfo,
+    token_b: TokenInfo,
+    fields: &Value,
+    version: u64,
+) -> Result<PoolState> {
+    let reserve_a = integer_field(fields, "coin_a")?;
+    let reserve_b = integer_field(fields, "coin_b")?;
+    let fee = integer_field(fields, "fee_rate")?;
+    let sqrt_price_x64 = integer_field(fields, "current_sqrt_price")?;
+    // I32 ticks are stored as their two's complement bits
+    let tick_bits = fields
+        .pointer("/current_tick_index/fields/bits")
+        .map(|bits| integer_value(bits, "current_tick_index"))
+        .transpose()?
+        .unwrap_or(0);
+    let is_paused = fields.get("is_pause").and_then(Value::as_bool).unwrap_or(false);
+
+    Ok(PoolState {
+        dex_id: DexId::Cetus,
+        pool_id,
+        token_a,
+        token_b,
+        reserve_a: Decimal::from(reserve_a),
+        reserve_b: Decimal::from(reserve_b),
+        fee_rate: Decimal::from(fee) / Decimal::from(FEE_DENOMINATOR),
+        block_timestamp: now(),
+        is_active: !is_paused,
+        version,
+        sqrt_price_x64,
+        current_tick_index: tick_bits as u32 as i32,
+        amp_factor: None,
+    })
+}
+
+fn integer_field(fields: &Value, name: &str) -> Result<u128> {
+    let value = fields
+        .get(name)
+        .ok_or_else(|| BotError::Parse(format!("Cetus pool is missing field {}", name)))?;
+    integer_value(value, name)
+}
+
+/// Read an integer, which Sui JSON encodes either as a number or a string
+fn integer_value(value: &Value, name: &str) -> Result<u128> {
+    value
+        .as_u64()
+        .map(u128::from)
+        .or_else(|| value.as_str().and_then(|s| s.parse().ok()))
+        .ok_or_else(|| BotError::Parse(format!("Cetus pool field {} is not an integer: {}", name, value)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POOL_FIXTURE: &str = r#"{
+        "coin_a": "1265398725663925",
+        "coin_b": "4419862339871",
+        "current_sqrt_price": "1443425838891463458",
+        "current_tick_index": { "type": "0x714a::i32::I32", "fields": { "bits": 4294913234 } },
+        "fee_rate": "2500",
+        "id": { "id": "0xcf994611fd4c48e277ce3ffd4d4364c914af2c3cbb05f7bf6facd371de688630" },
+        "is_pause": false,
+        "liquidity": "5126787438519497",
+        "tick_spacing": 60
+    }"#;
+
+    #[test]
+    fn test_parse_pool_fixture() -> Result<()> {
+        let fields: Value = serde_json::from_str(POOL_FIXTURE)?;
+        let pool = parse_pool(
+            "0xcf994611".into(),
+            TokenInfo::sui(),
+            TokenInfo::new("USDC", "0xdba3::usdc::USDC", 6),
+            &fields,
+            7,
+        )?;
+
+        assert_eq!(pool.reserve_a, Decimal::from(1_265_398_725_663_925u64));
+        assert_eq!(pool.reserve_b, Decimal::from(4_419_862_339_871u64));
+        assert_eq!(pool.fee_rate, Decimal::new(25, 4));
+        assert_eq!(pool.sqrt_price_x64, 1_443_425_838_891_463_458);
+        assert_eq!(pool.current_tick_index, -54_062);
+        assert!(pool.is_active());
+        Ok(())
+    }
+}
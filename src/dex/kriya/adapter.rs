@@ -0,0 +1,289 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tracing::{debug, warn};
+
+use crate::{
+    client::sui_rpc::{SuiObjectData, SuiObjectDataOptions, SuiRpcClient},
+    dex::{adapter::DexAdapter, kriya::pools, state::{DexConfig, DexState}},
+    event::parsers::kriya::{self, KriyaEventParser},
+    types::{
+        BotError, DexId, HealthStatus, Network, PoolId, PoolState, Price, PriceSource, PriceUpdate, RawEvent, Result,
+        SwapEvent, SyncResult, TokenPair, UpdateTrigger, now,
+    },
+    utils::config,
+};
+
+/// Pools not updated for this long count as stale in heartbeats
+const STALE_POOL_AGE_MS: u64 = 3_600_000;
+
+/// Kriya constant-product adapter keeping pool states current from swap events
+pub struct KriyaAdapter {
+    state: DexState,
+    rpc: Arc<SuiRpcClient>,
+    network: Network,
+}
+
+impl KriyaAdapter {
+    pub fn new(config: DexConfig, rpc: Arc<SuiRpcClient>) -> Self {
+        Self {
+            state: DexState::new(config),
+            rpc,
+            network: Network::SuiMainnet,
+        }
+    }
+
+    /// Network the RPC client and package belong to, mainnet by default
+    pub fn with_network(mut self, network: Network) -> Self {
+        self.network = network;
+        self
+    }
+
+    /// Adapter monitoring the pools listed in the DEX's config entry
+    pub fn from_config(dex: &config::DexConfig, rpc: Arc<SuiRpcClient>) -> Self {
+        let mut config = DexConfig::new(DexId::Kriya, dex.package_id.clone());
+        for pool in &dex.pools {
+            let pair = TokenPair::new(pool.token_a.clone(), pool.token_b.clone());
+            config.monitored_pairs.push(pair.clone());
+            config.pool_addresses.insert(pair, pool.address.clone());
+        }
+        Self::new(config, rpc)
+    }
+
+    pub fn with_pool(mut self, pool: PoolState) -> Self {
+        self.state.pools.insert(pool.pool_id.clone(), pool);
+        self
+    }
+
+    fn parse_object(&self, object: SuiObjectData) -> Result<PoolState> {
+        let (token_a, token_b) = self.state.pool_tokens(&object.object_id)?;
+        pools::parse_pool(object.object_id, token_a, token_b, &object.fields, object.version)
+    }
+}
+
+#[async_trait]
+impl DexAdapter for KriyaAdapter {
+    fn dex_id(&self) -> DexId {
+        DexId::Kriya
+    }
+
+    fn network(&self) -> Network {
+        self.network
+    }
+
+    fn state(&self) -> &DexState {
+        &self.state
+    }
+
+    fn state_mut(&mut self) -> &mut DexState {
+        &mut self.state
+    }
+
+    /// Load every monitored pool from RPC
+    async fn initialize(&mut self) -> Result<()> {
+        let fetched = self.fetch_all_pools().await?;
+        let loaded = self.state.reconcile(fetched).len();
+        self.state.record_full_sync();
+        debug!("Kriya adapter initialized with {} pools", loaded);
+        Ok(())
+    }
+
+    async fn fetch_pool_state(&self, pool_id: &PoolId) -> Result<PoolState> {
+        // Resolve tokens first so unmonitored pools cost no request
+        self.state.pool_tokens(pool_id)?;
+        let object = self.rpc
+            .batch_get_objects(vec![pool_id.clone()], Some(SuiObjectDataOptions::full_content()))
+            .await?
+            .pop()
+            .ok_or_else(|| BotError::NotFound(format!("Pool {}", pool_id)))?;
+        self.parse_object(object)
+    }
+
+    async fn fetch_all_pools(&self) -> Result<Vec<PoolState>> {
+        self.rpc
+            .batch_get_objects(self.state.pool_ids(), Some(SuiObjectDataOptions::full_content()))
+            .await?
+            .into_iter()
+            .map(|object| self.parse_object(object))
+            .collect()
+    }
+
+    /// Kriya events name only the input coin, so the direction needs the
+    /// tokens of the pool the swap went through
+    fn parse_event(&self, raw: RawEvent) -> Result<SwapEvent> {
+        let (token_a, _) = self.state.pool_tokens(&kriya::pool_id(&raw)?)?;
+        KriyaEventParser::parse(&raw, &token_a)
+    }
+
+    fn process_swap_event(&mut self, event: SwapEvent) -> Result<PriceUpdate> {
+        let pool = self.state.pools
+            .get_mut(&event.pool_id)
+            .ok_or_else(|| BotError::NotFound(format!("Pool {}", event.pool_id)))?;
+
+        let old_price = pool
+            .is_initialized()
+            .then(|| Price::new(pool.spot_price_a_to_b(), PriceSource::Calculated));
+        pool.apply_swap(&event)?;
+        let new_price = Price::new(
+            pool.spot_price_a_to_b(),
+            PriceSource::Event {
+                block_height: event.block_height.unwrap_or(0),
+                transaction_digest: event.transaction_digest.clone(),
+            },
+        );
+        let pair = TokenPair::new(pool.token_a.clone(), pool.token_b.clone());
+
+        self.state.store_price(&pair, new_price.clone());
+        self.state.health.last_event = Some(event.timestamp);
+        self.state.stats.events_processed += 1;
+
+        Ok(PriceUpdate::new(
+            DexId::Kriya,
+            pair,
+            old_price,
+            new_price,
+            UpdateTrigger::SwapEvent {
+                transaction_digest: event.transaction_digest,
+                block_height: event.block_height,
+            },
+        ))
+    }
+
+    fn calculate_price(&self, pool: &PoolState) -> Result<Price> {
+        pools::calculate_price(pool)
+    }
+
+    fn get_price(&self, pair: &TokenPair) -> Option<Price> {
+        self.state.price(pair)
+    }
+
+    /// Fetch one monitored pool to prove the RPC is reachable
+    async fn heartbeat(&mut self) -> Result<HealthStatus> {
+        let Some(pool_id) = self.state.pool_ids().into_iter().next() else {
+            return Ok(HealthStatus::healthy("No pools monitored"));
+        };
+
+        let started = now();
+        let probe = self.fetch_pool_state(&pool_id).await.map(|_| ());
+        Ok(self.state.record_heartbeat(probe, started, STALE_POOL_AGE_MS))
+    }
+
+    /// Refetch every pool, keeping cached states that are already current
+    async fn periodic_sync(&mut self) -> Result<SyncResult> {
+        let started = now();
+        let fetched = match self.fetch_all_pools().await {
+            Ok(fetched) => fetched,
+            Err(e) => {
+                warn!("Kriya periodic sync failed: {}", e);
+                self.state.record_error(&e);
+                return Ok(SyncResult::failure(vec![e.to_string()]));
+            }
+        };
+
+        let updated_pools = self.state.reconcile(fetched);
+        self.state.record_full_sync();
+
+        let mut result = SyncResult::success(updated_pools.len(), now().saturating_sub(started));
+        result.updated_pools = updated_pools;
+        Ok(result)
+    }
+
+    fn is_healthy(&self) -> bool {
+        self.state.health.is_healthy
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal::Decimal;
+    use serde_json::{Value, json};
+
+    use crate::{
+        dex::kriya::KRIYA_PACKAGE_ID,
+        event::parsers::kriya::SWAP_EVENT_IDENTIFIER,
+        testing::{rpc_server, test_pool, test_token},
+        types::TokenInfo,
+    };
+
+    use super::*;
+
+    /// `rpc_server` handler answering with a Kriya pool object for each requested ID
+    fn kriya_pool_objects(request: Value) -> Value {
+        let ids = request["params"][0].as_array().cloned().unwrap_or_default();
+        ids.iter()
+            .map(|id| json!({
+                "data": {
+                    "objectId": id,
+                    "version": "4",
+                    "content": {
+                        "fields": {
+                            "reserve_x": "8000",
+                            "reserve_y": "2000",
+                            "lp_fee_percent": "25",
+                            "protocol_fee_percent": "5",
+                            "is_swap_enabled": true
+                        }
+                    }
+                }
+            }))
+            .collect()
+    }
+
+    fn adapter(rpc: Arc<SuiRpcClient>) -> KriyaAdapter {
+        let pool = test_pool("p1", DexId::Kriya, &test_token("SUI"), &test_token("USDC"), 1_000, 2_000);
+        KriyaAdapter::new(DexConfig::new(DexId::Kriya, KRIYA_PACKAGE_ID), rpc).with_pool(pool)
+    }
+
+    fn offline() -> Arc<SuiRpcClient> {
+        Arc::new(SuiRpcClient::new("http://127.0.0.1:9"))
+    }
+
+    #[test]
+    fn test_swap_event_updates_reserves() -> Result<()> {
+        let mut adapter = adapter(offline());
+        let event_type = format!("{}{}{}>", KRIYA_PACKAGE_ID, SWAP_EVENT_IDENTIFIER, TokenInfo::sui().address.unwrap_or_default());
+        let data = json!({ "pool_id": "p1", "amount_in": "1000000", "amount_out": "1500000" });
+
+        let event = adapter.parse_event(RawEvent::new(data, KRIYA_PACKAGE_ID.into(), event_type))?;
+        let update = adapter.process_swap_event(event)?;
+
+        let pool = adapter.state.pools.get("p1").ok_or_else(|| BotError::NotFound("p1".into()))?;
+        assert_eq!(pool.reserve_a, Decimal::from(1_000_001_000_000u64));
+        assert_eq!(pool.reserve_b, Decimal::from(1_999_998_500_000u64));
+        assert!(update.is_decrease());
+        assert_eq!(adapter.get_price(&update.pair).map(|price| price.value), Some(update.new_price.value));
+        Ok(())
+    }
+
+    #[test]
+    fn test_event_for_unmonitored_pool_is_rejected() {
+        let event_type = format!("{}{}0x2::sui::SUI>", KRIYA_PACKAGE_ID, SWAP_EVENT_IDENTIFIER);
+        let data = json!({ "pool_id": "p9", "amount_in": "1", "amount_out": "1" });
+
+        let result = adapter(offline()).parse_event(RawEvent::new(data, KRIYA_PACKAGE_ID.into(), event_type));
+
+        assert!(matches!(result, Err(BotError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_sync_over_rpc() -> Result<()> {
+        let mut adapter = adapter(Arc::new(SuiRpcClient::new(rpc_server(kriya_pool_objects).await?)));
+
+        adapter.initialize().await?;
+        let pool = adapter.fetch_pool_state(&"p1".to_string()).await?;
+        assert_eq!((pool.reserve_a, pool.fee_rate), (Decimal::from(8_000), Decimal::new(3, 3)));
+        assert_eq!(adapter.periodic_sync().await?.pools_synced, 0);
+        assert!(adapter.heartbeat().await?.is_healthy);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_failures_are_reported_not_raised() -> Result<()> {
+        let mut adapter = adapter(offline());
+
+        assert!(!adapter.periodic_sync().await?.success);
+        assert!(!adapter.heartbeat().await?.is_healthy);
+        assert_eq!(adapter.state.stats.errors_encountered, 2);
+        Ok(())
+    }
+}
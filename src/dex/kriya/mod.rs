@@ -0,0 +1,5 @@
+pub mod adapter;
+pub mod pools;
+
+/// Kriya spot DEX package on Sui mainnet
+pub const KRIYA_PACKAGE_ID: &str = "0xa0eba10b173538c8fecca1dff298e488402cc9ff374f8a12ca7758eebe830b66";
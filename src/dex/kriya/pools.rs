@@ -0,0 +1,142 @@
+use rust_decimal::Decimal;
+use serde_json::Value;
+
+use crate::types::{BotError, DexId, PoolId, PoolState, Price, PriceSource, Result, TokenInfo, now};
+
+/// Type name suffix of Kriya constant-product pool objects
+pub const POOL_TYPE_IDENTIFIER: &str = "amm_v2::Pool";
+
+/// Kriya fee fields are expressed in units of 1/10_000
+const FEE_DENOMINATOR: u64 = 10_000;
+
+/// Check if a Move object type is a Kriya pool
+pub fn is_pool_type(object_type: &str) -> bool {
+    object_type.contains(POOL_TYPE_IDENTIFIER)
+}
+
+/// Spot price of token A in token B for a Kriya constant-product pool,
+/// from its reserves adjusted for token decimals
+pub fn calculate_price(pool: &PoolState) -> Result<Price> {
+    if pool.reserve_a.is_zero() || pool.reserve_b.is_zero() {
+        return Err(BotError::dex(DexId::Kriya, format!("Pool {} has no liquidity", pool.pool_id)));
+    }
+
+    let scale = |decimals: u8| Decimal::from(10u64.pow(decimals as u32));
+    Ok(Price {
+        value: pool.spot_price_a_to_b() * scale(pool.token_a.decimals) / scale(pool.token_b.decimals),
+        timestamp: pool.block_timestamp,
+        source: PriceSource::Calculated,
+    })
+}
+
+/// Build a pool state from the `fields` map of a Kriya pool object.
+/// `fee_rate` combines the LP and protocol fees.
+pub fn parse_pool(
+    pool_id: PoolId,
+    token_a: TokenInfo,
+    token_b: TokenInfo,
+    fields: &Value,
+    version: u64,
+) -> Result<PoolState> {
+    let reserve_a = u64_field(fields, "reserve_x")?;
+    let reserve_b = u64_field(fields, "reserve_y")?;
+    let fee = u64_field(fields, "lp_fee_percent")? + u64_field(fields, "protocol_fee_percent")?;
+    let is_active = fields.get("is_swap_enabled").and_then(Value::as_bool).unwrap_or(true);
+
+    Ok(PoolState {
+        dex_id: DexId::Kriya,
+        pool_id,
+        token_a,
+        token_b,
+        reserve_a: Decimal::from(reserve_a),
+        reserve_b: Decimal::from(reserve_b),
+        fee_rate: Decimal::from(fee) / Decimal::from(FEE_DENOMINATOR),
+        block_timestamp: now(),
+        is_active,
+        version,
+        sqrt_price_x64: 0,
+        current_tick_index: 0,
+        amp_factor: None,
+    })
+}
+
+/// Read an integer field, which Sui JSON encodes either as a number or a string
+fn u64_field(fields: &Value, name: &str) -> Result<u64> {
+    let value = fields
+        .get(name)
+        .ok_or_else(|| BotError::Parse(format!("Kriya pool is missing field {}", name)))?;
+
+    value
+        .as_u64()
+        .or_else(|| value.as_str().and_then(|s| s.parse().ok()))
+        .ok_or_else(|| BotError::Parse(format!("Kriya pool field {} is not an integer: {}", name, value)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POOL_FIXTURE: &str = r#"{
+        "id": { "id": "0x5af4976b871fa1813362f352fa4cada3883a96191bb7212db1bd5d13685ae305" },
+        "reserve_x": "4211873962145",
+        "reserve_y": "8502367113",
+        "lp_supply": { "type": "0x2::balance::Supply<LSP>", "fields": { "value": "189262710458" } },
+        "lp_fee_percent": "25",
+        "protocol_fee_percent": "5",
+        "is_stable": false,
+        "is_swap_enabled": true
+    }"#;
+
+    fn parse(fields: &Value) -> Result<PoolState> {
+        parse_pool(
+            "0x5af4976b".into(),
+            TokenInfo::new("SUI", "0x2::sui::SUI", 9),
+            TokenInfo::new("USDC", "0x5d4b::coin::COIN", 6),
+            fields,
+            42,
+        )
+    }
+
+    #[test]
+    fn test_parse_pool_fixture() -> Result<()> {
+        let fields: Value = serde_json::from_str(POOL_FIXTURE)?;
+        let pool = parse(&fields)?;
+
+        assert_eq!(pool.dex_id, DexId::Kriya);
+        assert_eq!(pool.reserve_a, Decimal::from(4_211_873_962_145u64));
+        assert_eq!(pool.reserve_b, Decimal::from(8_502_367_113u64));
+        assert_eq!(pool.fee_rate, Decimal::new(3, 3));
+        assert!(pool.is_active());
+        assert_eq!(pool.version, 42);
+        Ok(())
+    }
+
+    #[test]
+    fn test_calculate_price_adjusts_decimals() -> Result<()> {
+        let fields: Value = serde_json::from_str(POOL_FIXTURE)?;
+        let mut pool = parse(&fields)?;
+        pool.reserve_a = Decimal::from(1_000_000_000_000u64);
+        pool.reserve_b = Decimal::from(3_500_000_000u64);
+
+        assert_eq!(calculate_price(&pool)?.value, Decimal::new(35, 1));
+        pool.reserve_a = Decimal::ZERO;
+        assert!(calculate_price(&pool).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_missing_reserve_is_parse_error() -> Result<()> {
+        let mut fields: Value = serde_json::from_str(POOL_FIXTURE)?;
+        if let Some(map) = fields.as_object_mut() {
+            map.remove("reserve_y");
+        }
+        assert!(matches!(parse(&fields), Err(BotError::Parse(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_pool_type_identifier() {
+        assert!(!is_pool_type("0xa0eba1::spot_dex::Pool<0x2::sui::SUI, 0x5d4b::coin::COIN>"));
+        assert!(is_pool_type("0xa0eba1::amm_v2::Pool<0x2::sui::SUI, 0x5d4b::coin::COIN>"));
+    }
+}
@@ -1,26 +1,61 @@
-use crate::{dex::adapter::DexAdapter, types::{DexId, HealthStatus, Price, Result, StateSnapshot, SyncResult, TokenPair}};
-use std::collections::HashMap;
+use crate::{dex::adapter::DexAdapter, types::{BotError, DexId, HealthStatus, Network, PoolEvent, PoolId, PoolState, PoolStateDelta, Price, Result, StateSnapshot, StateSnapshotBuilder, SyncResult, TokenPair, now}, utils::config::{PoolConfig, SyncConfig}};
+use rust_decimal::Decimal;
+use std::{collections::HashMap, time::Duration};
+use tokio::sync::broadcast;
+use tracing::{debug, warn};
+
+/// Consecutive heartbeat failures after which a full resync is requested
+pub const EMERGENCY_SYNC_FAILURES: u32 = 3;
 
 /// Manages all DEX adapters
 pub struct DexManager {
     dexes: HashMap<DexId, Box<dyn DexAdapter>>,
+    pool_events: broadcast::Sender<PoolEvent>,
+    pool_count: HashMap<DexId, usize>,
+    health_statuses: HashMap<DexId, HealthStatus>,
+    needs_emergency_sync: bool,
+    dex_networks: HashMap<DexId, Network>,
+    /// Prices and pool states older than this are ignored
+    state_ttl: Duration,
 }
 
 impl DexManager {
-    pub fn new() -> Self {
+    pub fn new(config: &SyncConfig) -> Self {
+        let (pool_events, _) = broadcast::channel(config.event_channel_capacity);
         
         Self {
             dexes: HashMap::new(),
+            pool_events,
+            pool_count: HashMap::new(),
+            health_statuses: HashMap::new(),
+            needs_emergency_sync: false,
+            dex_networks: HashMap::new(),
+            state_ttl: Duration::from_secs(config.state_ttl_seconds),
         }
     }
     
-    // TODO Phase 4: Implement DEX registration
+    /// Subscribe to pool lifecycle notifications
+    pub fn subscribe_pool_events(&self) -> broadcast::Receiver<PoolEvent> {
+        self.pool_events.subscribe()
+    }
     
-    /// Register a new DEX adapter
-    pub fn register_dex(&mut self, _dex: Box<dyn DexAdapter>) -> Result<()> {
-        // TODO: Insert into dexes HashMap
-        // TODO: Prevent duplicate registrations
-        todo!("Register DEX adapter")
+    /// Broadcast a pool event; having no subscribers is not an error
+    fn emit(&self, event: PoolEvent) {
+        let _ = self.pool_events.send(event);
+    }
+    
+    /// Register a new DEX adapter, rejecting a second adapter for the same DEX
+    pub fn register_dex(&mut self, dex: Box<dyn DexAdapter>) -> Result<()> {
+        let dex_id = dex.dex_id();
+        if self.dexes.contains_key(&dex_id) {
+            return Err(BotError::InvalidState(format!("DEX {} already registered", dex_id)));
+        }
+        
+        self.pool_count.insert(dex_id, dex.state().pools.len());
+        self.dex_networks.insert(dex_id, dex.network());
+        self.dexes.insert(dex_id, dex);
+        debug!("Registered DEX {}", dex_id);
+        Ok(())
     }
     
     /// Initialize all registered DEXs
@@ -31,39 +66,260 @@ impl DexManager {
         todo!("Initialize all DEX adapters")
     }
     
-    /// Get all current prices for a pair across all DEXs
-    pub fn get_all_prices(&self, _pair: &TokenPair) -> Vec<(DexId, Price)> {
-        // TODO: Query each DEX for price
-        // TODO: Filter out None results
-        // TODO: Return Vec of (dex_id, price)
-        todo!("Get prices from all DEXs")
+    /// Get all current prices for a pair across all DEXs, quoted for the
+    /// pair's canonical orientation
+    pub fn get_all_prices(&self, pair: &TokenPair) -> Vec<(DexId, Price)> {
+        let pair = pair.canonical();
+        self.dexes
+            .iter()
+            .filter_map(|(dex_id, dex)| Some((*dex_id, dex.get_price(&pair)?)))
+            .collect()
+    }
+    
+    /// Prices for a pair that are younger than the state TTL
+    fn fresh_prices(&self, pair: &TokenPair) -> impl Iterator<Item = (DexId, Price)> {
+        let max_age_ms = self.state_ttl.as_millis() as u64;
+        self.get_all_prices(pair)
+            .into_iter()
+            .filter(move |(_, price)| now().saturating_sub(price.timestamp) <= max_age_ms)
+    }
+    
+    /// Highest fresh price for a pair across DEXs
+    pub fn get_best_price(&self, pair: &TokenPair) -> Option<(DexId, Price)> {
+        self.fresh_prices(pair).max_by(|(_, a), (_, b)| a.value.cmp(&b.value))
+    }
+    
+    /// Lowest fresh price for a pair across DEXs
+    pub fn get_worst_price(&self, pair: &TokenPair) -> Option<(DexId, Price)> {
+        self.fresh_prices(pair).min_by(|(_, a), (_, b)| a.value.cmp(&b.value))
+    }
+    
+    /// Gap between the best and worst fresh price, as a percentage of the worst
+    pub fn get_price_spread(&self, pair: &TokenPair) -> Option<Decimal> {
+        let (_, best) = self.get_best_price(pair)?;
+        let (_, worst) = self.get_worst_price(pair)?;
+        ((best.value - worst.value) * Decimal::ONE_HUNDRED).checked_div(worst.value)
     }
     
     /// Get specific DEX adapter
-    pub fn get_dex(&self, _dex_id: &DexId) -> Option<&dyn DexAdapter> {
-        // TODO: Return reference to adapter
-        todo!("Get DEX adapter reference")
+    pub fn get_dex(&self, dex_id: &DexId) -> Option<&dyn DexAdapter> {
+        self.dexes.get(dex_id).map(|dex| dex.as_ref())
     }
     
     /// Get mutable DEX adapter
-    pub fn get_dex_mut(&mut self, _dex_id: &DexId) -> Option<&mut Box<dyn DexAdapter>> {
-        // TODO: Return mutable reference
-        todo!("Get mutable DEX adapter reference")
+    pub fn get_dex_mut(&mut self, dex_id: &DexId) -> Option<&mut Box<dyn DexAdapter>> {
+        self.dexes.get_mut(dex_id)
+    }
+    
+    /// Start tracking a pool on its DEX
+    pub fn register_pool(&mut self, state: PoolState) -> Result<()> {
+        let (pool_id, dex_id) = (state.pool_id.clone(), state.dex_id);
+        let dex = self.dexes
+            .get_mut(&dex_id)
+            .ok_or_else(|| BotError::NotFound(format!("DEX {} not registered", dex_id)))?;
+        
+        if dex.state_mut().pools.insert(pool_id.clone(), state).is_none() {
+            *self.pool_count.entry(dex_id).or_insert(0) += 1;
+        }
+        self.emit(PoolEvent::Registered(pool_id, dex_id));
+        Ok(())
+    }
+    
+    /// Stop tracking a pool, returning its last known state
+    pub fn remove_pool(&mut self, pool_id: &PoolId) -> Option<PoolState> {
+        let removed = self.dexes
+            .values_mut()
+            .find_map(|dex| dex.state_mut().pools.remove(pool_id))?;
+        
+        if let Some(count) = self.pool_count.get_mut(&removed.dex_id) {
+            *count = count.saturating_sub(1);
+        }
+        self.emit(PoolEvent::Removed(pool_id.clone()));
+        Some(removed)
+    }
+    
+    /// Tracked pools whose last state is older than the state TTL
+    pub fn get_stale_pools(&self) -> Vec<PoolId> {
+        let (now, max_age_ms) = (now(), self.state_ttl.as_millis() as u64);
+        self.all_pools()
+            .filter(|pool| now.saturating_sub(pool.block_timestamp) > max_age_ms)
+            .map(|pool| pool.pool_id.clone())
+            .collect()
+    }
+    
+    /// Stop tracking every stale pool, returning the removed IDs
+    pub fn remove_stale_pools(&mut self) -> Vec<PoolId> {
+        let stale = self.get_stale_pools();
+        for pool_id in &stale {
+            if let Some(pool) = self.remove_pool(pool_id) {
+                warn!(
+                    "Evicted stale pool {} on {}, last updated {}ms ago",
+                    pool_id, pool.dex_id, now().saturating_sub(pool.block_timestamp)
+                );
+            }
+        }
+        stale
+    }
+    
+    /// Look up a tracked pool on the given DEX
+    pub fn get_pool(&self, dex_id: &DexId, pool_id: &PoolId) -> Option<&PoolState> {
+        self.dexes.get(dex_id)?.state().pools.get(pool_id)
     }
     
-    /// Get healthy DEXs
-    pub fn healthy_dexes(&self) -> Vec<DexId> {
-        // TODO: Filter DEXs by is_healthy()
-        vec![]
+    /// All tracked pools across DEXs
+    pub fn all_pools(&self) -> impl Iterator<Item = &PoolState> {
+        self.dexes.values().flat_map(|dex| dex.state().pools.values())
+    }
+    
+    /// Number of tracked pools per DEX
+    pub fn pool_count_by_dex(&self) -> &HashMap<DexId, usize> {
+        &self.pool_count
+    }
+    
+    /// Export all tracked pools as config entries, grouped by DEX
+    pub fn export_pools(&self) -> HashMap<DexId, Vec<PoolConfig>> {
+        self.dexes
+            .iter()
+            .map(|(dex_id, dex)| {
+                let pools = dex.state().pools
+                    .values()
+                    .map(|pool| PoolConfig {
+                        address: pool.pool_id.clone(),
+                        token_a: pool.token_a.clone(),
+                        token_b: pool.token_b.clone(),
+                    })
+                    .collect();
+                (*dex_id, pools)
+            })
+            .collect()
+    }
+    
+    /// Tracked pools accepted by `filter`, as config entries grouped by the
+    /// network and DEX they are fetched from
+    pub fn group_pools_by_network_and_dex(
+        &self,
+        filter: impl Fn(&PoolState) -> bool,
+    ) -> HashMap<(Network, DexId), Vec<PoolConfig>> {
+        let mut groups: HashMap<(Network, DexId), Vec<PoolConfig>> = HashMap::new();
+        for (dex_id, dex) in &self.dexes {
+            let network = self.dex_networks.get(dex_id).copied().unwrap_or_else(|| dex.network());
+            for pool in dex.state().pools.values().filter(|pool| filter(pool)) {
+                groups.entry((network, *dex_id)).or_default().push(PoolConfig {
+                    address: pool.pool_id.clone(),
+                    token_a: pool.token_a.clone(),
+                    token_b: pool.token_b.clone(),
+                });
+            }
+        }
+        groups
+    }
+    
+    /// Store a fresh pool state, ignoring updates older than the current version.
+    /// States for untracked pools register them. Returns the change that was
+    /// applied, or `None` for stale updates.
+    pub fn update_pool_state(&mut self, incoming: PoolState) -> Result<Option<PoolStateDelta>> {
+        let dex = self.dexes
+            .get_mut(&incoming.dex_id)
+            .ok_or_else(|| BotError::NotFound(format!("DEX {} not registered", incoming.dex_id)))?;
+        
+        let pools = &mut dex.state_mut().pools;
+        if let Some(current) = pools.get_mut(&incoming.pool_id)
+            && incoming.version <= current.version
+        {
+            // An unchanged object still proves the state is current as of this read
+            if incoming.version == current.version {
+                current.block_timestamp = current.block_timestamp.max(incoming.block_timestamp);
+            }
+            debug!("Ignoring stale update for pool {}", incoming.pool_id);
+            return Ok(None);
+        }
+        let current = pools.get(&incoming.pool_id);
+        
+        let delta = incoming.delta_from(current);
+        let (pool_id, dex_id) = (incoming.pool_id.clone(), incoming.dex_id);
+        if pools.insert(pool_id.clone(), incoming).is_none() {
+            *self.pool_count.entry(dex_id).or_insert(0) += 1;
+            self.emit(PoolEvent::Registered(pool_id, dex_id));
+        } else {
+            self.emit(PoolEvent::StateUpdated(delta.clone()));
+        }
+        Ok(Some(delta))
+    }
+    
+    /// Apply a batch of pool states, keeping only newer versions
+    pub fn merge_pool_states(&mut self, states: Vec<PoolState>) -> MergeResult {
+        let mut result = MergeResult::default();
+        for state in states {
+            if !state.is_initialized() {
+                debug!("Rejecting uninitialized state for pool {}", state.pool_id);
+                result.rejected_invalid += 1;
+                continue;
+            }
+            
+            let pool_id = state.pool_id.clone();
+            match self.update_pool_state(state) {
+                Ok(Some(_)) => result.accepted += 1,
+                Ok(None) => result.rejected_stale += 1,
+                Err(e) => {
+                    debug!("Rejecting state for pool {}: {}", pool_id, e);
+                    result.rejected_invalid += 1;
+                }
+            }
+        }
+        result
+    }
+    
+    /// Healthy DEXs grouped by the network they run on
+    pub fn healthy_dexes(&self) -> HashMap<Network, Vec<DexId>> {
+        let mut healthy: HashMap<Network, Vec<DexId>> = HashMap::new();
+        for (dex_id, dex) in &self.dexes {
+            if !dex.is_healthy() {
+                continue;
+            }
+            let network = self.dex_networks.get(dex_id).copied().unwrap_or_else(|| dex.network());
+            healthy.entry(network).or_default().push(*dex_id);
+        }
+        healthy
     }
     
     // TODO Phase 5: Health & sync operations
     
-    /// Perform heartbeat for all DEXs
+    /// Perform heartbeat for all DEXs. A failed heartbeat counts as one more
+    /// consecutive failure on top of the DEX's last known status.
     pub async fn heartbeat_all(&mut self) -> Result<HashMap<DexId, HealthStatus>> {
-        // TODO: Call heartbeat() on each DEX
-        // TODO: Collect results
-        todo!("Heartbeat all DEXs")
+        let mut results = HashMap::new();
+        for (dex_id, dex) in self.dexes.iter_mut() {
+            let status = match dex.heartbeat().await {
+                Ok(status) => status,
+                Err(e) => {
+                    let prev_failures = self.health_statuses
+                        .get(dex_id)
+                        .map_or(0, |status| status.consecutive_failures);
+                    HealthStatus::unhealthy(e.to_string(), prev_failures + 1)
+                }
+            };
+            self.health_statuses.insert(*dex_id, status.clone());
+            results.insert(*dex_id, status);
+        }
+        
+        self.needs_emergency_sync = false;
+        for (dex_id, status) in &self.health_statuses {
+            if status.consecutive_failures >= EMERGENCY_SYNC_FAILURES {
+                warn!("DEX {} failed {} heartbeats in a row, requesting full sync", dex_id, status.consecutive_failures);
+                self.needs_emergency_sync = true;
+            }
+        }
+        Ok(results)
+    }
+    
+    /// Whether a DEX has failed enough heartbeats in a row to need a full resync
+    pub fn needs_emergency_sync(&self) -> bool {
+        self.needs_emergency_sync
+    }
+    
+    /// Last heartbeat status of each DEX
+    pub fn health_statuses(&self) -> &HashMap<DexId, HealthStatus> {
+        &self.health_statuses
     }
     
     /// Perform periodic sync for all DEXs
@@ -73,7 +329,176 @@ impl DexManager {
         todo!("Sync all DEXs")
     }
 
-    pub fn get_state_snapshot(&self) -> Result<StateSnapshot>{
-        todo!("get_state_snapshot")
-    } 
-}
\ No newline at end of file
+    /// Copy of every tracked pool, with spot prices and tokens
+    pub fn get_state_snapshot(&self) -> Result<StateSnapshot> {
+        Ok(StateSnapshotBuilder::new().build(self))
+    }
+    
+    /// Number of registered DEXs
+    pub fn dex_count(&self) -> usize {
+        self.dexes.len()
+    }
+}
+
+/// Outcome of `DexManager::merge_pool_states`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MergeResult {
+    pub accepted: usize,
+    pub rejected_stale: usize,
+    pub rejected_invalid: usize,
+}
+
+impl Default for DexManager {
+    fn default() -> Self {
+        Self::new(&SyncConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testing::{MockDexAdapter, test_token}, types::{PriceSource, TokenInfo}};
+
+    fn pool(version: u64, reserve: u64) -> PoolState {
+        PoolState {
+            dex_id: DexId::Cetus,
+            pool_id: "0xpool".into(),
+            token_a: TokenInfo::new("SUI", "0x2::sui::SUI", 9),
+            token_b: TokenInfo::new("USDC", "0xusdc", 6),
+            reserve_a: Decimal::from(reserve),
+            reserve_b: Decimal::from(reserve),
+            fee_rate: Decimal::ZERO,
+            block_timestamp: 0,
+            is_active: true,
+            version,
+            sqrt_price_x64: 0,
+            current_tick_index: 0,
+            amp_factor: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_repeated_heartbeat_failures_request_emergency_sync() -> Result<()> {
+        let mut manager = DexManager::default();
+        manager.register_dex(Box::new(MockDexAdapter::new(DexId::Cetus)))?;
+        manager.register_dex(Box::new(MockDexAdapter::new(DexId::Turbos).unhealthy()))?;
+
+        for _ in 0..EMERGENCY_SYNC_FAILURES - 1 {
+            manager.heartbeat_all().await?;
+        }
+        assert!(!manager.needs_emergency_sync());
+
+        let statuses = manager.heartbeat_all().await?;
+        assert!(statuses.get(&DexId::Cetus).is_some_and(|status| status.is_healthy));
+        assert_eq!(statuses.get(&DexId::Turbos).map(|status| status.consecutive_failures), Some(EMERGENCY_SYNC_FAILURES));
+        assert!(manager.needs_emergency_sync());
+        Ok(())
+    }
+
+    #[test]
+    fn test_healthy_dexes_grouped_by_network() -> Result<()> {
+        let mut manager = DexManager::default();
+        manager.register_dex(Box::new(MockDexAdapter::new(DexId::Cetus)))?;
+        manager.register_dex(Box::new(MockDexAdapter::new(DexId::Turbos).with_network(Network::SuiTestnet)))?;
+        manager.register_dex(Box::new(MockDexAdapter::new(DexId::Kriya).unhealthy()))?;
+
+        let healthy = manager.healthy_dexes();
+
+        assert_eq!(healthy.get(&Network::SuiMainnet), Some(&vec![DexId::Cetus]));
+        assert_eq!(healthy.get(&Network::SuiTestnet), Some(&vec![DexId::Turbos]));
+        assert_eq!(healthy.values().flatten().count(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_best_and_worst_price_across_dexes() -> Result<()> {
+        let pair = TokenPair::new(test_token("SUI"), test_token("USDC"));
+        let price = |value: i64, scale: u32| Price::new(Decimal::new(value, scale), PriceSource::Calculated);
+        let mut stale = price(30, 1);
+        stale.timestamp = now() - 600_000;
+
+        let mut manager = DexManager::default();
+        manager.register_dex(Box::new(MockDexAdapter::new(DexId::Cetus).with_price(price(200, 2))))?;
+        manager.register_dex(Box::new(MockDexAdapter::new(DexId::Turbos).with_price(price(210, 2))))?;
+        manager.register_dex(Box::new(MockDexAdapter::new(DexId::Kriya).with_price(price(175, 2))))?;
+        manager.register_dex(Box::new(MockDexAdapter::new(DexId::Aftermath).with_price(stale)))?;
+
+        assert_eq!(manager.get_all_prices(&pair).len(), 4);
+        let (best_dex, best) = manager.get_best_price(&pair).ok_or_else(|| BotError::NotFound("best price".into()))?;
+        let (worst_dex, worst) = manager.get_worst_price(&pair).ok_or_else(|| BotError::NotFound("worst price".into()))?;
+        assert_eq!((best_dex, best.value), (DexId::Turbos, Decimal::new(210, 2)));
+        assert_eq!((worst_dex, worst.value), (DexId::Kriya, Decimal::new(175, 2)));
+        assert_eq!(manager.get_price_spread(&pair), Some(Decimal::from(20)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_pool_states_resolves_by_version() {
+        let mut manager = DexManager::default();
+        manager.dexes.insert(DexId::Cetus, Box::new(MockDexAdapter::new(DexId::Cetus)));
+
+        let result = manager.merge_pool_states(vec![pool(2, 100), pool(1, 50), pool(3, 0)]);
+
+        assert_eq!(result, MergeResult { accepted: 1, rejected_stale: 1, rejected_invalid: 1 });
+        assert_eq!(
+            manager.get_pool(&DexId::Cetus, &"0xpool".to_string()).map(|pool| pool.version),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn test_remove_stale_pools() -> Result<()> {
+        let mut fresh = pool(1, 100);
+        fresh.pool_id = "0xfresh".into();
+        fresh.block_timestamp = now();
+        let mut manager = DexManager::default();
+        manager.register_dex(Box::new(MockDexAdapter::new(DexId::Cetus).with_pool(pool(1, 100)).with_pool(fresh)))?;
+
+        assert_eq!(manager.get_stale_pools(), vec!["0xpool".to_string()]);
+        assert_eq!(manager.remove_stale_pools(), vec!["0xpool".to_string()]);
+        assert!(manager.get_stale_pools().is_empty());
+        assert_eq!(manager.all_pools().count(), 1);
+        assert_eq!(manager.pool_count_by_dex().get(&DexId::Cetus), Some(&1));
+        Ok(())
+    }
+
+    #[test]
+    fn test_unchanged_refresh_keeps_pool_fresh() -> Result<()> {
+        let mut manager = DexManager::default();
+        manager.register_dex(Box::new(MockDexAdapter::new(DexId::Cetus).with_pool(pool(1, 100))))?;
+        let mut refreshed = pool(1, 100);
+        refreshed.block_timestamp = now();
+
+        assert!(manager.update_pool_state(refreshed)?.is_none());
+        assert!(manager.get_stale_pools().is_empty());
+        assert!(manager.remove_stale_pools().is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_merging_new_pool_registers_it() -> Result<()> {
+        let mut manager = DexManager::default();
+        manager.register_dex(Box::new(MockDexAdapter::new(DexId::Cetus).with_pool(pool(1, 100))))?;
+        let mut events = manager.subscribe_pool_events();
+        let mut new_pool = pool(1, 100);
+        new_pool.pool_id = "0xnew".into();
+
+        let merged = manager.merge_pool_states(vec![new_pool, pool(2, 200)]);
+
+        assert_eq!(merged.accepted, 2);
+        assert_eq!(manager.pool_count_by_dex().get(&DexId::Cetus), Some(&2));
+        assert_eq!(events.try_recv().ok(), Some(PoolEvent::Registered("0xnew".into(), DexId::Cetus)));
+        assert!(matches!(events.try_recv(), Ok(PoolEvent::StateUpdated(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_register_dex_rejects_duplicates() -> Result<()> {
+        let mut manager = DexManager::default();
+        manager.register_dex(Box::new(MockDexAdapter::new(DexId::Cetus).with_pool(pool(1, 100))))?;
+
+        assert!(manager.register_dex(Box::new(MockDexAdapter::new(DexId::Cetus))).is_err());
+        assert_eq!(manager.pool_count_by_dex().get(&DexId::Cetus), Some(&1));
+        Ok(())
+    }
+}
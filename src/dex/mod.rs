@@ -1,4 +1,6 @@
+pub mod aftermath;
 pub mod cetus;
+pub mod kriya;
 
 pub mod adapter;
 pub mod manager;
@@ -1,9 +1,8 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, sync::{Arc, RwLock}};
 
 use chrono::Duration;
-use tokio::sync::RwLock;
 
-use crate::types::{DexId, FeeStructure, PoolId, PoolState, Price, Timestamp, TokenPair};
+use crate::types::{BotError, DexId, FeeStructure, HealthDetails, HealthStatus, PoolId, PoolState, Price, PriceSource, Result, Timestamp, TokenInfo, TokenPair, now};
 
 /// State for a single DEX instance
 pub struct DexState {
@@ -16,6 +15,157 @@ pub struct DexState {
     pub stats: DexStatistics,
 }
 
+impl DexState {
+    /// Fresh state for a DEX with no pools loaded yet
+    pub fn new(config: DexConfig) -> Self {
+        let now = now();
+        let settings = &config.sync_settings;
+        let sync_state = SyncState {
+            last_full_sync: now,
+            next_sync_due: now,
+            sync_interval: Duration::seconds(settings.periodic_sync_interval_secs as i64),
+            heartbeat_interval: Duration::seconds(settings.heartbeat_interval_secs as i64),
+            heartbeat_timeout: Duration::seconds(settings.heartbeat_timeout_secs as i64),
+        };
+
+        Self {
+            dex_id: config.name,
+            config,
+            pools: HashMap::new(),
+            prices: Arc::new(RwLock::new(HashMap::new())),
+            health: DexHealthState {
+                last_event: None,
+                last_heartbeat: now,
+                last_sync: now,
+                consecutive_failures: 0,
+                is_healthy: true,
+            },
+            sync_state,
+            stats: DexStatistics::default(),
+        }
+    }
+}
+
+impl DexState {
+    /// Tokens of a pool, from its config entry or its cached state
+    pub fn pool_tokens(&self, pool_id: &PoolId) -> Result<(TokenInfo, TokenInfo)> {
+        self.config.pool_addresses
+            .iter()
+            .find(|(_, address)| *address == pool_id)
+            .map(|(pair, _)| (pair.base.clone(), pair.quote.clone()))
+            .or_else(|| {
+                let pool = self.pools.get(pool_id)?;
+                Some((pool.token_a.clone(), pool.token_b.clone()))
+            })
+            .ok_or_else(|| BotError::NotFound(format!("Pool {} is not monitored", pool_id)))
+    }
+
+    /// Every pool the DEX knows of, configured or cached
+    pub fn pool_ids(&self) -> Vec<PoolId> {
+        let mut pool_ids: Vec<PoolId> = self.config.pool_addresses.values().cloned().collect();
+        for pool_id in self.pools.keys() {
+            if !pool_ids.contains(pool_id) {
+                pool_ids.push(pool_id.clone());
+            }
+        }
+        pool_ids
+    }
+
+    /// Store a pool's price under the canonical pair
+    pub fn store_price(&self, pair: &TokenPair, price: Price) {
+        let canonical_price = if pair.is_canonical() { Some(price) } else { price.inverse() };
+        if let Some(price) = canonical_price {
+            self.prices
+                .write()
+                .unwrap_or_else(|e| e.into_inner())
+                .insert(pair.canonical(), price);
+        }
+    }
+
+    /// Price stored for `pair`, inverted when asked for the reverse of the
+    /// canonical orientation
+    pub fn price(&self, pair: &TokenPair) -> Option<Price> {
+        let price = self.prices
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&pair.canonical())
+            .cloned()?;
+        if pair.is_canonical() { Some(price) } else { price.inverse() }
+    }
+
+    /// Replace cached pools with newer fetched versions, returning the IDs
+    /// of the pools that changed
+    pub fn reconcile(&mut self, fetched: Vec<PoolState>) -> Vec<PoolId> {
+        let mut updated = Vec::new();
+        for pool in fetched {
+            if let Some(current) = self.pools.get(&pool.pool_id)
+                && pool.version <= current.version
+            {
+                continue;
+            }
+            if pool.is_initialized() {
+                let pair = TokenPair::new(pool.token_a.clone(), pool.token_b.clone());
+                self.store_price(&pair, Price::new(pool.spot_price_a_to_b(), PriceSource::RpcPoll { synced: true }));
+            }
+            updated.push(pool.pool_id.clone());
+            self.pools.insert(pool.pool_id.clone(), pool);
+        }
+        updated
+    }
+
+    /// Note a completed full sync and schedule the next one
+    pub fn record_full_sync(&mut self) {
+        let now = now();
+        self.health.last_sync = now;
+        self.sync_state.last_full_sync = now;
+        self.sync_state.next_sync_due = now + self.sync_state.sync_interval.num_milliseconds().max(0) as u64;
+        self.stats.syncs_completed += 1;
+    }
+
+    pub fn record_error(&mut self, error: &BotError) {
+        self.stats.errors_encountered += 1;
+        self.stats.last_error = Some((now(), error.to_string()));
+    }
+
+    /// Update health from a heartbeat probe that started at `started`.
+    /// Pools not updated within `stale_after_ms` count as stale.
+    pub fn record_heartbeat(&mut self, probe: Result<()>, started: Timestamp, stale_after_ms: u64) -> HealthStatus {
+        match probe {
+            Ok(()) => {
+                self.health.consecutive_failures = 0;
+                self.health.is_healthy = true;
+                self.health.last_heartbeat = now();
+                let mut status = HealthStatus::healthy("Connected");
+                status.last_event = self.health.last_event;
+                status.details = Some(self.health_details(stale_after_ms, None, Some(now().saturating_sub(started))));
+                status
+            }
+            Err(e) => {
+                self.health.consecutive_failures += 1;
+                self.health.is_healthy = false;
+                self.record_error(&e);
+                let mut status = HealthStatus::unhealthy(format!("Fetch failed: {}", e), self.health.consecutive_failures);
+                status.details = Some(self.health_details(stale_after_ms, Some(e.to_string()), None));
+                status
+            }
+        }
+    }
+
+    fn health_details(&self, stale_after_ms: u64, last_error: Option<String>, response_time_ms: Option<u64>) -> HealthDetails {
+        let now = now();
+        HealthDetails {
+            last_error,
+            time_since_last_success_ms: now.saturating_sub(self.health.last_heartbeat),
+            pools_monitored: self.pools.len(),
+            stale_pools: self.pools
+                .values()
+                .filter(|pool| now.saturating_sub(pool.block_timestamp) > stale_after_ms)
+                .count(),
+            avg_response_time_ms: response_time_ms,
+        }
+    }
+}
+
 /// Health monitoring for a DEX
 pub struct DexHealthState {
     pub last_event: Option<Timestamp>,
@@ -44,6 +194,19 @@ pub struct DexConfig {
     pub sync_settings: SyncSettings,
 }
 
+impl DexConfig {
+    pub fn new(name: DexId, package_id: impl Into<String>) -> Self {
+        Self {
+            name,
+            package_id: package_id.into(),
+            monitored_pairs: Vec::new(),
+            pool_addresses: HashMap::new(),
+            fee_structure: FeeStructure::default(),
+            sync_settings: SyncSettings::default(),
+        }
+    }
+}
+
 pub struct SyncSettings {
     pub heartbeat_interval_secs: u64,
     pub heartbeat_timeout_secs: u64,
@@ -51,7 +214,19 @@ pub struct SyncSettings {
     pub enable_fallback_polling: bool,
 }
 
+impl Default for SyncSettings {
+    fn default() -> Self {
+        Self {
+            heartbeat_interval_secs: 30,
+            heartbeat_timeout_secs: 10,
+            periodic_sync_interval_secs: 300,
+            enable_fallback_polling: false,
+        }
+    }
+}
+
 /// Statistics tracking
+#[derive(Default)]
 pub struct DexStatistics {
     pub events_received: u64,
     pub events_processed: u64,
@@ -1,3 +1,4 @@
 pub mod dispatcher;
+pub mod parsers;
 pub mod processor;
 pub  mod websocket;
\ No newline at end of file
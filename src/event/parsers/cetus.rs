@@ -0,0 +1,160 @@
+use serde_json::Value;
+
+use crate::types::{BotError, DexId, RawEvent, Result, SwapEvent};
+
+/// Type name suffix of swap events emitted by Cetus pools
+pub const SWAP_EVENT_IDENTIFIER: &str = "::pool::SwapEvent";
+
+/// Check if a Move event type is a Cetus swap
+pub fn is_swap_event(event_type: &str) -> bool {
+    event_type.ends_with(SWAP_EVENT_IDENTIFIER)
+}
+
+pub struct CetusEventParser;
+
+impl CetusEventParser {
+    /// Build a swap event from a Cetus `SwapEvent`. Accepts either the bare
+    /// `parsedJson` payload or the full subscription notification.
+    pub fn parse(raw: &RawEvent) -> Result<SwapEvent> {
+        let fields = raw.data.pointer("/params/result/parsedJson").unwrap_or(&raw.data);
+
+        let pool_id = fields
+            .get("pool")
+            .and_then(Value::as_str)
+            .ok_or_else(|| BotError::Parse("Cetus swap event is missing field pool".into()))?;
+        // Older packages name the direction flag `atob`
+        let a_to_b = fields
+            .get("a2b")
+            .or_else(|| fields.get("atob"))
+            .and_then(Value::as_bool)
+            .ok_or_else(|| BotError::Parse("Cetus swap event is missing field a2b".into()))?;
+
+        Ok(SwapEvent {
+            dex_id: DexId::Cetus,
+            pool_id: pool_id.to_string(),
+            amount_in: u64_field(fields, "amount_in")?,
+            amount_out: u64_field(fields, "amount_out")?,
+            base_to_quote: a_to_b,
+            timestamp: raw.timestamp,
+            transaction_digest: raw.transaction_digest.clone().unwrap_or_default(),
+            sender: raw.sender.clone(),
+            block_height: None,
+            sequence: None,
+            fee_amount: Some(u64_field(fields, "fee_amount")?),
+            sqrt_price_before: Some(u128_field(fields, "before_sqrt_price")?),
+            sqrt_price_after: Some(u128_field(fields, "after_sqrt_price")?),
+        })
+    }
+}
+
+/// Read an integer field, which Sui JSON encodes either as a number or a string
+fn u128_field(fields: &Value, name: &str) -> Result<u128> {
+    let value = fields
+        .get(name)
+        .ok_or_else(|| BotError::Parse(format!("Cetus swap event is missing field {}", name)))?;
+
+    value
+        .as_u64()
+        .map(u128::from)
+        .or_else(|| value.as_str().and_then(|s| s.parse().ok()))
+        .ok_or_else(|| BotError::Parse(format!("Cetus swap event field {} is not an integer: {}", name, value)))
+}
+
+fn u64_field(fields: &Value, name: &str) -> Result<u64> {
+    u128_field(fields, name)?
+        .try_into()
+        .map_err(|_| BotError::Parse(format!("Cetus swap event field {} exceeds u64", name)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dex::cetus::CETUS_PACKAGE_ID;
+
+    use super::*;
+
+    /// `suix_subscribeEvent` notification for a mainnet SUI/USDC swap
+    const SWAP_NOTIFICATION_FIXTURE: &str = r#"{
+        "jsonrpc": "2.0",
+        "method": "suix_subscribeEvent",
+        "params": {
+            "subscription": 4127,
+            "result": {
+                "id": { "txDigest": "8Fq3kQnUz7GZ5Lr2Yb1v9hNwXe4TjcM6sDpRaKoV3iBt", "eventSeq": "0" },
+                "packageId": "0x1eabed72c53feb3805120a081dc15963c204dc8d091542592abaf7a35689b2fb",
+                "transactionModule": "pool_script_v2",
+                "sender": "0x8d0e3a1c6f3b7b0f2a51d9c4e6a7f0b2c3d4e5f60718293a4b5c6d7e8f901a2b",
+                "type": "0x1eabed72c53feb3805120a081dc15963c204dc8d091542592abaf7a35689b2fb::pool::SwapEvent",
+                "parsedJson": {
+                    "after_sqrt_price": "1443425838891463458",
+                    "amount_in": "2500000000",
+                    "amount_out": "8732561",
+                    "atob": true,
+                    "before_sqrt_price": "1443437061237846402",
+                    "fee_amount": "6250000",
+                    "partner": "0x639b5e433da31739e800cd085f356e64cae222966d0f1b11bd9dc76b322ff58b",
+                    "pool": "0xcf994611fd4c48e277ce3ffd4d4364c914af2c3cbb05f7bf6facd371de688630",
+                    "ref_amount": "0",
+                    "steps": "1",
+                    "vault_a_amount": "1265398725663925",
+                    "vault_b_amount": "4419862339871"
+                },
+                "timestampMs": "1717171717171"
+            }
+        }
+    }"#;
+
+    fn notification() -> Result<RawEvent> {
+        let data: Value = serde_json::from_str(SWAP_NOTIFICATION_FIXTURE)?;
+        let event_type = format!("{}{}", CETUS_PACKAGE_ID, SWAP_EVENT_IDENTIFIER);
+        let mut raw = RawEvent::new(data, CETUS_PACKAGE_ID.into(), event_type);
+        raw.transaction_digest = Some("8Fq3kQnUz7GZ5Lr2Yb1v9hNwXe4TjcM6sDpRaKoV3iBt".into());
+        Ok(raw)
+    }
+
+    #[test]
+    fn test_parse_swap_fixture() -> Result<()> {
+        let raw = notification()?;
+        assert!(is_swap_event(&raw.event_type));
+
+        let event = CetusEventParser::parse(&raw)?;
+
+        assert_eq!(event.dex_id, DexId::Cetus);
+        assert_eq!(event.pool_id, "0xcf994611fd4c48e277ce3ffd4d4364c914af2c3cbb05f7bf6facd371de688630");
+        assert!(event.base_to_quote);
+        assert_eq!(event.amount_in, 2_500_000_000);
+        assert_eq!(event.amount_out, 8_732_561);
+        assert_eq!(event.fee_amount, Some(6_250_000));
+        assert_eq!(event.sqrt_price_after, Some(1_443_425_838_891_463_458));
+        assert_eq!(event.transaction_digest, "8Fq3kQnUz7GZ5Lr2Yb1v9hNwXe4TjcM6sDpRaKoV3iBt");
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_bare_payload() -> Result<()> {
+        let mut raw = notification()?;
+        raw.data = raw.data
+            .pointer("/params/result/parsedJson")
+            .cloned()
+            .ok_or_else(|| BotError::NotFound("parsedJson".into()))?;
+        if let Some(fields) = raw.data.as_object_mut() {
+            fields.remove("atob");
+            fields.insert("a2b".into(), Value::Bool(false));
+        }
+
+        let event = CetusEventParser::parse(&raw)?;
+
+        assert!(!event.base_to_quote);
+        assert_eq!(event.amount_in, 2_500_000_000);
+        Ok(())
+    }
+
+    #[test]
+    fn test_missing_amount_is_parse_error() -> Result<()> {
+        let mut raw = notification()?;
+        if let Some(fields) = raw.data.pointer_mut("/params/result/parsedJson").and_then(Value::as_object_mut) {
+            fields.remove("amount_out");
+        }
+        assert!(matches!(CetusEventParser::parse(&raw), Err(BotError::Parse(_))));
+        Ok(())
+    }
+}
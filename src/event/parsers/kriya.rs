@@ -0,0 +1,123 @@
+use serde_json::Value;
+
+use crate::types::{BotError, DexId, RawEvent, Result, SwapEvent, TokenInfo};
+
+/// Type name of swap events emitted by Kriya pools, generic over the input coin
+pub const SWAP_EVENT_IDENTIFIER: &str = "::spot_dex::SwapEvent<";
+
+/// Check if a Move event type is a Kriya swap
+pub fn is_swap_event(event_type: &str) -> bool {
+    event_type.contains(SWAP_EVENT_IDENTIFIER)
+}
+
+/// Pool a Kriya swap event traded through, from either the bare
+/// `parsedJson` payload or the full subscription notification
+pub fn pool_id(raw: &RawEvent) -> Result<String> {
+    fields(raw)
+        .get("pool_id")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .ok_or_else(|| BotError::Parse("Kriya swap event is missing field pool_id".into()))
+}
+
+pub struct KriyaEventParser;
+
+impl KriyaEventParser {
+    /// Build a swap event from a Kriya `SwapEvent<X>`. The event only names
+    /// the input coin `X`, so the direction is resolved against `token_a`,
+    /// the pool's first coin.
+    pub fn parse(raw: &RawEvent, token_a: &TokenInfo) -> Result<SwapEvent> {
+        let fields = fields(raw);
+        let coin_in = raw.event_type
+            .split_once(SWAP_EVENT_IDENTIFIER)
+            .and_then(|(_, coin)| coin.strip_suffix('>'))
+            .ok_or_else(|| BotError::Parse(format!("Kriya swap event type has no input coin: {}", raw.event_type)))?;
+        let symbol = coin_in.rsplit("::").next().unwrap_or(coin_in);
+
+        Ok(SwapEvent {
+            dex_id: DexId::Kriya,
+            pool_id: pool_id(raw)?,
+            amount_in: u64_field(fields, "amount_in")?,
+            amount_out: u64_field(fields, "amount_out")?,
+            base_to_quote: TokenInfo::new(symbol, coin_in, token_a.decimals).matches(token_a),
+            timestamp: raw.timestamp,
+            transaction_digest: raw.transaction_digest.clone().unwrap_or_default(),
+            sender: raw.sender.clone(),
+            block_height: None,
+            sequence: None,
+            fee_amount: None,
+            sqrt_price_before: None,
+            sqrt_price_after: None,
+        })
+    }
+}
+
+fn fields(raw: &RawEvent) -> &Value {
+    raw.data.pointer("/params/result/parsedJson").unwrap_or(&raw.data)
+}
+
+/// Read an integer field, which Sui JSON encodes either as a number or a string
+fn u64_field(fields: &Value, name: &str) -> Result<u64> {
+    let value = fields
+        .get(name)
+        .ok_or_else(|| BotError::Parse(format!("Kriya swap event is missing field {}", name)))?;
+
+    value
+        .as_u64()
+        .or_else(|| value.as_str().and_then(|s| s.parse().ok()))
+        .ok_or_else(|| BotError::Parse(format!("Kriya swap event field {} is not an integer: {}", name, value)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dex::kriya::KRIYA_PACKAGE_ID;
+
+    use super::*;
+
+    const SWAP_FIXTURE: &str = r#"{
+        "pool_id": "0x5af4976b871fa1813362f352fa4cada3883a96191bb7212db1bd5d13685ae305",
+        "user": "0x8d0e3a1c6f3b7b0f2a51d9c4e6a7f0b2c3d4e5f60718293a4b5c6d7e8f901a2b",
+        "reserve_x": "4211873962145",
+        "reserve_y": "8502367113",
+        "amount_in": "1000000000",
+        "amount_out": "2013442"
+    }"#;
+
+    fn swap(coin_in: &str) -> Result<RawEvent> {
+        let event_type = format!("{}{}{}>", KRIYA_PACKAGE_ID, SWAP_EVENT_IDENTIFIER, coin_in);
+        Ok(RawEvent::new(serde_json::from_str(SWAP_FIXTURE)?, KRIYA_PACKAGE_ID.into(), event_type))
+    }
+
+    #[test]
+    fn test_parse_swap_fixture() -> Result<()> {
+        let raw = swap("0x2::sui::SUI")?;
+        assert!(is_swap_event(&raw.event_type));
+
+        let event = KriyaEventParser::parse(&raw, &TokenInfo::sui())?;
+
+        assert_eq!(event.dex_id, DexId::Kriya);
+        assert_eq!(event.pool_id, "0x5af4976b871fa1813362f352fa4cada3883a96191bb7212db1bd5d13685ae305");
+        assert!(event.base_to_quote);
+        assert_eq!(event.amount_in, 1_000_000_000);
+        assert_eq!(event.amount_out, 2_013_442);
+        Ok(())
+    }
+
+    #[test]
+    fn test_quote_coin_in_swaps_b_to_a() -> Result<()> {
+        let event = KriyaEventParser::parse(&swap("0x5d4b::coin::COIN")?, &TokenInfo::sui())?;
+
+        assert!(!event.base_to_quote);
+        Ok(())
+    }
+
+    #[test]
+    fn test_missing_amount_is_parse_error() -> Result<()> {
+        let mut raw = swap("0x2::sui::SUI")?;
+        if let Some(fields) = raw.data.as_object_mut() {
+            fields.remove("amount_out");
+        }
+        assert!(matches!(KriyaEventParser::parse(&raw, &TokenInfo::sui()), Err(BotError::Parse(_))));
+        Ok(())
+    }
+}
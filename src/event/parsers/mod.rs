@@ -0,0 +1,2 @@
+pub mod cetus;
+pub mod kriya;
@@ -1,13 +1,16 @@
 use async_trait::async_trait;
-use tokio::sync::{RwLock, mpsc};
-use tracing::info;
-use std::{collections::HashMap, sync::Arc};
+use serde::Serialize;
+use tokio::{sync::{Mutex as AsyncMutex, RwLock, mpsc}, task::JoinHandle};
+use tracing::{debug, info, warn};
+use std::{collections::HashMap, sync::{Arc, Mutex, atomic::{AtomicU64, Ordering}}, time::Duration};
 
 use crate::{
+    arbitrage::{calculator::ArbitrageCalculator, detector::ArbitrageDetectorHandle},
     dex::manager::DexManager, 
-    event::websocket::{ DefaultWebSocketManager, WebSocketManager }, 
-    types::{ DexId, RawEvent, Result, SwapEvent }, 
-    utils::config::NetworkConfig
+    event::{parsers::{cetus::{self, CetusEventParser}, kriya}, websocket::{ DefaultWebSocketManager, WebSocketManager }}, 
+    sync::periodic::{SyncOrchestrator, SyncScope},
+    types::{ BotError, DexId, RawEvent, Result, SwapEvent, Timestamp, TokenPair, now }, 
+    utils::{config::{NetworkConfig, SyncConfig}, digest_cache::DigestCache, event_latency::EventLatencyTracker, event_rate::EventRateCounter, metrics::MetricsRegistry}
 };
 
 #[async_trait]
@@ -23,73 +26,384 @@ pub trait EventProcessor: Send + Sync {
     
     /// Get processing status for each DEX
     async fn get_status(&self) -> HashMap<DexId, ProcessorStatus>;
+    
+    /// Parse a single raw event and apply it to the pool states.
+    /// Used by the live pipeline and for replaying recorded events.
+    async fn process_event(&mut self, raw_event: RawEvent) -> Result<SwapEvent>;
+    
+    /// Collect events that were received but not yet processed.
+    /// Call after `stop()` so no new events arrive while draining.
+    fn drain_pending_events(&mut self) -> Vec<(DexId, RawEvent)>;
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ProcessorStatus {
     pub is_running: bool,
     pub events_processed: u64,
     pub last_event_time: Option<u64>,
     pub error_count: u64,
+    pub latency: EventLatencyTracker,
+    /// Events dropped because their transaction digest was already processed
+    pub duplicate_events_dropped: u64,
 }
 
+/// Wait before the full sync that follows a WebSocket reconnect, so the
+/// new connection settles first
+const RECONNECT_SYNC_DELAY: Duration = Duration::from_secs(5);
+
+/// How often a processor task checks for reconnects while no events arrive
+const RECONNECT_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A DEX's WebSocket manager, locked by its processor task while it waits for events
+type SharedWebSocketManager = Arc<AsyncMutex<Box<dyn WebSocketManager>>>;
+
 // Default implementation
 pub struct DefaultEventProcessor {
-    dex_manager: Arc<RwLock<DexManager>>,
-    websocket_managers: HashMap<DexId, Box<dyn WebSocketManager>>,
-    swap_sender: mpsc::Sender<SwapEvent>,
+    websocket_managers: HashMap<DexId, SharedWebSocketManager>,
     swap_receiver: mpsc::Receiver<SwapEvent>,
-    processor_tasks: HashMap<DexId, tokio::task::JoinHandle<()>>,
+    processor_tasks: HashMap<DexId, JoinHandle<()>>,
     is_running: bool,
+    pipeline: EventPipeline,
+}
+
+/// Everything needed to apply an event, shared between the processor and
+/// its per-DEX tasks
+#[derive(Clone)]
+struct EventPipeline {
+    dex_manager: Arc<RwLock<DexManager>>,
+    swap_sender: mpsc::Sender<SwapEvent>,
     network_config: NetworkConfig,
+    sync_config: SyncConfig,
+    latency_trackers: Arc<Mutex<HashMap<DexId, EventLatencyTracker>>>,
+    seen_digests: Arc<Mutex<DigestCache>>,
+    duplicate_events_dropped: Arc<HashMap<DexId, AtomicU64>>,
+    events_processed: Arc<HashMap<DexId, AtomicU64>>,
+    last_event_time: Arc<RwLock<HashMap<DexId, Timestamp>>>,
+    error_count: Arc<HashMap<DexId, AtomicU64>>,
+    event_rate: Arc<EventRateCounter>,
+    opportunity_detection: Option<Arc<(ArbitrageDetectorHandle, Box<dyn ArbitrageCalculator>)>>,
+    sync_orchestrator: Option<Arc<SyncOrchestrator>>,
+    metrics: Option<Arc<MetricsRegistry>>,
 }
 
 impl DefaultEventProcessor {
     pub fn new(
         dex_manager: Arc<RwLock<DexManager>>,
         network_config: NetworkConfig,
+        sync_config: SyncConfig,
     ) -> Self {
         let (swap_sender, swap_receiver) = mpsc::channel(1000);
         
         Self {
-            dex_manager,
             websocket_managers: HashMap::new(),
-            swap_sender,
             swap_receiver,
             processor_tasks: HashMap::new(),
             is_running: false,
-            network_config
+            pipeline: EventPipeline {
+                dex_manager,
+                swap_sender,
+                network_config,
+                sync_config,
+                latency_trackers: Arc::new(Mutex::new(HashMap::new())),
+                seen_digests: Arc::new(Mutex::new(DigestCache::default())),
+                duplicate_events_dropped: Arc::new(EventPipeline::counters()),
+                events_processed: Arc::new(EventPipeline::counters()),
+                last_event_time: Arc::new(RwLock::new(HashMap::new())),
+                error_count: Arc::new(EventPipeline::counters()),
+                event_rate: Arc::new(EventRateCounter::new()),
+                opportunity_detection: None,
+                sync_orchestrator: None,
+                metrics: None,
+            },
         }
     }
     
+    /// Scan for opportunities after every processed swap and queue them
+    /// on the detector
+    pub fn with_opportunity_detection(
+        mut self,
+        handle: ArbitrageDetectorHandle,
+        calculator: Box<dyn ArbitrageCalculator>,
+    ) -> Self {
+        self.pipeline.opportunity_detection = Some(Arc::new((handle, calculator)));
+        self
+    }
+    
+    /// Refetch each swapped pool from chain instead of waiting for the next
+    /// periodic sync
+    pub fn with_sync_orchestrator(mut self, sync_orchestrator: Arc<SyncOrchestrator>) -> Self {
+        self.pipeline.sync_orchestrator = Some(sync_orchestrator);
+        self
+    }
+    
+    /// Record event latency here and reconnections in the WebSocket managers
+    pub fn with_metrics(mut self, metrics: Arc<MetricsRegistry>) -> Self {
+        self.pipeline.metrics = Some(metrics);
+        self
+    }
+    
+    /// Count processed events in `event_rate`, typically shared with the
+    /// sync orchestrator for adaptive sync
+    pub fn with_event_rate(mut self, event_rate: Arc<EventRateCounter>) -> Self {
+        self.pipeline.event_rate = event_rate;
+        self
+    }
+    
+    /// Processed events per second across all DEXs over the last minute
+    pub fn get_events_per_second(&self) -> f64 {
+        self.pipeline.event_rate.events_per_second(now())
+    }
+    
     /// Initialize WebSocket managers for all enabled DEXs
     pub async fn initialize_websockets(&mut self, dex_ids: Vec<DexId>) -> Result<()> {
         info!("Initializing WebSocket managers for DEXs: {:?}", dex_ids);
+        let network_config = &self.pipeline.network_config;
         for dex_id in dex_ids {
-            let ws_manager = Box::new(DefaultWebSocketManager::new(
+            let mut ws_manager = DefaultWebSocketManager::new(
                 dex_id,
-                self.network_config.ws_url.to_string(),
-            ));
-            self.websocket_managers.insert(dex_id, ws_manager);
+                network_config.ws_url.to_string(),
+                network_config.event_buffer_size(dex_id),
+                network_config.backpressure_policy,
+            )
+            .with_ws_config(&network_config.ws);
+            let package_id = network_config
+                .dex_config(dex_id)
+                .map(|dex| dex.package_id.clone())
+                .or_else(|| dex_id.package_id(network_config.network).map(str::to_string));
+            if let Some(package_id) = package_id {
+                ws_manager = ws_manager.with_package_id(package_id);
+            }
+            if let Some(metrics) = &self.pipeline.metrics {
+                ws_manager = ws_manager.with_metrics(metrics.clone());
+            }
+            let ws_manager: Box<dyn WebSocketManager> = Box::new(ws_manager);
+            self.websocket_managers.insert(dex_id, Arc::new(AsyncMutex::new(ws_manager)));
             info!("WebSocket manager initialized for DEX {}", dex_id);
         }
         Ok(())
     }
     
+    /// Connect the DEX's WebSocket and apply each event it delivers in a
+    /// background task
     async fn start_dex_processor(&mut self, dex_id: DexId) -> Result<()> {
-        info!("Starting event processor for DEX {}", dex_id);
+        let ws_manager = self.websocket_managers
+            .get(&dex_id)
+            .cloned()
+            .ok_or_else(|| BotError::NotFound(format!("WebSocket manager for DEX {}", dex_id)))?;
+        let pipeline = self.pipeline.clone();
+        
+        let mut reconnects = ws_manager.lock().await.reconnect_count();
+        
+        let task = tokio::spawn(async move {
+            if let Err(e) = ws_manager.lock().await.connect().await {
+                warn!("Failed to connect WebSocket for DEX {}: {}", dex_id, e);
+                return;
+            }
+            loop {
+                let next_event = {
+                    let mut manager = ws_manager.lock().await;
+                    let reconnect_count = manager.reconnect_count();
+                    if reconnect_count > reconnects {
+                        reconnects = reconnect_count;
+                        pipeline.schedule_reconnect_sync(dex_id);
+                    }
+                    tokio::time::timeout(RECONNECT_CHECK_INTERVAL, manager.next_event()).await
+                };
+                match next_event {
+                    Ok(Some(raw_event)) => {
+                        if let Err(e) = pipeline.process_event(raw_event).await {
+                            debug!("Skipping event for DEX {}: {}", dex_id, e);
+                        }
+                    }
+                    Ok(None) => break,
+                    Err(_) => continue,
+                }
+            }
+            warn!("Event stream for DEX {} closed", dex_id);
+        });
+        self.processor_tasks.insert(dex_id, task);
+        info!("Started event processor for DEX {}", dex_id);
         Ok(())
     }
+
+    /// Healthy DEXs on the network this processor's WebSocket URL serves
+    async fn get_enabled_dex_ids(&self) -> Result<Vec<DexId>> {
+        let manager = self.pipeline.dex_manager.read().await;
+        Ok(manager.healthy_dexes().remove(&self.pipeline.network_config.network).unwrap_or_default())
+    }
+}
+
+impl EventPipeline {
+    /// One zeroed counter per known DEX, so counters never need a write lock
+    fn counters() -> HashMap<DexId, AtomicU64> {
+        DexId::all().into_iter().map(|dex_id| (dex_id, AtomicU64::new(0))).collect()
+    }
+    
+    fn increment(counters: &HashMap<DexId, AtomicU64>, dex_id: DexId) {
+        if let Some(counter) = counters.get(&dex_id) {
+            counter.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+    
+    /// Sync every pool shortly after a reconnect, since events sent while
+    /// the WebSocket was down are lost
+    fn schedule_reconnect_sync(&self, dex_id: DexId) {
+        if let Some(sync_orchestrator) = &self.sync_orchestrator {
+            info!("WebSocket for DEX {} reconnected, full sync in {:?}", dex_id, RECONNECT_SYNC_DELAY);
+            sync_orchestrator.schedule_sync_after(RECONNECT_SYNC_DELAY, SyncScope::All);
+        }
+    }
+    
+    /// Count a processed event or a processing failure for `dex_id`
+    async fn record_outcome(&self, dex_id: DexId, succeeded: bool) {
+        Self::increment(if succeeded { &self.events_processed } else { &self.error_count }, dex_id);
+        if succeeded {
+            let at = now();
+            self.event_rate.record(at);
+            self.last_event_time.write().await.insert(dex_id, at);
+        }
+    }
+    
+    /// Resolve the DEX that emitted an event from its package ID,
+    /// checking known packages first and then the configured DEXs
+    fn dex_id_for_event(&self, raw_event: &RawEvent) -> Result<DexId> {
+        DexId::from_package_id(&raw_event.package_id, self.network_config.network)
+            .or_else(|| {
+                self.network_config.dexes
+                    .iter()
+                    .find(|dex| dex.package_id.eq_ignore_ascii_case(&raw_event.package_id))
+                    .map(|dex| dex.id)
+            })
+            .ok_or_else(|| BotError::Event(format!(
+                "No DEX configured for package {}", raw_event.package_id
+            )))
+    }
+    
+    /// Record processing latency and warn when p95 exceeds the configured threshold
+    fn record_latency(&self, dex_id: DexId, received_at: u64) {
+        let latency_ms = now().saturating_sub(received_at);
+        if let Some(metrics) = &self.metrics {
+            metrics.observe_pool_sync_latency(dex_id, latency_ms);
+        }
+        let mut trackers = self.latency_trackers.lock().unwrap_or_else(|e| e.into_inner());
+        let tracker = trackers.entry(dex_id).or_default();
+        tracker.record(latency_ms);
+        
+        let p95 = tracker.p95_ms();
+        if p95 > self.sync_config.event_lag_alert_ms {
+            warn!("Event processing lag p95={}ms exceeds threshold", p95);
+        }
+    }
+    
+    /// Transaction digest plus event sequence, since one transaction (a
+    /// multi-hop swap) can emit several events. Events recorded without a
+    /// sequence fall back to their type and payload. `None` when the digest
+    /// is unknown.
+    fn dedup_key(raw_event: &RawEvent) -> Option<String> {
+        let digest = raw_event.transaction_digest.as_ref()?;
+        Some(match &raw_event.event_seq {
+            Some(event_seq) => format!("{}:{}", digest, event_seq),
+            None => format!("{}:{}:{}", digest, raw_event.event_type, raw_event.data),
+        })
+    }
     
+    /// Convert a raw event into a normalized swap using the parser for its
+    /// DEX and event type
     fn parse_raw_event(dex_id: DexId, raw_event: RawEvent) -> Result<SwapEvent> {
-        // TODO: Implement DEX-specific event parsing
-        // This would convert raw WebSocket data to normalized SwapEvent
-        todo!("Parse raw event for {}", dex_id)
+        match dex_id {
+            DexId::Cetus if cetus::is_swap_event(&raw_event.event_type) => CetusEventParser::parse(&raw_event),
+            _ => Err(BotError::Event(format!(
+                "Unsupported event type {} for DEX {}", raw_event.event_type, dex_id
+            ))),
+        }
     }
 
-    async fn get_enabled_dex_ids(&self) -> Result<Vec<DexId>> {
-        let manager = self.dex_manager.read().await;
-        Ok(manager.healthy_dexes())
+    /// Rescan the updated state and queue opportunities trading the pair
+    /// of the swapped pool
+    async fn detect_opportunities(&self, swap_event: &SwapEvent) {
+        let Some(detection) = &self.opportunity_detection else {
+            return;
+        };
+        let (handle, calculator) = detection.as_ref();
+        let snapshot = match self.dex_manager.read().await.get_state_snapshot() {
+            Ok(snapshot) => snapshot,
+            Err(e) => {
+                warn!("Cannot snapshot state after swap in {}: {}", swap_event.pool_id, e);
+                return;
+            }
+        };
+        let Some(pool) = snapshot.pools.get(&swap_event.pool_id) else {
+            return;
+        };
+        let pair = TokenPair::new(pool.token_a.clone(), pool.token_b.clone());
+        
+        for opportunity in calculator.find_opportunities(&snapshot).await {
+            if !opportunity.path.hops.iter().any(|hop| hop.pair.matches(&pair)) {
+                continue;
+            }
+            if let Err(e) = handle.try_send(opportunity) {
+                warn!("Dropping opportunities after swap in {}: {}", swap_event.pool_id, e);
+                break;
+            }
+        }
+    }
+    
+    /// Refresh the swapped pool in the background, correcting any drift
+    /// between the locally applied swap and the on-chain state
+    fn resync_pool(&self, swap_event: &SwapEvent) {
+        let Some(sync_orchestrator) = self.sync_orchestrator.clone() else {
+            return;
+        };
+        let pool_id = swap_event.pool_id.clone();
+        tokio::spawn(async move {
+            if let Err(e) = sync_orchestrator.sync_specific_pools(vec![pool_id.clone()]).await {
+                warn!("Re-sync of pool {} failed: {}", pool_id, e);
+            }
+        });
+    }
+    
+    /// Parse an event and apply it to its DEX's pool states. Kriya swaps
+    /// are parsed by the adapter, which knows the tokens of each pool.
+    async fn apply_event(&self, dex_id: DexId, raw_event: RawEvent) -> Result<SwapEvent> {
+        let mut manager = self.dex_manager.write().await;
+        let swap_event = match manager.get_dex(&dex_id) {
+            Some(dex) if dex_id == DexId::Kriya && kriya::is_swap_event(&raw_event.event_type) => dex.parse_event(raw_event)?,
+            _ => Self::parse_raw_event(dex_id, raw_event)?,
+        };
+        if let Some(dex) = manager.get_dex_mut(&dex_id) {
+            dex.process_swap_event(swap_event.clone())?;
+        }
+        Ok(swap_event)
+    }
+    
+    /// Drop duplicates, apply the event and update the counters, then look
+    /// for opportunities in the updated state
+    async fn process_event(&self, raw_event: RawEvent) -> Result<SwapEvent> {
+        let dex_id = self.dex_id_for_event(&raw_event)?;
+        let digest = Self::dedup_key(&raw_event);
+        if let Some(digest) = &digest
+            && self.seen_digests.lock().unwrap_or_else(|e| e.into_inner()).contains(digest, now())
+        {
+            Self::increment(&self.duplicate_events_dropped, dex_id);
+            return Err(BotError::Event(format!("Duplicate event from transaction {}", digest)));
+        }
+        
+        let received_at = raw_event.timestamp;
+        let result = self.apply_event(dex_id, raw_event).await;
+        self.record_outcome(dex_id, result.is_ok()).await;
+        let swap_event = result?;
+        
+        if self.swap_sender.try_send(swap_event.clone()).is_err() {
+            warn!("Swap event channel full, dropping event for DEX {}", dex_id);
+        }
+        
+        if let Some(digest) = digest {
+            self.seen_digests.lock().unwrap_or_else(|e| e.into_inner()).insert(digest, now());
+        }
+        self.detect_opportunities(&swap_event).await;
+        self.resync_pool(&swap_event);
+        self.record_latency(dex_id, received_at);
+        Ok(swap_event)
     }
 }
 
@@ -123,15 +437,18 @@ impl EventProcessor for DefaultEventProcessor {
         info!("Stopping Event Processor...");
         
         self.is_running = false;
+        // Events drained during shutdown are applied without re-syncing from chain
+        self.pipeline.sync_orchestrator = None;
         
-        // Stop all WebSocket connections
-        for ws_manager in self.websocket_managers.values_mut() {
-            ws_manager.disconnect().await?;
-        }
-        
-        // Cancel all processor tasks
+        // Cancel all processor tasks, waiting for each to release its manager
         for (_, task) in self.processor_tasks.drain() {
             task.abort();
+            let _ = task.await;
+        }
+        
+        // Stop all WebSocket connections
+        for ws_manager in self.websocket_managers.values() {
+            ws_manager.lock().await.disconnect().await?;
         }
         
         info!("Event processor stopped");
@@ -143,6 +460,369 @@ impl EventProcessor for DefaultEventProcessor {
     }
     
     async fn get_status(&self) -> HashMap<DexId, ProcessorStatus> {
-        todo!("Return status for each DEX processor")
+        let count = |counters: &HashMap<DexId, AtomicU64>, dex_id: &DexId| {
+            counters.get(dex_id).map_or(0, |counter| counter.load(Ordering::Relaxed))
+        };
+        let pipeline = &self.pipeline;
+        let last_event_time = pipeline.last_event_time.read().await;
+        let latency_trackers = pipeline.latency_trackers.lock().unwrap_or_else(|e| e.into_inner()).clone();
+        
+        self.websocket_managers
+            .keys()
+            .map(|dex_id| {
+                let status = ProcessorStatus {
+                    is_running: self.processor_tasks
+                        .get(dex_id)
+                        .is_some_and(|task| !task.is_finished()),
+                    events_processed: count(&pipeline.events_processed, dex_id),
+                    last_event_time: last_event_time.get(dex_id).copied(),
+                    error_count: count(&pipeline.error_count, dex_id),
+                    latency: latency_trackers.get(dex_id).cloned().unwrap_or_default(),
+                    duplicate_events_dropped: count(&pipeline.duplicate_events_dropped, dex_id),
+                };
+                (*dex_id, status)
+            })
+            .collect()
+    }
+    
+    async fn process_event(&mut self, raw_event: RawEvent) -> Result<SwapEvent> {
+        self.pipeline.process_event(raw_event).await
+    }
+    
+    fn drain_pending_events(&mut self) -> Vec<(DexId, RawEvent)> {
+        let mut pending = Vec::new();
+        for (dex_id, ws_manager) in &self.websocket_managers {
+            // Only a running processor task holds the lock, and `stop()` ends those
+            if let Ok(mut ws_manager) = ws_manager.try_lock() {
+                pending.extend(ws_manager.drain_events().into_iter().map(|event| (*dex_id, event)));
+            }
+        }
+        pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal::Decimal;
+
+    use crate::{
+        arbitrage::{
+            calculator::DefaultArbitrageCalculator,
+            detector::{ArbitrageDetector, DefaultArbitrageDetector},
+        },
+        client::sui_rpc::SuiRpcClient,
+        dex::{cetus::{CETUS_TESTNET_PACKAGE_ID, adapter::CetusAdapter}, kriya::{KRIYA_PACKAGE_ID, adapter::KriyaAdapter}, state::DexConfig},
+        event::parsers::{cetus::SWAP_EVENT_IDENTIFIER, kriya},
+        sync::fetcher::PoolStateFetcher,
+        testing::{MockDexAdapter, test_pool, test_token, triangle_snapshot},
+        types::Network,
+        utils::config::ArbitrageConfig,
+    };
+
+    use super::*;
+
+    /// Delivers no events; the test bumps its reconnect count
+    struct ReconnectingManager(Arc<std::sync::atomic::AtomicU32>);
+
+    #[async_trait]
+    impl WebSocketManager for ReconnectingManager {
+        async fn connect(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn disconnect(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn is_connected(&self) -> bool {
+            true
+        }
+
+        async fn next_event(&mut self) -> Option<RawEvent> {
+            std::future::pending().await
+        }
+
+        fn drain_events(&mut self) -> Vec<RawEvent> {
+            Vec::new()
+        }
+
+        fn reconnect_count(&self) -> u32 {
+            self.0.load(Ordering::SeqCst)
+        }
+    }
+
+    fn processor() -> DefaultEventProcessor {
+        DefaultEventProcessor::new(
+            Arc::new(RwLock::new(DexManager::default())),
+            NetworkConfig::default(),
+            SyncConfig::default(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_drops_already_processed_digest() {
+        let mut processor = processor();
+        let mut event = RawEvent::new(serde_json::Value::Null, CETUS_TESTNET_PACKAGE_ID.into(), "SwapEvent".into());
+        event.transaction_digest = Some("0xd1".into());
+        if let Some(key) = EventPipeline::dedup_key(&event) {
+            processor.pipeline.seen_digests.lock().unwrap_or_else(|e| e.into_inner()).insert(key, now());
+        }
+
+        let result = processor.process_event(event).await;
+
+        assert!(matches!(result, Err(BotError::Event(message)) if message.contains("Duplicate")));
+        assert_eq!(processor.pipeline.duplicate_events_dropped.get(&DexId::Cetus).map(|count| count.load(Ordering::Relaxed)), Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_status_reports_counters_per_dex() -> Result<()> {
+        let mut processor = processor();
+        processor.initialize_websockets(vec![DexId::Cetus]).await?;
+        processor.pipeline.record_outcome(DexId::Cetus, true).await;
+        processor.pipeline.record_outcome(DexId::Cetus, true).await;
+        processor.pipeline.record_outcome(DexId::Cetus, false).await;
+        processor.pipeline.record_outcome(DexId::Turbos, false).await;
+
+        let status = processor.get_status().await;
+
+        // Only DEXs with a WebSocket manager are reported
+        assert_eq!(status.len(), 1);
+        let cetus = status.get(&DexId::Cetus).ok_or_else(|| BotError::NotFound("Cetus status".into()))?;
+        assert_eq!(cetus.events_processed, 2);
+        assert_eq!(cetus.error_count, 1);
+        assert!(cetus.last_event_time.is_some());
+        assert!(!cetus.is_running);
+        assert_eq!(processor.get_events_per_second(), 2.0 / 60.0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_each_dex_gets_a_processor_task() -> Result<()> {
+        let network_config = NetworkConfig { ws_url: "ws://127.0.0.1:9".into(), ..NetworkConfig::default() };
+        let dex_manager = Arc::new(RwLock::new(DexManager::default()));
+        let sync_orchestrator = Arc::new(SyncOrchestrator::new(dex_manager.clone(), PoolStateFetcher::default(), &SyncConfig::default()));
+        let mut processor = DefaultEventProcessor::new(dex_manager, network_config, SyncConfig::default())
+            .with_sync_orchestrator(sync_orchestrator);
+        processor.initialize_websockets(vec![DexId::Cetus]).await?;
+        processor.is_running = true;
+        processor.start_dex_processor(DexId::Cetus).await?;
+
+        let status = processor.get_status().await;
+        assert_eq!(status.get(&DexId::Cetus).map(|status| status.is_running), Some(true));
+
+        processor.stop().await?;
+        assert!(processor.processor_tasks.is_empty());
+        assert!(processor.pipeline.sync_orchestrator.is_none());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_websocket_events_reach_pool_state() -> Result<()> {
+        use futures_util::{SinkExt, StreamExt};
+        use tokio::net::TcpListener;
+        use tokio_tungstenite::{accept_async, tungstenite::Message};
+
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let ws_url = format!("ws://{}", listener.local_addr()?);
+        let notification = serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "suix_subscribeEvent",
+            "params": { "subscription": 1, "result": {
+                "id": { "txDigest": "0xd1", "eventSeq": "0" },
+                "packageId": CETUS_TESTNET_PACKAGE_ID,
+                "type": format!("{}{}", CETUS_TESTNET_PACKAGE_ID, SWAP_EVENT_IDENTIFIER),
+                "parsedJson": {
+                    "pool": "p1", "amount_in": "1000", "amount_out": "1990", "atob": true,
+                    "before_sqrt_price": "0", "after_sqrt_price": "0", "fee_amount": "3",
+                },
+            }},
+        });
+        // Send one swap after the subscription, then hold the socket open
+        let server = tokio::spawn(async move {
+            let Ok((stream, _)) = listener.accept().await else { return };
+            let Ok(mut ws) = accept_async(stream).await else { return };
+            let _ = ws.next().await;
+            let _ = ws.send(Message::text(notification.to_string())).await;
+            while let Some(Ok(_)) = ws.next().await {}
+        });
+
+        let rpc = Arc::new(SuiRpcClient::new("http://127.0.0.1:9"));
+        let pool = test_pool("p1", DexId::Cetus, &test_token("SUI"), &test_token("USDC"), 1_000, 2_000);
+        let reserve_before = pool.reserve_a;
+        let mut manager = DexManager::default();
+        manager.register_dex(Box::new(CetusAdapter::new(DexConfig::new(DexId::Cetus, CETUS_TESTNET_PACKAGE_ID), rpc).with_network(Network::SuiTestnet).with_pool(pool)))?;
+        let dex_manager = Arc::new(RwLock::new(manager));
+        let network_config = NetworkConfig { ws_url, ..NetworkConfig::default() };
+        let mut processor = DefaultEventProcessor::new(dex_manager.clone(), network_config, SyncConfig::default());
+        processor.start().await?;
+
+        let processed = async {
+            loop {
+                let status = processor.get_status().await;
+                if status.get(&DexId::Cetus).is_some_and(|status| status.events_processed == 1) {
+                    break;
+                }
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            }
+        };
+        tokio::time::timeout(std::time::Duration::from_secs(5), processed)
+            .await
+            .map_err(|_| BotError::Event("swap was never processed".into()))?;
+
+        let reserve_after = dex_manager.read().await
+            .get_pool(&DexId::Cetus, &"p1".to_string())
+            .map(|pool| pool.reserve_a)
+            .ok_or_else(|| BotError::NotFound("p1".into()))?;
+        assert_eq!(reserve_after, reserve_before + Decimal::from(1000));
+
+        processor.stop().await?;
+        server.abort();
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_reconnect_schedules_full_sync() -> Result<()> {
+        let dex_manager = Arc::new(RwLock::new(DexManager::default()));
+        let sync_orchestrator = Arc::new(SyncOrchestrator::new(dex_manager.clone(), PoolStateFetcher::default(), &SyncConfig::default()));
+        let mut processor = DefaultEventProcessor::new(dex_manager, NetworkConfig::default(), SyncConfig::default())
+            .with_sync_orchestrator(sync_orchestrator.clone());
+        let reconnects = Arc::new(std::sync::atomic::AtomicU32::new(0));
+        let ws_manager: Box<dyn WebSocketManager> = Box::new(ReconnectingManager(reconnects.clone()));
+        processor.websocket_managers.insert(DexId::Cetus, Arc::new(AsyncMutex::new(ws_manager)));
+        processor.start_dex_processor(DexId::Cetus).await?;
+
+        reconnects.store(1, Ordering::SeqCst);
+        let scheduled = tokio::time::timeout(Duration::from_secs(3), async {
+            while sync_orchestrator.pending_scheduled_syncs() == 0 {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+            }
+        })
+        .await;
+        for task in processor.processor_tasks.values() {
+            task.abort();
+        }
+
+        assert!(scheduled.is_ok());
+        assert!(sync_orchestrator.get_sync_history().is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_event_type_counts_as_error() -> Result<()> {
+        let mut processor = processor();
+        processor.initialize_websockets(vec![DexId::Cetus]).await?;
+        let event = RawEvent::new(serde_json::Value::Null, CETUS_TESTNET_PACKAGE_ID.into(), "0x2::pool::AddLiquidityEvent".into());
+
+        assert!(matches!(processor.process_event(event).await, Err(BotError::Event(_))));
+        let status = processor.get_status().await;
+        assert_eq!(status.get(&DexId::Cetus).map(|status| status.error_count), Some(1));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_swap_queues_opportunities_on_detector() -> Result<()> {
+        let snapshot = triangle_snapshot();
+        let rpc = Arc::new(SuiRpcClient::new("http://127.0.0.1:9"));
+        let mut cetus = CetusAdapter::new(DexConfig::new(DexId::Cetus, CETUS_TESTNET_PACKAGE_ID), rpc);
+        let mut turbos = MockDexAdapter::new(DexId::Turbos);
+        for pool in snapshot.pools.values().cloned() {
+            match pool.dex_id {
+                DexId::Cetus => cetus = cetus.with_pool(pool),
+                _ => turbos = turbos.with_pool(pool),
+            }
+        }
+        let mut manager = DexManager::default();
+        manager.register_dex(Box::new(cetus))?;
+        manager.register_dex(Box::new(turbos))?;
+        let dex_manager = Arc::new(RwLock::new(manager));
+
+        let config = ArbitrageConfig::default();
+        let pool_events = dex_manager.read().await.subscribe_pool_events();
+        let calculator = || Box::new(DefaultArbitrageCalculator::new(config.clone()));
+        let (mut detector, handle) = DefaultArbitrageDetector::new(dex_manager.clone(), calculator(), pool_events, &config);
+        let mut processor = DefaultEventProcessor::new(dex_manager, NetworkConfig::default(), SyncConfig::default())
+            .with_opportunity_detection(handle, calculator());
+
+        let swap = serde_json::json!({
+            "pool": "p1", "amount_in": "1000", "amount_out": "1990", "atob": true,
+            "before_sqrt_price": "0", "after_sqrt_price": "0", "fee_amount": "3",
+        });
+        let event_type = format!("{}{}", CETUS_TESTNET_PACKAGE_ID, SWAP_EVENT_IDENTIFIER);
+        processor.process_event(RawEvent::new(swap, CETUS_TESTNET_PACKAGE_ID.into(), event_type)).await?;
+
+        let opportunity = detector
+            .next_opportunity()
+            .await
+            .ok_or_else(|| BotError::NotFound("queued opportunity".into()))?;
+        assert!(opportunity.path.hops.iter().any(|hop| hop.pool_id == "p1"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_kriya_swap_is_applied_through_adapter() -> Result<()> {
+        let rpc = Arc::new(SuiRpcClient::new("http://127.0.0.1:9"));
+        let pool = test_pool("p1", DexId::Kriya, &test_token("SUI"), &test_token("USDC"), 1_000, 2_000);
+        let mut manager = DexManager::default();
+        manager.register_dex(Box::new(KriyaAdapter::new(DexConfig::new(DexId::Kriya, KRIYA_PACKAGE_ID), rpc).with_pool(pool)))?;
+        let dex_manager = Arc::new(RwLock::new(manager));
+        let network_config = NetworkConfig { network: Network::SuiMainnet, ..NetworkConfig::default() };
+        let mut processor = DefaultEventProcessor::new(dex_manager.clone(), network_config, SyncConfig::default());
+
+        // USDC in, so the swap runs from token B to token A
+        let swap = serde_json::json!({ "pool_id": "p1", "amount_in": "2000", "amount_out": "990" });
+        let event_type = format!("{}{}{}>", KRIYA_PACKAGE_ID, kriya::SWAP_EVENT_IDENTIFIER, "0xusdc::coin::USDC");
+        let event = processor.process_event(RawEvent::new(swap, KRIYA_PACKAGE_ID.into(), event_type)).await?;
+
+        assert!(!event.base_to_quote);
+        let manager = dex_manager.read().await;
+        let pool = manager.get_pool(&DexId::Kriya, &"p1".to_string()).ok_or_else(|| BotError::NotFound("p1".into()))?;
+        assert_eq!(pool.reserve_a, Decimal::from(999_999_999_010u64));
+        Ok(())
+    }
+
+    #[test]
+    fn test_events_of_one_transaction_are_distinct() {
+        let swap = |amount: u64, event_seq: Option<&str>| {
+            let mut event = RawEvent::new(serde_json::json!({ "amount_in": amount }), "0xcetus".into(), "SwapEvent".into());
+            event.transaction_digest = Some("0xd1".into());
+            event.event_seq = event_seq.map(str::to_string);
+            event
+        };
+
+        // Two identical swaps in one transaction differ only by sequence
+        assert_ne!(EventPipeline::dedup_key(&swap(1, Some("0"))), EventPipeline::dedup_key(&swap(1, Some("1"))));
+        assert_eq!(EventPipeline::dedup_key(&swap(1, Some("0"))), EventPipeline::dedup_key(&swap(2, Some("0"))));
+        assert_ne!(EventPipeline::dedup_key(&swap(1, None)), EventPipeline::dedup_key(&swap(2, None)));
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_event_updates_pool_once() -> Result<()> {
+        let rpc = Arc::new(SuiRpcClient::new("http://127.0.0.1:9"));
+        let pool = test_pool("p1", DexId::Cetus, &test_token("SUI"), &test_token("USDC"), 1_000, 2_000);
+        let reserve_before = pool.reserve_a;
+        let mut manager = DexManager::default();
+        manager.register_dex(Box::new(CetusAdapter::new(DexConfig::new(DexId::Cetus, CETUS_TESTNET_PACKAGE_ID), rpc).with_pool(pool)))?;
+        let dex_manager = Arc::new(RwLock::new(manager));
+        let mut processor = DefaultEventProcessor::new(dex_manager.clone(), NetworkConfig::default(), SyncConfig::default());
+
+        let swap = serde_json::json!({
+            "pool": "p1", "amount_in": "1000", "amount_out": "1990", "atob": true,
+            "before_sqrt_price": "0", "after_sqrt_price": "0", "fee_amount": "3",
+        });
+        let event_type = format!("{}{}", CETUS_TESTNET_PACKAGE_ID, SWAP_EVENT_IDENTIFIER);
+        let mut event = RawEvent::new(swap, CETUS_TESTNET_PACKAGE_ID.into(), event_type);
+        event.transaction_digest = Some("0xd1".into());
+        event.event_seq = Some("0".into());
+
+        processor.process_event(event.clone()).await?;
+        assert!(processor.process_event(event).await.is_err());
+
+        let reserve_after = dex_manager.read().await
+            .get_pool(&DexId::Cetus, &"p1".to_string())
+            .map(|pool| pool.reserve_a)
+            .ok_or_else(|| BotError::NotFound("p1".into()))?;
+        assert_eq!(reserve_after, reserve_before + Decimal::from(1000));
+        assert_eq!(processor.pipeline.duplicate_events_dropped.get(&DexId::Cetus).map(|count| count.load(Ordering::Relaxed)), Some(1));
+        Ok(())
     }
 }
@@ -1,54 +1,449 @@
+use std::{sync::{Arc, Mutex, atomic::{AtomicBool, AtomicU32, Ordering}}, time::{Duration, Instant}};
+
 use async_trait::async_trait;
-use tokio::sync::mpsc;
-use tracing::info;
-use crate::types::{DexId, RawEvent, Result};
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{Value, json};
+use tokio::{sync::mpsc, task::JoinHandle};
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tracing::{debug, info, warn};
+use crate::{types::{BotError, DexId, RawEvent, Result, now}, utils::{config::{BackpressurePolicy, WsConfig}, metrics::MetricsRegistry}};
+
+/// First reconnect delay, doubled on every consecutive failure
+const BASE_RECONNECT_DELAY_MS: u64 = 500;
+const MAX_RECONNECT_DELAY_MS: u64 = 30_000;
+/// Jitter applied to each reconnect delay, in percent
+const RECONNECT_JITTER_PERCENT: u64 = 10;
+/// Consecutive failures after which we stop backing off and warn
+const MAX_CONSECUTIVE_FAILURES: u32 = 10;
 
 #[async_trait]
 pub trait WebSocketManager: Send + Sync {
     async fn connect(&mut self) -> Result<()>;
     async fn disconnect(&mut self) -> Result<()>;
     async fn is_connected(&self) -> bool;
+
+    /// Wait for the next received event; `None` once the channel is closed
+    async fn next_event(&mut self) -> Option<RawEvent>;
+
+    /// Take all events still buffered in the channel without waiting
+    fn drain_events(&mut self) -> Vec<RawEvent>;
+
+    /// Number of times the connection has been re-established
+    fn reconnect_count(&self) -> u32;
 }
 
 /// Simple WebSocket manager for Sui/Aptos DEXs
 pub struct DefaultWebSocketManager {
+    connection: Connection,
+    event_receiver: mpsc::Receiver<RawEvent>,
+    task: Option<JoinHandle<()>>,
+}
+
+/// Everything the background connection task needs
+#[derive(Clone)]
+struct Connection {
     dex_id: DexId,
     ws_url: String,
+    /// Only events from this package are subscribed to when set
+    package_id: Option<String>,
     event_sender: mpsc::Sender<RawEvent>,
-    event_receiver: mpsc::Receiver<RawEvent>,
-    is_connected: bool,
+    backpressure_policy: BackpressurePolicy,
+    is_connected: Arc<AtomicBool>,
+    reconnect_count: Arc<AtomicU32>,
+    ping_interval: Duration,
+    /// Silence longer than this counts as a dropped connection
+    read_timeout: Duration,
+    last_pong_received: Arc<Mutex<Instant>>,
+    metrics: Option<Arc<MetricsRegistry>>,
 }
 
 impl DefaultWebSocketManager {
-    pub fn new(dex_id: DexId, ws_url: String) -> Self {
-        let (event_sender, event_receiver) = mpsc::channel(1000);
-        
+    pub fn new(
+        dex_id: DexId,
+        ws_url: String,
+        buffer_size: usize,
+        backpressure_policy: BackpressurePolicy,
+    ) -> Self {
+        let (event_sender, event_receiver) = mpsc::channel(buffer_size);
+
         Self {
-            dex_id,
-            ws_url,
-            event_sender,
+            connection: Connection {
+                dex_id,
+                ws_url,
+                package_id: None,
+                event_sender,
+                backpressure_policy,
+                is_connected: Arc::new(AtomicBool::new(false)),
+                reconnect_count: Arc::new(AtomicU32::new(0)),
+                ping_interval: Duration::from_secs(WsConfig::default().ping_interval_secs),
+                read_timeout: Duration::from_secs(WsConfig::default().read_timeout_secs),
+                last_pong_received: Arc::new(Mutex::new(Instant::now())),
+                metrics: None,
+            },
             event_receiver,
-            is_connected: false,
+            task: None,
+        }
+    }
+
+    /// Subscribe only to events emitted by `package_id`
+    pub fn with_package_id(mut self, package_id: impl Into<String>) -> Self {
+        self.connection.package_id = Some(package_id.into());
+        self
+    }
+    
+    pub fn with_ws_config(mut self, config: &WsConfig) -> Self {
+        self.connection.ping_interval = Duration::from_secs(config.ping_interval_secs);
+        self.connection.read_timeout = Duration::from_secs(config.read_timeout_secs);
+        self
+    }
+    
+    pub fn with_metrics(mut self, metrics: Arc<MetricsRegistry>) -> Self {
+        self.connection.metrics = Some(metrics);
+        self
+    }
+    
+    /// When the server last answered one of our pings
+    pub fn last_pong_received(&self) -> Instant {
+        self.connection.last_pong_received.lock().map(|instant| *instant).unwrap_or_else(|e| *e.into_inner())
+    }
+}
+
+impl Connection {
+    /// Keep a session open, reconnecting with exponential backoff whenever it drops
+    async fn run(self) {
+        let mut consecutive_failures = 0u32;
+        loop {
+            match self.session(&mut consecutive_failures).await {
+                Ok(()) => info!("WebSocket closed by server for DEX {}", self.dex_id),
+                Err(e) => warn!("WebSocket error for DEX {}: {}", self.dex_id, e),
+            }
+            self.is_connected.store(false, Ordering::SeqCst);
+
+            let delay = if consecutive_failures >= MAX_CONSECUTIVE_FAILURES {
+                warn!(
+                    "WebSocket for DEX {} failed {} times in a row, retrying every {}ms",
+                    self.dex_id, consecutive_failures, MAX_RECONNECT_DELAY_MS
+                );
+                Duration::from_millis(MAX_RECONNECT_DELAY_MS)
+            } else {
+                reconnect_delay(consecutive_failures)
+            };
+            consecutive_failures += 1;
+
+            debug!("Reconnecting DEX {} WebSocket in {:?}", self.dex_id, delay);
+            tokio::time::sleep(delay).await;
+            self.reconnect_count.fetch_add(1, Ordering::SeqCst);
+            if let Some(metrics) = &self.metrics {
+                metrics.record_websocket_reconnection(self.dex_id);
+            }
+        }
+    }
+
+    /// One connection: subscribe, then forward events until the socket closes
+    async fn session(&self, consecutive_failures: &mut u32) -> Result<()> {
+        let (stream, _) = connect_async(self.ws_url.as_str())
+            .await
+            .map_err(|e| BotError::WebSocket(format!("Failed to connect to {}: {}", self.ws_url, e)))?;
+        let (mut write, mut read) = stream.split();
+
+        write
+            .send(Message::text(self.subscribe_message().to_string()))
+            .await
+            .map_err(|e| BotError::WebSocket(format!("Failed to subscribe: {}", e)))?;
+        self.is_connected.store(true, Ordering::SeqCst);
+        *consecutive_failures = 0;
+        info!("WebSocket connected for DEX {}", self.dex_id);
+
+        // Keepalive pings go out between reads; a socket quiet past the
+        // deadline is treated as dropped
+        let mut ping = tokio::time::interval(self.ping_interval);
+        ping.reset();
+        let mut deadline = tokio::time::Instant::now() + self.read_timeout;
+        loop {
+            let next = tokio::select! {
+                _ = ping.tick() => {
+                    write.send(Message::Ping(Vec::new().into())).await.map_err(|e| BotError::WebSocket(e.to_string()))?;
+                    continue;
+                }
+                _ = tokio::time::sleep_until(deadline) => {
+                    return Err(BotError::WebSocket(format!("No message for {:?}", self.read_timeout)));
+                }
+                next = read.next() => next,
+            };
+            let Some(message) = next else {
+                return Ok(());
+            };
+            deadline = tokio::time::Instant::now() + self.read_timeout;
+            let message = message.map_err(|e| BotError::WebSocket(e.to_string()))?;
+            match message {
+                Message::Text(text) => {
+                    if let Some(event) = parse_notification(&text) {
+                        self.dispatch_event(event).await?;
+                    }
+                }
+                Message::Ping(payload) => {
+                    write.send(Message::Pong(payload)).await.map_err(|e| BotError::WebSocket(e.to_string()))?;
+                }
+                Message::Pong(_) => {
+                    if let Ok(mut last_pong) = self.last_pong_received.lock() {
+                        *last_pong = Instant::now();
+                    }
+                }
+                Message::Close(_) => return Ok(()),
+                _ => {}
+            }
+        }
+    }
+
+    /// `suix_subscribeEvent` request for this DEX's package, or for all events
+    fn subscribe_message(&self) -> Value {
+        let filter = match &self.package_id {
+            Some(package_id) => json!({ "Package": package_id }),
+            None => json!({ "All": [] }),
+        };
+        json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "suix_subscribeEvent",
+            "params": [filter],
+        })
+    }
+
+    /// Push a received event into the DEX channel, honouring the backpressure policy
+    async fn dispatch_event(&self, event: RawEvent) -> Result<()> {
+        match self.backpressure_policy {
+            BackpressurePolicy::Drop => {
+                if let Err(mpsc::error::TrySendError::Full(_)) = self.event_sender.try_send(event) {
+                    warn!("Event buffer full for DEX {}, dropping event", self.dex_id);
+                }
+                Ok(())
+            }
+            BackpressurePolicy::Block => self.event_sender
+                .send(event)
+                .await
+                .map_err(|e| BotError::WebSocket(format!("Event channel closed: {}", e))),
         }
     }
 }
 
+/// `base * 2^attempt` capped at the maximum, with ±10% jitter
+fn reconnect_delay(attempt: u32) -> Duration {
+    let delay = BASE_RECONNECT_DELAY_MS
+        .saturating_mul(1u64 << attempt.min(16))
+        .min(MAX_RECONNECT_DELAY_MS);
+
+    // Sub-millisecond clock noise is random enough to spread reconnects
+    let noise = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.subsec_nanos() as u64)
+        .unwrap_or(0);
+    let spread = delay * RECONNECT_JITTER_PERCENT / 100;
+    Duration::from_millis(delay - spread + noise % (2 * spread + 1))
+}
+
+/// Event carried by a subscription notification; other messages yield `None`
+fn parse_notification(text: &str) -> Option<RawEvent> {
+    let message: Value = serde_json::from_str(text).ok()?;
+    let event = message.get("params")?.get("result")?;
+
+    Some(RawEvent {
+        data: event.get("parsedJson").cloned().unwrap_or(Value::Null),
+        timestamp: now(),
+        package_id: event.get("packageId")?.as_str()?.to_string(),
+        event_type: event.get("type")?.as_str()?.to_string(),
+        transaction_digest: event.pointer("/id/txDigest").and_then(Value::as_str).map(str::to_string),
+        event_seq: event.pointer("/id/eventSeq").and_then(Value::as_str).map(str::to_string),
+        sender: event.get("sender").and_then(Value::as_str).map(str::to_string),
+    })
+}
+
 #[async_trait]
 impl WebSocketManager for DefaultWebSocketManager {
     async fn connect(&mut self) -> Result<()> {
-        // TODO: Implement actual WebSocket connection
-        self.is_connected = true;
-        info!("WebSocket connected for DEX {}", self.dex_id);
+        if self.task.is_some() {
+            return Ok(());
+        }
+        self.task = Some(tokio::spawn(self.connection.clone().run()));
+        info!("WebSocket connecting for DEX {}", self.connection.dex_id);
         Ok(())
     }
-    
+
     async fn disconnect(&mut self) -> Result<()> {
-        self.is_connected = false;
-        info!("WebSocket disconnected for DEX {}", self.dex_id);
+        if let Some(task) = self.task.take() {
+            task.abort();
+        }
+        self.connection.is_connected.store(false, Ordering::SeqCst);
+        info!("WebSocket disconnected for DEX {}", self.connection.dex_id);
         Ok(())
     }
-    
+
     async fn is_connected(&self) -> bool {
-        self.is_connected
+        self.connection.is_connected.load(Ordering::SeqCst)
+    }
+
+    async fn next_event(&mut self) -> Option<RawEvent> {
+        self.event_receiver.recv().await
+    }
+
+    fn drain_events(&mut self) -> Vec<RawEvent> {
+        let mut events = Vec::new();
+        while let Ok(event) = self.event_receiver.try_recv() {
+            events.push(event);
+        }
+        events
+    }
+
+    fn reconnect_count(&self) -> u32 {
+        self.connection.reconnect_count.load(Ordering::SeqCst)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::accept_async;
+
+    use super::*;
+
+    fn notification(digest: &str) -> String {
+        json!({
+            "jsonrpc": "2.0",
+            "method": "suix_subscribeEvent",
+            "params": {
+                "subscription": 7,
+                "result": {
+                    "id": { "txDigest": digest, "eventSeq": "0" },
+                    "packageId": "0xcetus",
+                    "type": "0xcetus::pool::SwapEvent",
+                    "sender": "0xa11ce",
+                    "parsedJson": { "amount_in": "100" },
+                },
+            },
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn test_reconnect_delay_backs_off() {
+        let within = |delay: Duration, expected: u64| {
+            let millis = delay.as_millis() as u64;
+            millis >= expected * 9 / 10 && millis <= expected * 11 / 10
+        };
+        assert!(within(reconnect_delay(0), 500));
+        assert!(within(reconnect_delay(3), 4_000));
+        assert!(within(reconnect_delay(20), MAX_RECONNECT_DELAY_MS));
+    }
+
+    #[test]
+    fn test_parse_notification() -> Result<()> {
+        let event = parse_notification(&notification("0xd1")).ok_or_else(|| BotError::Parse("no event".into()))?;
+        assert_eq!(event.package_id, "0xcetus");
+        assert_eq!(event.transaction_digest.as_deref(), Some("0xd1"));
+        assert_eq!(event.event_seq.as_deref(), Some("0"));
+        assert_eq!(event.get_string("amount_in").as_deref(), Some("100"));
+
+        // The subscription acknowledgement carries no event
+        assert!(parse_notification(r#"{"jsonrpc":"2.0","id":1,"result":7}"#).is_none());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_reconnects_after_server_close() -> Result<()> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let url = format!("ws://{}", listener.local_addr()?);
+
+        // Each session reads the subscription, sends one event and closes
+        let server = tokio::spawn(async move {
+            let mut subscriptions = Vec::new();
+            for digest in ["0xd1", "0xd2"] {
+                let Ok((stream, _)) = listener.accept().await else { break };
+                let Ok(mut ws) = accept_async(stream).await else { break };
+                if let Some(Ok(Message::Text(text))) = ws.next().await {
+                    subscriptions.push(text.to_string());
+                }
+                let _ = ws.send(Message::text(notification(digest))).await;
+                let _ = ws.close(None).await;
+            }
+            subscriptions
+        });
+
+        let mut manager = DefaultWebSocketManager::new(DexId::Cetus, url, 16, BackpressurePolicy::Block)
+            .with_package_id("0xcetus");
+        manager.connect().await?;
+
+        let subscriptions = tokio::time::timeout(Duration::from_secs(5), server)
+            .await
+            .map_err(|_| BotError::WebSocket("server timed out".into()))?
+            .map_err(|e| BotError::WebSocket(e.to_string()))?;
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let digests: Vec<Option<String>> = manager.drain_events().into_iter().map(|event| event.transaction_digest).collect();
+        assert_eq!(digests, vec![Some("0xd1".to_string()), Some("0xd2".to_string())]);
+        assert_eq!(subscriptions.len(), 2);
+        assert!(subscriptions.iter().all(|message| message.contains("0xcetus")));
+        assert!(manager.reconnect_count() >= 1);
+
+        manager.disconnect().await?;
+        Ok(())
+    }
+
+    /// Manager for `url` with keepalive timings in milliseconds
+    fn fast_manager(url: String, ping_ms: u64, timeout_ms: u64) -> DefaultWebSocketManager {
+        let mut manager = DefaultWebSocketManager::new(DexId::Cetus, url, 16, BackpressurePolicy::Drop);
+        manager.connection.ping_interval = Duration::from_millis(ping_ms);
+        manager.connection.read_timeout = Duration::from_millis(timeout_ms);
+        manager
+    }
+
+    #[tokio::test]
+    async fn test_pings_keep_connection_alive() -> Result<()> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let url = format!("ws://{}", listener.local_addr()?);
+
+        // Reading is enough for the server to answer pings
+        let server = tokio::spawn(async move {
+            let Ok((stream, _)) = listener.accept().await else { return };
+            let Ok(mut ws) = accept_async(stream).await else { return };
+            while let Some(Ok(_)) = ws.next().await {}
+        });
+
+        let mut manager = fast_manager(url, 50, 150);
+        let started = Instant::now();
+        manager.connect().await?;
+        tokio::time::sleep(Duration::from_millis(400)).await;
+
+        assert!(manager.last_pong_received() > started);
+        assert!(manager.is_connected().await);
+        assert_eq!(manager.reconnect_count(), 0);
+
+        manager.disconnect().await?;
+        server.abort();
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_silent_connection_times_out_and_reconnects() -> Result<()> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let url = format!("ws://{}", listener.local_addr()?);
+
+        // Accept connections but never read or answer pings
+        let server = tokio::spawn(async move {
+            let mut sockets = Vec::new();
+            while let Ok((stream, _)) = listener.accept().await {
+                if let Ok(ws) = accept_async(stream).await {
+                    sockets.push(ws);
+                }
+            }
+        });
+
+        let mut manager = fast_manager(url, 1_000, 100);
+        manager.connect().await?;
+        tokio::time::sleep(Duration::from_millis(900)).await;
+
+        assert!(manager.reconnect_count() >= 1);
+
+        manager.disconnect().await?;
+        server.abort();
+        Ok(())
+    }
+}
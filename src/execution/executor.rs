@@ -1,40 +1,530 @@
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
 use async_trait::async_trait;
 use rust_decimal::Decimal;
+use tokio::sync::RwLock;
+use tracing::{info, warn};
 
-use crate::{types::{ArbitrageOpportunity, ExecutionResult, Result}, utils::config::ExecutionConfig};
+use crate::{dex::manager::DexManager, execution::wallet::{WalletPool, WalletState}, types::{ArbitrageOpportunity, BotError, ExecutionResult, ExecutionStats, ExecutionStatus, Result, now}, utils::{config::ExecutionConfig, math::calculate_amm_output, metrics::MetricsRegistry, pnl::PnlTracker}};
 
 #[async_trait]
 pub trait TradeExecutor: Send + Sync {
     async fn execute(&mut self, opportunity: ArbitrageOpportunity) -> ExecutionResult;
+    
+    /// Dry-run the trade on chain without submitting it.
+    /// Returns (gas_used, simulated_profit).
+    async fn simulate(&self, opportunity: &ArbitrageOpportunity) -> Result<(u64, Decimal)>;
+}
+
+/// Signs and sends one transaction for an opportunity
+#[async_trait]
+pub trait TransactionSubmitter: Send + Sync {
+    /// Sign with `wallet`, or the configured `private_key` when `None`.
+    /// Returns (transaction_digest, gas_used, actual_profit)
+    async fn submit(
+        &self,
+        wallet: Option<&WalletState>,
+        opportunity: &ArbitrageOpportunity,
+        gas_budget: u64,
+    ) -> Result<(String, u64, Decimal)>;
 }
 
 pub struct DefaultTradeExecutor {
-    _config: ExecutionConfig,
+    config: ExecutionConfig,
+    stats: ExecutionStats,
+    wallets: WalletPool,
+    /// Source of current pool reserves for re-quoting hops before a trade
+    dex_manager: Option<Arc<RwLock<DexManager>>>,
+    submitter: Option<Box<dyn TransactionSubmitter>>,
+    /// Stop retrying once the path data is older than this
+    max_opportunity_age_ms: Option<u64>,
+    metrics: Option<Arc<MetricsRegistry>>,
+    pnl_tracker: Option<Arc<RwLock<PnlTracker>>>,
 }
 
 impl DefaultTradeExecutor {
     pub fn new(config: ExecutionConfig) -> Self {
+        let wallets = WalletPool::new(&config.wallets);
         Self {
-            _config : config,
+            config,
+            stats: ExecutionStats::new(),
+            wallets,
+            dex_manager: None,
+            submitter: None,
+            max_opportunity_age_ms: None,
+            metrics: None,
+            pnl_tracker: None,
+        }
+    }
+    
+    /// Re-quote hops against the manager's pools instead of the reserves
+    /// seen at discovery time
+    pub fn with_dex_manager(mut self, dex_manager: Arc<RwLock<DexManager>>) -> Self {
+        self.dex_manager = Some(dex_manager);
+        self
+    }
+    
+    pub fn with_metrics(mut self, metrics: Arc<MetricsRegistry>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+    
+    /// Add the profit of every successful trade to `pnl_tracker`
+    pub fn with_pnl_tracker(mut self, pnl_tracker: Arc<RwLock<PnlTracker>>) -> Self {
+        self.pnl_tracker = Some(pnl_tracker);
+        self
+    }
+    
+    pub fn with_submitter(mut self, submitter: Box<dyn TransactionSubmitter>) -> Self {
+        self.submitter = Some(submitter);
+        self
+    }
+    
+    /// Give up on an opportunity between attempts once its path is stale
+    pub fn with_max_opportunity_age_ms(mut self, max_age_ms: u64) -> Self {
+        self.max_opportunity_age_ms = Some(max_age_ms);
+        self
+    }
+    
+    pub fn stats(&self) -> &ExecutionStats {
+        &self.stats
+    }
+    
+    pub fn wallets(&self) -> &WalletPool {
+        &self.wallets
+    }
+    
+    /// Submit with the next available wallet, or the single configured key
+    async fn submit(&mut self, opportunity: &ArbitrageOpportunity) -> ExecutionResult {
+        let result = ExecutionResult::new(opportunity.clone());
+        let actual_amounts = match self.quote_hops(opportunity).await {
+            Ok(amounts) => amounts,
+            Err(e) => return result.failure(e),
+        };
+        
+        let wallet_index = if self.wallets.is_empty() {
+            None
+        } else {
+            let trade_usd = match self.trade_input_usd(opportunity).await {
+                Ok(trade_usd) => trade_usd,
+                Err(e) => return result.failure(e),
+            };
+            self.wallets.roll_over_day(now());
+            let Some(index) = self.wallets.select(trade_usd) else {
+                return result.failure(BotError::Execution(format!("No wallet available for a {} USD trade", trade_usd)));
+            };
+            if let Some(wallet) = self.wallets.get_mut(index) {
+                wallet.current_position = Some(opportunity.path.path_id.clone());
+            }
+            Some(index)
+        };
+        
+        let wallet = wallet_index.and_then(|index| self.wallets.get(index));
+        let result = match self.execute_transaction(opportunity, wallet).await {
+            Ok((digest, gas_used, actual_profit)) => {
+                result.success(digest, actual_amounts, gas_used, actual_profit)
+            }
+            Err(e) => result.failure(e),
+        };
+        
+        if let Some(wallet) = wallet_index.and_then(|index| self.wallets.get_mut(index)) {
+            wallet.finish(&result);
+        }
+        result
+    }
+    
+    /// USD value of the path's input at current pool prices
+    async fn trade_input_usd(&self, opportunity: &ArbitrageOpportunity) -> Result<Decimal> {
+        let path = &opportunity.path;
+        let price = match &self.dex_manager {
+            Some(manager) => manager.read().await.get_state_snapshot()?.usd_price(&path.start_token),
+            None => None,
+        };
+        price
+            .map(|price| path.start_token.to_decimal(path.initial_amount) * price)
+            .ok_or_else(|| BotError::Execution(format!("Cannot value {} in USD", path.start_token.symbol)))
+    }
+    
+    /// Output of every hop, chained from `initial_amount` through current
+    /// pool reserves. Without a DEX manager the discovery-time quotes are
+    /// reused. Fails as soon as a hop would return less than its `min_amount_out`.
+    async fn quote_hops(&self, opportunity: &ArbitrageOpportunity) -> Result<HashMap<usize, u64>> {
+        let manager = match &self.dex_manager {
+            Some(manager) => Some(manager.read().await),
+            None => None,
+        };
+        
+        let mut amounts = HashMap::new();
+        let mut amount_in = opportunity.path.initial_amount;
+        for (index, hop) in opportunity.path.hops.iter().enumerate() {
+            let amount_out = match &manager {
+                Some(manager) => {
+                    let pool = manager.get_pool(&hop.dex_id, &hop.pool_id).ok_or_else(|| BotError::InsufficientLiquidity {
+                        pool_id: hop.pool_id.clone(),
+                    })?;
+                    let (reserve_in, reserve_out, fee_bps) = pool.swap_params(hop.token_in.matches(&pool.token_a));
+                    calculate_amm_output(amount_in, reserve_in, reserve_out, fee_bps)
+                }
+                None => hop.expected_amount_out,
+            };
+            
+            if amount_out < hop.min_amount_out {
+                return Err(BotError::SlippageTooHigh {
+                    expected: Decimal::from(hop.min_amount_out),
+                    actual: Decimal::from(amount_out),
+                });
+            }
+            amounts.insert(index, amount_out);
+            amount_in = amount_out;
         }
+        Ok(amounts)
+    }
+    
+    /// Simulate locally: re-quote every hop, charge `base_gas_per_swap` per
+    /// hop and report profit in the start token net of the estimated gas.
+    /// Returns (hop amounts, gas_used, simulated_profit).
+    async fn simulate_hops(&self, opportunity: &ArbitrageOpportunity) -> Result<(HashMap<usize, u64>, u64, Decimal)> {
+        let amounts = self.quote_hops(opportunity).await?;
+        let path = &opportunity.path;
+        let final_amount = path.hops.len().checked_sub(1).and_then(|last| amounts.get(&last)).copied().unwrap_or(0);
+        
+        let gas_used = self.config.base_gas_per_swap.saturating_mul(path.hops.len() as u64);
+        let simulated_profit = path.start_token.to_decimal(final_amount)
+            - path.start_token.to_decimal(path.initial_amount)
+            - opportunity.estimated_gas_cost;
+        
+        Ok((amounts, gas_used, simulated_profit))
     }
     
-    async fn simulate_transaction(&self, _opportunity: &ArbitrageOpportunity) -> Result<(u64, Decimal)> {
-        // TODO: Implement transaction simulation
-        // Return (gas_used, simulated_profit)
-        todo!()
+    async fn simulate_transaction(&self, opportunity: &ArbitrageOpportunity) -> Result<(u64, Decimal)> {
+        let (_, gas_used, simulated_profit) = self.simulate_hops(opportunity).await?;
+        Ok((gas_used, simulated_profit))
     }
     
-    async fn execute_transaction(&self, _opportunity: &ArbitrageOpportunity) -> Result<(String, u64, Decimal)> {
-        // TODO: Implement actual transaction execution
-        // Return (transaction_digest, gas_used, actual_profit)
-        todo!()
+    /// Submit, retrying network and RPC failures up to `max_retries` times
+    /// with the gas budget raised by `gas_escalation_factor` each time.
+    /// Returns (transaction_digest, gas_used, actual_profit).
+    async fn execute_transaction(
+        &self,
+        opportunity: &ArbitrageOpportunity,
+        wallet: Option<&WalletState>,
+    ) -> Result<(String, u64, Decimal)> {
+        let Some(submitter) = &self.submitter else {
+            return Err(BotError::Execution(format!(
+                "Cannot submit {}: no Sui RPC client or signer configured", opportunity.path.path_id
+            )));
+        };
+        
+        let mut gas_budget = self.config.gas_budget;
+        let mut attempt = 0;
+        loop {
+            if let Some(max_age_ms) = self.max_opportunity_age_ms
+                && opportunity.path.is_stale(max_age_ms)
+            {
+                return Err(BotError::StalePrice {
+                    age_ms: now().saturating_sub(opportunity.path.calculated_at),
+                    max_age_ms,
+                });
+            }
+            
+            match submitter.submit(wallet, opportunity, gas_budget).await {
+                Ok(outcome) => return Ok(outcome),
+                Err(e) if attempt < self.config.max_retries && is_retryable(&e) => {
+                    attempt += 1;
+                    gas_budget = (gas_budget as f64 * self.config.gas_escalation_factor) as u64;
+                    warn!(
+                        "Submitting {} failed ({}), retry {}/{} with gas budget {}",
+                        opportunity.path.path_id, e, attempt, self.config.max_retries, gas_budget
+                    );
+                    tokio::time::sleep(Duration::from_secs(self.config.retry_delay_seconds)).await;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+    
+    /// Simulate first when required. Returns `Some` when the simulation
+    /// decides the outcome (dry run, failure or unprofitable trade).
+    async fn run_simulation_guard(&mut self, opportunity: &ArbitrageOpportunity) -> Option<ExecutionResult> {
+        if !self.config.dry_run && !self.config.require_profitable_simulation {
+            return None;
+        }
+        
+        let result = ExecutionResult::new(opportunity.clone());
+        let (amounts, gas_used, simulated_profit) = match self.simulate_hops(opportunity).await {
+            Ok(simulation) => simulation,
+            Err(e) => return Some(result.failure(e)),
+        };
+        
+        if self.config.dry_run {
+            let path = &opportunity.path;
+            let hop_amounts: Vec<String> = (0..path.hops.len())
+                .map(|index| amounts.get(&index).copied().unwrap_or(0).to_string())
+                .collect();
+            info!(
+                "Dry run {}: {} | in {} out [{}] (expected {}) | gas {} | profit {} {} (expected {})",
+                path.path_id,
+                path.path_description(),
+                path.initial_amount,
+                hop_amounts.join(", "),
+                path.expected_final_amount,
+                gas_used,
+                simulated_profit,
+                path.start_token.symbol,
+                opportunity.net_profit,
+            );
+            return Some(result.simulated(amounts, gas_used, simulated_profit));
+        }
+        
+        if simulated_profit <= Decimal::ZERO {
+            self.stats.simulation_rejections += 1;
+            warn!("Rejecting {}: simulated profit {}", opportunity.path.path_id, simulated_profit);
+            return Some(result.failure(BotError::Execution(format!(
+                "Simulation shows unprofitable: {}", simulated_profit
+            ))));
+        }
+        
+        None
     }
 }
 
+/// Transient failures worth resubmitting; on-chain reverts such as
+/// `SlippageTooHigh` would fail the same way again
+fn is_retryable(error: &BotError) -> bool {
+    matches!(error, BotError::Network(_) | BotError::Rpc(_))
+}
+
 #[async_trait]
 impl TradeExecutor for DefaultTradeExecutor {
-    async fn execute(&mut self, _opportunity: ArbitrageOpportunity) -> ExecutionResult {
-        todo!()
+    async fn execute(&mut self, opportunity: ArbitrageOpportunity) -> ExecutionResult {
+        let mut result = match self.run_simulation_guard(&opportunity).await {
+            Some(result) => result,
+            None => self.submit(&opportunity).await,
+        };
+        result.record_hop_profits();
+        
+        self.stats.record(&result);
+        if let Some(metrics) = &self.metrics {
+            metrics.record_execution(&result);
+        }
+        if let Some(pnl_tracker) = &self.pnl_tracker
+            && result.status == ExecutionStatus::Success
+        {
+            pnl_tracker
+                .write()
+                .await
+                .record_trade(result.actual_profit, &result.opportunity.path.start_token.symbol);
+        }
+        result
+    }
+    
+    async fn simulate(&self, opportunity: &ArbitrageOpportunity) -> Result<(u64, Decimal)> {
+        self.simulate_transaction(opportunity).await
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use crate::{
+        arbitrage::calculator::{ArbitrageCalculator, DefaultArbitrageCalculator},
+        testing::{manager_with, triangle_snapshot},
+        types::{DexId, ExecutionStatus},
+        utils::config::{ArbitrageConfig, WalletConfig},
+    };
+
+    use super::*;
+
+    /// Dry-run executor over the triangle pools and its best opportunity
+    async fn setup() -> Result<(DefaultTradeExecutor, Arc<RwLock<DexManager>>, ArbitrageOpportunity)> {
+        let snapshot = triangle_snapshot();
+        let opportunity = DefaultArbitrageCalculator::new(ArbitrageConfig::default())
+            .find_opportunities(&snapshot)
+            .await
+            .into_iter()
+            .next()
+            .ok_or_else(|| BotError::NotFound("triangle opportunity".into()))?;
+
+        let manager = Arc::new(RwLock::new(manager_with(&snapshot)?));
+        let executor = DefaultTradeExecutor::new(ExecutionConfig::default()).with_dex_manager(manager.clone());
+        Ok((executor, manager, opportunity))
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_simulates_every_hop() -> Result<()> {
+        let (mut executor, _, opportunity) = setup().await?;
+        let expected_final = opportunity.path.expected_final_amount;
+
+        let result = executor.execute(opportunity).await;
+
+        assert_eq!(result.status, ExecutionStatus::Simulated);
+        assert_eq!(result.actual_amounts.len(), 3);
+        assert_eq!(result.actual_amounts.get(&2), Some(&expected_final));
+        assert_eq!(result.gas_used, 3 * ExecutionConfig::default().base_gas_per_swap);
+        assert!(result.actual_profit > Decimal::ZERO);
+        assert_eq!(executor.stats().total_executions, 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_aborts_when_hop_falls_below_minimum() -> Result<()> {
+        let (mut executor, manager, opportunity) = setup().await?;
+        {
+            let mut manager = manager.write().await;
+            let mut pool = manager
+                .get_pool(&DexId::Turbos, &"p2".to_string())
+                .cloned()
+                .ok_or_else(|| BotError::NotFound("p2".into()))?;
+            pool.reserve_b *= Decimal::new(9, 1);
+            pool.version += 1;
+            manager.update_pool_state(pool)?;
+        }
+
+        let result = executor.execute(opportunity).await;
+
+        assert_eq!(result.status, ExecutionStatus::Failed);
+        assert!(result.error.is_some_and(|error| error.contains("Slippage")));
+        assert_eq!(executor.stats().failed_executions, 1);
+        Ok(())
+    }
+
+    /// Fails with the queued errors in order, then succeeds; records each gas budget
+    struct FlakySubmitter {
+        failures: Mutex<Vec<BotError>>,
+        budgets: Arc<Mutex<Vec<u64>>>,
+    }
+
+    #[async_trait]
+    impl TransactionSubmitter for FlakySubmitter {
+        async fn submit(
+            &self,
+            _wallet: Option<&WalletState>,
+            _opportunity: &ArbitrageOpportunity,
+            gas_budget: u64,
+        ) -> Result<(String, u64, Decimal)> {
+            if let Ok(mut budgets) = self.budgets.lock() {
+                budgets.push(gas_budget);
+            }
+            let failure = self.failures.lock().ok().and_then(|mut failures| failures.pop());
+            match failure {
+                Some(e) => Err(e),
+                None => Ok(("0xdigest".into(), gas_budget, Decimal::ONE)),
+            }
+        }
+    }
+
+    /// Live executor whose submissions fail with `failures` (last one first)
+    async fn live_setup(failures: Vec<BotError>) -> Result<(DefaultTradeExecutor, Arc<Mutex<Vec<u64>>>, ArbitrageOpportunity)> {
+        let (_, manager, opportunity) = setup().await?;
+        let budgets = Arc::new(Mutex::new(Vec::new()));
+        let config = ExecutionConfig {
+            dry_run: false,
+            retry_delay_seconds: 0,
+            ..ExecutionConfig::default()
+        };
+        let executor = DefaultTradeExecutor::new(config)
+            .with_dex_manager(manager)
+            .with_submitter(Box::new(FlakySubmitter { failures: Mutex::new(failures), budgets: budgets.clone() }));
+        Ok((executor, budgets, opportunity))
+    }
+
+    fn recorded(budgets: &Arc<Mutex<Vec<u64>>>) -> Vec<u64> {
+        budgets.lock().map(|budgets| budgets.clone()).unwrap_or_default()
+    }
+
+    #[tokio::test]
+    async fn test_retries_network_errors_with_more_gas() -> Result<()> {
+        let (mut executor, budgets, opportunity) =
+            live_setup(vec![BotError::Rpc("timeout".into()), BotError::Network("reset".into())]).await?;
+
+        let result = executor.execute(opportunity).await;
+
+        assert_eq!(result.status, ExecutionStatus::Success);
+        assert_eq!(recorded(&budgets), vec![10_000_000, 15_000_000, 22_500_000]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_retries() -> Result<()> {
+        let failures = (0..3).map(|i| BotError::Network(format!("attempt {}", 3 - i))).collect();
+        let (mut executor, budgets, opportunity) = live_setup(failures).await?;
+
+        let result = executor.execute(opportunity).await;
+
+        assert_eq!(result.status, ExecutionStatus::Failed);
+        assert_eq!(result.error.as_deref(), Some("Network error: attempt 3"));
+        assert_eq!(recorded(&budgets).len(), 3);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_reverted_swap_is_not_retried() -> Result<()> {
+        let revert = BotError::SlippageTooHigh { expected: Decimal::ONE, actual: Decimal::ZERO };
+        let (mut executor, budgets, opportunity) = live_setup(vec![revert]).await?;
+
+        let result = executor.execute(opportunity).await;
+
+        assert_eq!(result.status, ExecutionStatus::Failed);
+        assert_eq!(recorded(&budgets).len(), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_stale_path_is_not_submitted() -> Result<()> {
+        let (executor, budgets, mut opportunity) = live_setup(Vec::new()).await?;
+        let mut executor = executor.with_max_opportunity_age_ms(1_000);
+        opportunity.path.calculated_at -= 5_000;
+
+        let result = executor.execute(opportunity).await;
+
+        assert_eq!(result.status, ExecutionStatus::Failed);
+        assert!(recorded(&budgets).is_empty());
+        Ok(())
+    }
+
+    /// Records the key of the wallet each trade is signed with
+    struct KeyRecorder(Arc<Mutex<Vec<Option<String>>>>);
+
+    #[async_trait]
+    impl TransactionSubmitter for KeyRecorder {
+        async fn submit(
+            &self,
+            wallet: Option<&WalletState>,
+            _opportunity: &ArbitrageOpportunity,
+            gas_budget: u64,
+        ) -> Result<(String, u64, Decimal)> {
+            if let Ok(mut keys) = self.0.lock() {
+                keys.push(wallet.map(|wallet| wallet.private_key.clone()));
+            }
+            Ok(("0xdigest".into(), gas_budget, Decimal::ONE))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_trades_rotate_through_wallets_within_capital() -> Result<()> {
+        let (_, manager, opportunity) = setup().await?;
+        let wallet = |key: &str, max_capital_usd: u64| WalletConfig {
+            private_key: key.into(),
+            max_capital_usd: Decimal::from(max_capital_usd),
+            enabled: true,
+        };
+        let config = ExecutionConfig {
+            dry_run: false,
+            wallets: vec![wallet("a", 1_000_000_000), wallet("b", 1_000_000_000), wallet("small", 0)],
+            ..ExecutionConfig::default()
+        };
+        let keys = Arc::new(Mutex::new(Vec::new()));
+        let mut executor = DefaultTradeExecutor::new(config)
+            .with_dex_manager(manager)
+            .with_submitter(Box::new(KeyRecorder(keys.clone())));
+
+        for _ in 0..3 {
+            assert_eq!(executor.execute(opportunity.clone()).await.status, ExecutionStatus::Success);
+        }
+
+        // The third turn falls to "small", which cannot cover the trade
+        let keys = keys.lock().map(|keys| keys.clone()).unwrap_or_default();
+        assert_eq!(keys, vec![Some("a".to_string()), Some("b".to_string()), Some("a".to_string())]);
+        Ok(())
+    }
+}
@@ -1 +1,3 @@
-pub mod executor;
\ No newline at end of file
+pub mod executor;
+pub mod ptb_builder;
+pub mod wallet;
\ No newline at end of file
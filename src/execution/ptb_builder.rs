@@ -0,0 +1,232 @@
+//! Programmable transaction blocks for atomic multi-hop swaps
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::types::{ArbitrageHop, ArbitragePath, BotError, DexId, Network, Result, SUI_COIN_TYPE, TokenInfo};
+
+/// Module and function of the Cetus swap entry point
+const CETUS_SWAP_MODULE: &str = "router";
+const CETUS_SWAP_FUNCTION: &str = "swap";
+
+/// Transaction input: an on-chain object or a pure value
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum CallArg {
+    Object(String),
+    Pure(Value),
+}
+
+/// Value passed to a command
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Argument {
+    /// The coin paying for gas
+    GasCoin,
+    /// Index into the transaction inputs
+    Input(u16),
+    /// Output of an earlier command
+    Result(u16),
+    /// One output of an earlier command returning several values
+    NestedResult(u16, u16),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum Command {
+    SplitCoins {
+        coin: Argument,
+        amounts: Vec<Argument>,
+    },
+    MoveCall {
+        package: String,
+        module: String,
+        function: String,
+        type_arguments: Vec<String>,
+        arguments: Vec<Argument>,
+    },
+    TransferObjects {
+        objects: Vec<Argument>,
+        address: Argument,
+    },
+}
+
+/// Inputs and commands executed atomically in one transaction
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ProgrammableTransaction {
+    pub inputs: Vec<CallArg>,
+    pub commands: Vec<Command>,
+}
+
+impl ProgrammableTransaction {
+    fn input(&mut self, arg: CallArg) -> Argument {
+        self.inputs.push(arg);
+        Argument::Input(self.inputs.len() as u16 - 1)
+    }
+
+    fn command(&mut self, command: Command) -> Argument {
+        self.commands.push(command);
+        Argument::Result(self.commands.len() as u16 - 1)
+    }
+}
+
+/// Builds one PTB per arbitrage path: split the input coin, chain each
+/// swap's output coin into the next hop, and send the result to the sender
+pub struct ArbitragePtbBuilder {
+    network: Network,
+    sender: String,
+    /// Coin object to split the input from when the path does not start in SUI
+    source_coin: Option<String>,
+}
+
+impl ArbitragePtbBuilder {
+    pub fn new(network: Network, sender: impl Into<String>) -> Self {
+        Self {
+            network,
+            sender: sender.into(),
+            source_coin: None,
+        }
+    }
+
+    /// Coin object holding the start token, needed for non-SUI paths
+    pub fn with_source_coin(mut self, object_id: impl Into<String>) -> Self {
+        self.source_coin = Some(object_id.into());
+        self
+    }
+
+    pub fn build(&self, path: &ArbitragePath) -> Result<ProgrammableTransaction> {
+        path.validate()?;
+        if path.initial_amount == 0 {
+            return Err(BotError::InvalidState(format!("Path {} has no input amount", path.path_id)));
+        }
+
+        let mut tx = ProgrammableTransaction::default();
+
+        let source = if path.start_token.address.as_deref() == Some(SUI_COIN_TYPE) {
+            Argument::GasCoin
+        } else {
+            let coin = self.source_coin.clone().ok_or_else(|| {
+                BotError::InvalidState(format!("No {} coin to fund path {}", path.start_token.symbol, path.path_id))
+            })?;
+            tx.input(CallArg::Object(coin))
+        };
+        let amount = tx.input(CallArg::Pure(path.initial_amount.into()));
+        let mut coin = tx.command(Command::SplitCoins { coin: source, amounts: vec![amount] });
+
+        for hop in &path.hops {
+            let swap = self.swap_call(&mut tx, hop, coin)?;
+            coin = tx.command(swap);
+        }
+
+        let sender = tx.input(CallArg::Pure(self.sender.clone().into()));
+        tx.command(Command::TransferObjects { objects: vec![coin], address: sender });
+        Ok(tx)
+    }
+
+    /// Move call swapping `coin_in` through the hop's pool
+    fn swap_call(&self, tx: &mut ProgrammableTransaction, hop: &ArbitrageHop, coin_in: Argument) -> Result<Command> {
+        if hop.dex_id != DexId::Cetus {
+            return Err(BotError::dex(hop.dex_id, "PTB swaps are not supported"));
+        }
+        let package = hop.dex_id
+            .package_id(self.network)
+            .ok_or_else(|| BotError::dex(hop.dex_id, format!("No package on {:?}", self.network)))?;
+        let coin_type = |token: &TokenInfo| {
+            token.address.clone().ok_or_else(|| BotError::InvalidState(format!("{} has no coin type", token.symbol)))
+        };
+
+        let pool = tx.input(CallArg::Object(hop.pool_id.clone()));
+        let min_amount_out = tx.input(CallArg::Pure(hop.min_amount_out.into()));
+        Ok(Command::MoveCall {
+            package: package.to_string(),
+            module: CETUS_SWAP_MODULE.to_string(),
+            function: CETUS_SWAP_FUNCTION.to_string(),
+            type_arguments: vec![coin_type(&hop.token_in)?, coin_type(&hop.token_out)?],
+            arguments: vec![pool, coin_in, min_amount_out],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        arbitrage::calculator::{ArbitrageCalculator, DefaultArbitrageCalculator},
+        testing::{snapshot_with, test_pool, test_token},
+        utils::config::ArbitrageConfig,
+    };
+
+    use super::*;
+
+    const SENDER: &str = "0xa11ce";
+
+    /// Priced SUI -> USDC -> SUI path selling through `p2` on `dex_id` and
+    /// buying back on Cetus `p1`
+    async fn cetus_path(dex_id: DexId) -> Result<ArbitragePath> {
+        let (sui, usdc) = (test_token("SUI"), test_token("USDC"));
+        let snapshot = snapshot_with(vec![
+            test_pool("p1", DexId::Cetus, &sui, &usdc, 1_000_000, 2_000_000),
+            test_pool("p2", dex_id, &sui, &usdc, 1_000_000, 2_200_000),
+        ]);
+        let calculator = DefaultArbitrageCalculator::new(ArbitrageConfig {
+            min_dex_count_per_path: 1,
+            ..ArbitrageConfig::default()
+        });
+
+        let path = calculator
+            .find_paths(&sui, 2, &snapshot)
+            .await
+            .into_iter()
+            .find(|path| path.hops[0].pool_id == "p2")
+            .ok_or_else(|| BotError::NotFound("SUI path".into()))?;
+        Ok(calculator.calculate_profitability(&path, &snapshot).await?.path)
+    }
+
+    #[tokio::test]
+    async fn test_chains_swaps_in_one_transaction() -> Result<()> {
+        let path = cetus_path(DexId::Cetus).await?;
+        let tx = ArbitragePtbBuilder::new(Network::SuiMainnet, SENDER).build(&path)?;
+
+        assert_eq!(tx.commands.len(), 4);
+        assert!(matches!(&tx.commands[0], Command::SplitCoins { coin: Argument::GasCoin, .. }));
+        for (index, hop) in path.hops.iter().enumerate() {
+            let Command::MoveCall { package, arguments, .. } = &tx.commands[index + 1] else {
+                return Err(BotError::InvalidState(format!("command {} is not a swap", index + 1)));
+            };
+            assert_eq!(package, crate::dex::cetus::CETUS_PACKAGE_ID);
+            // Each swap spends the coin produced by the command before it
+            assert_eq!(arguments[1], Argument::Result(index as u16));
+            let Argument::Input(min_out) = arguments[2] else {
+                return Err(BotError::InvalidState("min_amount_out is not an input".into()));
+            };
+            assert_eq!(tx.inputs[min_out as usize], CallArg::Pure(hop.min_amount_out.into()));
+        }
+        assert_eq!(
+            tx.commands[3],
+            Command::TransferObjects { objects: vec![Argument::Result(2)], address: Argument::Input(5) }
+        );
+        assert_eq!(tx.inputs[5], CallArg::Pure(SENDER.into()));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_rejects_unsupported_dex() -> Result<()> {
+        let path = cetus_path(DexId::Turbos).await?;
+        let result = ArbitragePtbBuilder::new(Network::SuiMainnet, SENDER).build(&path);
+
+        assert!(matches!(result, Err(BotError::Dex { dex: DexId::Turbos, .. })));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_non_sui_start_needs_source_coin() -> Result<()> {
+        let mut path = cetus_path(DexId::Cetus).await?;
+        path.start_token = test_token("USDC");
+        path.end_token = test_token("USDC");
+        path.hops.reverse();
+        let builder = ArbitragePtbBuilder::new(Network::SuiMainnet, SENDER);
+
+        assert!(builder.build(&path).is_err());
+
+        let tx = builder.with_source_coin("0xc01n").build(&path)?;
+        assert_eq!(tx.inputs[0], CallArg::Object("0xc01n".into()));
+        assert_eq!(tx.commands[0], Command::SplitCoins { coin: Argument::Input(0), amounts: vec![Argument::Input(1)] });
+        Ok(())
+    }
+}
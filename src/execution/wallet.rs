@@ -0,0 +1,181 @@
+use std::{fmt, sync::atomic::{AtomicUsize, Ordering}};
+
+use rust_decimal::Decimal;
+
+use crate::{types::{ExecutionResult, ExecutionStats, Timestamp, now}, utils::{config::{REDACTED, WalletConfig}, pnl::PNL_DAY_MS}};
+
+/// Runtime state of one trading wallet
+#[derive(Clone)]
+pub struct WalletState {
+    pub private_key: String,
+    pub max_capital_usd: Decimal,
+    /// Realized losses since the last daily reset
+    pub daily_loss: Decimal,
+    /// Path currently being executed with this wallet
+    pub current_position: Option<String>,
+    pub stats: ExecutionStats,
+}
+
+impl WalletState {
+    pub fn new(config: &WalletConfig) -> Self {
+        Self {
+            private_key: config.private_key.clone(),
+            max_capital_usd: config.max_capital_usd,
+            daily_loss: Decimal::ZERO,
+            current_position: None,
+            stats: ExecutionStats::new(),
+        }
+    }
+
+    /// Free to take a trade whose input is worth `trade_usd`
+    pub fn is_available(&self, trade_usd: Decimal) -> bool {
+        self.current_position.is_none() && trade_usd <= self.max_capital_usd
+    }
+
+    /// Release the wallet and account for the trade outcome
+    pub fn finish(&mut self, result: &ExecutionResult) {
+        self.current_position = None;
+        if result.actual_profit < Decimal::ZERO {
+            self.daily_loss -= result.actual_profit;
+        }
+        self.stats.record(result);
+    }
+}
+
+impl fmt::Debug for WalletState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WalletState")
+            .field("private_key", &REDACTED)
+            .field("max_capital_usd", &self.max_capital_usd)
+            .field("daily_loss", &self.daily_loss)
+            .field("current_position", &self.current_position)
+            .field("stats", &self.stats)
+            .finish()
+    }
+}
+
+/// Enabled wallets handed out in round-robin order
+#[derive(Debug, Default)]
+pub struct WalletPool {
+    wallets: Vec<WalletState>,
+    next_wallet_index: AtomicUsize,
+    /// Day (ms since epoch / `PNL_DAY_MS`) the daily losses cover
+    loss_day: u64,
+}
+
+impl WalletPool {
+    pub fn new(configs: &[WalletConfig]) -> Self {
+        Self {
+            wallets: configs
+                .iter()
+                .filter(|config| config.enabled)
+                .map(WalletState::new)
+                .collect(),
+            next_wallet_index: AtomicUsize::new(0),
+            loss_day: now() / PNL_DAY_MS,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.wallets.is_empty()
+    }
+
+    pub fn wallets(&self) -> &[WalletState] {
+        &self.wallets
+    }
+
+    /// Pick the next wallet after the last one used that is free and may
+    /// trade `trade_usd`
+    pub fn select(&self, trade_usd: Decimal) -> Option<usize> {
+        let count = self.wallets.len();
+        let start = self.next_wallet_index.fetch_add(1, Ordering::Relaxed);
+        (0..count)
+            .map(|offset| (start + offset) % count)
+            .find(|index| self.wallets[*index].is_available(trade_usd))
+    }
+
+    pub fn get(&self, index: usize) -> Option<&WalletState> {
+        self.wallets.get(index)
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut WalletState> {
+        self.wallets.get_mut(index)
+    }
+
+    /// Clear daily loss counters
+    pub fn reset_daily_losses(&mut self) {
+        for wallet in &mut self.wallets {
+            wallet.daily_loss = Decimal::ZERO;
+        }
+    }
+
+    /// Reset daily losses once `now` falls on a new UTC day
+    pub fn roll_over_day(&mut self, now: Timestamp) {
+        let day = now / PNL_DAY_MS;
+        if day != self.loss_day {
+            self.reset_daily_losses();
+            self.loss_day = day;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(enabled: bool) -> WalletConfig {
+        WalletConfig {
+            private_key: "key".into(),
+            max_capital_usd: Decimal::from(100),
+            enabled,
+        }
+    }
+
+    #[test]
+    fn test_select_round_robin_skips_busy_wallets() {
+        let mut pool = WalletPool::new(&[config(true), config(false), config(true)]);
+        assert_eq!(pool.wallets().len(), 2);
+
+        assert_eq!(pool.select(Decimal::ONE), Some(0));
+        assert_eq!(pool.select(Decimal::ONE), Some(1));
+
+        if let Some(wallet) = pool.get_mut(0) {
+            wallet.current_position = Some("path".into());
+        }
+        assert_eq!(pool.select(Decimal::ONE), Some(1));
+        assert_eq!(pool.select(Decimal::ONE), Some(1));
+    }
+
+    #[test]
+    fn test_select_skips_wallets_below_trade_size() {
+        let mut pool = WalletPool::new(&[config(true)]);
+        if let Some(wallet) = pool.get_mut(0) {
+            wallet.daily_loss = Decimal::from(500);
+        }
+        // Losses do not block trading; the trade size does
+        assert_eq!(pool.select(Decimal::from(100)), Some(0));
+        assert_eq!(pool.select(Decimal::from(101)), None);
+    }
+
+    #[test]
+    fn test_daily_losses_reset_on_new_day() {
+        let mut pool = WalletPool::new(&[config(true)]);
+        pool.loss_day = 10;
+        if let Some(wallet) = pool.get_mut(0) {
+            wallet.daily_loss = Decimal::from(5);
+        }
+
+        pool.roll_over_day(10 * PNL_DAY_MS + 1);
+        assert_eq!(pool.wallets()[0].daily_loss, Decimal::from(5));
+        pool.roll_over_day(11 * PNL_DAY_MS);
+        assert_eq!(pool.wallets()[0].daily_loss, Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_debug_redacts_private_key() {
+        let pool = WalletPool::new(&[config(true)]);
+        let debug = format!("{:?}", pool);
+        assert!(!debug.contains("\"key\""));
+        assert!(debug.contains(REDACTED));
+    }
+}
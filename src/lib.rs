@@ -3,9 +3,12 @@
 #![deny(clippy::expect_used)]
 #![deny(unused_must_use)]
 pub mod arbitrage;
+pub mod client;
 pub mod dex;
 pub mod event;
 pub mod execution;
 pub mod sync;
+#[cfg(test)]
+pub mod testing;
 pub mod types;
 pub mod utils;
\ No newline at end of file
@@ -1,39 +1,172 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
-use arbitrage_bot::{arbitrage::{arbitrage_engine::{ArbitrageEngine, ArbitrageEngineBuilder}, calculator::{ArbitrageCalculator, DefaultArbitrageCalculator}, detector::{ArbitrageDetector, DefaultArbitrageDetector}, validator::{DefaultOpportunityValidator, OpportunityValidator}}, dex::manager::DexManager, event::processor::{DefaultEventProcessor, EventProcessor}, execution::executor::{DefaultTradeExecutor, TradeExecutor}, types::Result, utils::{config::Config, logger::init}};
-use tokio::sync::RwLock;
-use tracing::{info, error};
+use clap::Parser;
+use arbitrage_bot::{types::{BotError, DexId, RawEvent}, client::sui_rpc::SuiRpcClient, dex::{aftermath::adapter::AftermathAdapter, cetus::adapter::CetusAdapter, kriya::adapter::KriyaAdapter}, arbitrage::{arbitrage_engine::{ArbitrageEngine, ArbitrageEngineBuilder}, circuit_breaker::CircuitBreaker, calculator::{ArbitrageCalculator, DefaultArbitrageCalculator}, detector::{ArbitrageDetector, DefaultArbitrageDetector}, validator::{DefaultOpportunityValidator, OpportunityValidator}}, dex::manager::DexManager, event::processor::{DefaultEventProcessor, EventProcessor}, execution::executor::{DefaultTradeExecutor, TradeExecutor}, sync::periodic::{SYNC_STATE_FILE, SyncOrchestratorBuilder}, types::Result, utils::{alerting::Alerter, config::Config, event_rate::EventRateCounter, logger::init, metrics::{MetricsServer, StateEndpoint, register_metrics}, pnl::PnlTracker}};
+use tokio::sync::{RwLock, watch};
+use tracing::{info, error, warn};
+
+/// Where SIGUSR1 dumps the engine state
+#[cfg(unix)]
+const STATE_EXPORT_PATH: &str = "state_export.json";
+
+/// Command line options
+#[derive(Debug, Parser)]
+#[command(name = "arbitrage-bot", version, about = "Cross-DEX arbitrage bot for Sui")]
+struct Cli {
+    /// Path to the TOML config file
+    #[arg(long = "config")]
+    config_path: Option<String>,
+
+    /// Run in dry-run mode for the given number of seconds, then exit
+    #[arg(long, value_name = "SECONDS")]
+    dry_run_for: Option<u64>,
+
+    /// Replay recorded events from a JSONL file instead of going live
+    #[arg(long, value_name = "FILE")]
+    backtest: Option<String>,
+
+    /// Print the configuration layout with default values as JSON and exit
+    #[arg(long)]
+    print_schema: bool,
+
+    /// Print a default config file and exit
+    #[arg(long)]
+    generate_config: bool,
+
+    /// Print how the loaded config differs from the defaults and exit
+    #[arg(long)]
+    show_config_diff: bool,
+
+    /// On shutdown, merge discovered pools into the config and save it here
+    #[arg(long, value_name = "PATH")]
+    save_config: Option<String>,
+}
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    init();
+    let cli = Cli::parse();
+    
+    if cli.print_schema {
+        println!("{}", serde_json::to_string_pretty(&Config::default())?);
+        return Ok(());
+    }
+    
+    if cli.generate_config {
+        println!("{}", config_toml(&Config::with_example_dex())?);
+        return Ok(());
+    }
+    
+    // Loaded before the logger exists, since the logger is configured by it
+    let mut config = match &cli.config_path {
+        Some(path) => Config::load_from(path)?,
+        None => Config::load()?,
+    };
+    
+    init(config.logging_config());
     info!("Starting Arbitrage Bot");
     
-    let config = Config::load()?;
+    if cli.show_config_diff {
+        print_config_diff(&config)?;
+        return Ok(());
+    }
+    
+    if cli.backtest.is_some() || cli.dry_run_for.is_some() {
+        // Never submit real transactions when backtesting or smoke testing
+        config.execution.dry_run = true;
+    }
     config.validate()?;
     
+    let metrics = if config.logging_config().enable_metrics {
+        Some(register_metrics()?)
+    } else {
+        None
+    };
+    
     // 1. Create and initialize DexManager
-    let dex_manager = DexManager::new();
+    let mut dex_manager = DexManager::new(config.sync_config());
+    let mut rpc_client = SuiRpcClient::new_with_endpoint(&config.network_config().rpc_url)?
+        .with_fallback_urls(config.network_config().fallback_rpc_urls.clone());
+    if let Some(metrics) = &metrics {
+        rpc_client = rpc_client.with_metrics(metrics.clone());
+    }
+    let rpc_client = Arc::new(rpc_client);
+    for dex in config.network_config().dexes.iter().filter(|dex| dex.enabled) {
+        match dex.id {
+            DexId::Cetus => {
+                let adapter = CetusAdapter::from_config(dex, rpc_client.clone())
+                    .with_network(config.network_config().network);
+                dex_manager.register_dex(Box::new(adapter))?
+            }
+            DexId::Kriya => {
+                let adapter = KriyaAdapter::from_config(dex, rpc_client.clone())
+                    .with_network(config.network_config().network);
+                dex_manager.register_dex(Box::new(adapter))?
+            }
+            DexId::Aftermath => {
+                let adapter = AftermathAdapter::from_config(dex, rpc_client.clone())
+                    .with_network(config.network_config().network);
+                dex_manager.register_dex(Box::new(adapter))?
+            }
+            other => warn!("No adapter for DEX {}, skipping", other),
+        }
+    }
+    let pool_events = dex_manager.subscribe_pool_events();
     let dex_manager = Arc::new(RwLock::new(dex_manager));
+    let event_rate = Arc::new(EventRateCounter::new());
+    // Backtests replay recorded events and never reach a node
+    let sync_orchestrator = Arc::new(
+        SyncOrchestratorBuilder::new(dex_manager.clone(), config.sync_config())
+            .with_network_client(config.network_config().network, rpc_client.clone())
+            .with_alerter(Alerter::from_config(&config.alerts)?)
+            .with_event_rate(event_rate.clone())
+            .with_sync_state_path(SYNC_STATE_FILE)
+            .with_health_check(cli.backtest.is_none())
+            .build()
+            .await?,
+    );
     
     // 2. Create components
-    let event_processor = Box::new(DefaultEventProcessor::new(
-        dex_manager.clone(),
-        config.network_config().clone(),
-    )) as Box<dyn EventProcessor>;
     
-    let calculator = Box::new(DefaultArbitrageCalculator::new(
-        config.arbitrage_config().clone(),
-    )) as Box<dyn ArbitrageCalculator>;
+    let arbitrage_config = Arc::new(std::sync::RwLock::new(config.arbitrage_config().clone()));
+    let calculator = || {
+        Box::new(
+            DefaultArbitrageCalculator::with_shared_config(arbitrage_config.clone())
+                .with_base_gas_per_swap(config.execution_config().base_gas_per_swap),
+        ) as Box<dyn ArbitrageCalculator>
+    };
     
-    let detector = Box::new(DefaultArbitrageDetector::new(
+    let (detector, detector_handle) = DefaultArbitrageDetector::new(
         dex_manager.clone(),
-        calculator,
-    )) as Box<dyn ArbitrageDetector>;
+        calculator(),
+        pool_events,
+        config.arbitrage_config(),
+    );
+    let detector = Box::new(detector) as Box<dyn ArbitrageDetector>;
     
-    let executor = Box::new(DefaultTradeExecutor::new(
-        config.execution_config().clone(),
-    )) as Box<dyn TradeExecutor>;
+    let mut event_processor = DefaultEventProcessor::new(
+        dex_manager.clone(),
+        config.network_config().clone(),
+        config.sync_config().clone(),
+    )
+    .with_opportunity_detection(detector_handle, calculator())
+    .with_event_rate(event_rate);
+    // Replayed swaps must not trigger live RPC re-syncs
+    if cli.backtest.is_none() {
+        event_processor = event_processor.with_sync_orchestrator(sync_orchestrator.clone());
+    }
+    
+    let pnl_tracker = Arc::new(RwLock::new(PnlTracker::new()));
+    let mut executor = DefaultTradeExecutor::new(config.execution_config().clone())
+        .with_dex_manager(dex_manager.clone())
+        .with_max_opportunity_age_ms(config.validation_config().max_opportunity_age_ms)
+        .with_pnl_tracker(pnl_tracker.clone());
+    
+    if let Some(metrics) = &metrics {
+        event_processor = event_processor.with_metrics(metrics.clone());
+        executor = executor.with_metrics(metrics.clone());
+    }
+    let event_processor = Box::new(event_processor) as Box<dyn EventProcessor>;
+    let executor = Box::new(executor) as Box<dyn TradeExecutor>;
     
     let validator = Box::new(DefaultOpportunityValidator::new(
         dex_manager.clone(),
@@ -41,19 +174,116 @@ async fn main() -> Result<()> {
     )) as Box<dyn OpportunityValidator>;
     
     // 3. Create engine
-    let engine = ArbitrageEngineBuilder::new()
+    let mut engine_builder = ArbitrageEngineBuilder::new()
         .with_event_processor(event_processor)
         .with_detector(detector)
         .with_executor(executor)
         .with_validator(validator)
-        .build()?;
+        .with_validation_config(config.validation_config().clone())
+        .with_alerter(Alerter::from_config(&config.alerts)?)
+        .with_execution_broadcast_capacity(config.logging_config().execution_broadcast_capacity)
+        .with_dex_manager(dex_manager.clone())
+        .with_sync_orchestrator(sync_orchestrator.clone())
+        .with_sync_config(config.sync_config().clone())
+        .with_config_summary(config.redacted_summary()?)
+        .with_pnl_tracker(pnl_tracker, Duration::from_secs(config.arbitrage_config().pnl_report_interval_secs))
+        .with_circuit_breaker(CircuitBreaker::from_config(config.execution_config()));
+    if let Some(metrics) = &metrics {
+        engine_builder = engine_builder.with_metrics(metrics.clone());
+    }
+    // Kept alive for as long as the engine runs
+    let mut _config_watcher = None;
+    if let Some(path) = cli.config_path.as_deref().or_else(|| Config::find_config_file()) {
+        let (config_tx, config_rx) = watch::channel(config.clone());
+        _config_watcher = Some(Config::watch_file(path, config_tx)?);
+        engine_builder = engine_builder.with_config_updates(config_rx, arbitrage_config);
+    }
+    let mut engine = engine_builder.build()?;
+    if let Some(metrics) = metrics {
+        let state = config.state_api_secret.clone().map(|secret| StateEndpoint { handle: engine.state_handle(), secret });
+        MetricsServer::start(config.logging_config().metrics_port, metrics, state, Some(sync_orchestrator.clone())).await?;
+    }
+    #[cfg(unix)]
+    spawn_state_dump(engine.state_handle())?;
+
+    if let Some(path) = cli.backtest {
+        let events = load_events(&path)?;
+        let report = engine.backtest(events).await;
+        info!("{}", report.summary());
+        return Ok(());
+    }
+
+    // 4. Catch up on pool states and keep stale pools fresh, then run until shut down
+    if let Err(e) = sync_orchestrator.initialize().await {
+        warn!("Startup sync failed: {}", e);
+    }
+    let stale_sync = sync_orchestrator.spawn_stale_sync();
+    let shutdown = setup_graceful_shutdown(engine, cli.dry_run_for.map(Duration::from_secs)).await;
+    stale_sync.abort();
+    shutdown?;
+
+    // 5. Persist discovered pools
+    if let Some(path) = cli.save_config {
+        let discovered = dex_manager.read().await.export_pools();
+        for (dex_id, pools) in discovered {
+            config.merge_pools(pools, dex_id);
+        }
+        config.save(&path)?;
+    }
+
+    Ok(())
+}
 
-    // 4. Setup graceful shutdown
-    setup_graceful_shutdown(engine).await
+fn config_toml(config: &Config) -> Result<String> {
+    toml::to_string_pretty(config)
+        .map_err(|e| BotError::Config(format!("Failed to serialize config: {}", e)))
 }
 
-/// Handle graceful shutdown
-async fn setup_graceful_shutdown(mut engine: ArbitrageEngine) -> Result<()> {
+/// Print lines of the loaded config that differ from the defaults
+fn print_config_diff(config: &Config) -> Result<()> {
+    let defaults = config_toml(&Config::default())?;
+    let loaded = config_toml(config)?;
+    
+    for line in defaults.lines().filter(|line| !loaded.lines().any(|l| l == *line)) {
+        println!("- {}", line);
+    }
+    for line in loaded.lines().filter(|line| !defaults.lines().any(|d| d == *line)) {
+        println!("+ {}", line);
+    }
+    Ok(())
+}
+
+/// Load recorded events from a JSONL file (one `RawEvent` per line)
+fn load_events(path: &str) -> Result<Vec<RawEvent>> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| BotError::Config(format!("Failed to read backtest file {}: {}", path, e)))?;
+    
+    let events = content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(serde_json::from_str::<RawEvent>)
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    
+    info!("Loaded {} events from {}", events.len(), path);
+    Ok(events)
+}
+
+/// Write the engine state to `STATE_EXPORT_PATH` whenever SIGUSR1 arrives
+#[cfg(unix)]
+fn spawn_state_dump(state: arbitrage_bot::arbitrage::arbitrage_engine::StateHandle) -> Result<()> {
+    let mut signal = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::user_defined1())?;
+    tokio::spawn(async move {
+        while signal.recv().await.is_some() {
+            if let Err(e) = state.write_to(STATE_EXPORT_PATH).await {
+                warn!("Failed to export state: {}", e);
+            }
+        }
+    });
+    Ok(())
+}
+
+/// Handle graceful shutdown, optionally stopping after `run_for`
+async fn setup_graceful_shutdown(mut engine: ArbitrageEngine, run_for: Option<Duration>) -> Result<()> {
     let ctrl_c = async {
         tokio::signal::ctrl_c()
             .await
@@ -71,6 +301,13 @@ async fn setup_graceful_shutdown(mut engine: ArbitrageEngine) -> Result<()> {
     #[cfg(not(unix))]
     let terminate = std::future::pending::<()>();
 
+    let deadline = async {
+        match run_for {
+            Some(duration) => tokio::time::sleep(duration).await,
+            None => std::future::pending::<()>().await,
+        }
+    };
+
     tokio::select! {
         _ = ctrl_c => {
             info!("Received Ctrl-C, shutting down gracefully...");
@@ -78,6 +315,9 @@ async fn setup_graceful_shutdown(mut engine: ArbitrageEngine) -> Result<()> {
         _ = terminate => {
             info!("Received SIGTERM, shutting down gracefully...");
         },
+        _ = deadline => {
+            info!("Dry-run period elapsed, shutting down...");
+        },
         result = engine.start() => {
             if let Err(e) = result {
                 error!("Engine stopped with error: {}", e);
@@ -91,6 +331,10 @@ async fn setup_graceful_shutdown(mut engine: ArbitrageEngine) -> Result<()> {
         return Err(e);
     }
 
+    if run_for.is_some() {
+        info!("Dry-run profit summary:\n{}", engine.profit_summary().summary());
+    }
+
     info!("Arbitrage Bot shutdown complete");
     Ok(())
 }
\ No newline at end of file
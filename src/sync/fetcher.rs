@@ -0,0 +1,112 @@
+//! Batched pool state reads over each network's RPC endpoint
+
+use std::{collections::HashMap, sync::Arc};
+
+use crate::{
+    client::sui_rpc::{SuiObjectDataOptions, SuiRpcClient},
+    sync::parser::PoolParserRegistry,
+    types::{BotError, DexId, Network, PoolState, Result},
+    utils::config::PoolConfig,
+};
+
+/// Fetches pool objects through the RPC client of the pool's network
+#[derive(Default)]
+pub struct PoolStateFetcher {
+    network_to_rpc_client: HashMap<Network, Arc<SuiRpcClient>>,
+    registry: PoolParserRegistry,
+}
+
+impl PoolStateFetcher {
+    pub fn new(network_to_rpc_client: HashMap<Network, Arc<SuiRpcClient>>) -> Self {
+        Self {
+            network_to_rpc_client,
+            registry: PoolParserRegistry::default(),
+        }
+    }
+
+    pub fn with_registry(mut self, registry: PoolParserRegistry) -> Self {
+        self.registry = registry;
+        self
+    }
+
+    pub fn with_network_client(mut self, network: Network, client: Arc<SuiRpcClient>) -> Self {
+        self.network_to_rpc_client.insert(network, client);
+        self
+    }
+
+    pub fn networks(&self) -> Vec<Network> {
+        self.network_to_rpc_client.keys().copied().collect()
+    }
+
+    fn client(&self, network: Network) -> Result<&Arc<SuiRpcClient>> {
+        self.network_to_rpc_client
+            .get(&network)
+            .ok_or_else(|| BotError::Sync(format!("No RPC client for {:?}", network)))
+    }
+
+    /// Newest checkpoint of `network`
+    pub async fn latest_checkpoint(&self, network: Network) -> Result<u64> {
+        self.client(network)?.get_latest_checkpoint().await
+    }
+
+    /// On-chain object version of every pool in `pools`, in order, without
+    /// fetching their content
+    pub async fn fetch_versions(&self, network: Network, pools: &[PoolConfig]) -> Result<Vec<u64>> {
+        let object_ids = pools.iter().map(|pool| pool.address.clone()).collect();
+        let objects = self.client(network)?.batch_get_objects(object_ids, None).await?;
+        Ok(objects.into_iter().map(|object| object.version).collect())
+    }
+
+    /// Fetch every pool in `pools` from `network` in one request, in order
+    pub async fn fetch_batch(&self, network: Network, dex_id: DexId, pools: &[PoolConfig]) -> Result<Vec<PoolState>> {
+        let client = self.client(network)?;
+        let object_ids = pools.iter().map(|pool| pool.address.clone()).collect();
+        let objects = client
+            .batch_get_objects(object_ids, Some(SuiObjectDataOptions::full_content()))
+            .await?;
+
+        pools
+            .iter()
+            .zip(objects)
+            .map(|(pool, object)| self.registry.parse(dex_id, pool, object))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::{cetus_pool_objects, rpc_server, test_token};
+
+    use super::*;
+
+    fn pool(address: &str) -> PoolConfig {
+        PoolConfig {
+            address: address.into(),
+            token_a: test_token("SUI"),
+            token_b: test_token("USDC"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fetches_through_network_client() -> Result<()> {
+        let testnet = Arc::new(SuiRpcClient::new(rpc_server(cetus_pool_objects).await?));
+        let fetcher = PoolStateFetcher::default()
+            .with_network_client(Network::SuiMainnet, Arc::new(SuiRpcClient::new("http://127.0.0.1:9")))
+            .with_network_client(Network::SuiTestnet, testnet);
+
+        let pools = fetcher.fetch_batch(Network::SuiTestnet, DexId::Cetus, &[pool("0xp1"), pool("0xp2")]).await?;
+
+        assert_eq!(pools.iter().map(|pool| pool.pool_id.as_str()).collect::<Vec<_>>(), ["0xp1", "0xp2"]);
+        assert_eq!(pools[0].token_b, test_token("USDC"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_missing_network_client() {
+        let fetcher = PoolStateFetcher::default();
+
+        let result = fetcher.fetch_batch(Network::SuiTestnet, DexId::Cetus, &[pool("0xp1")]).await;
+
+        assert!(matches!(result, Err(BotError::Sync(_))));
+    }
+}
@@ -1,2 +1,5 @@
+pub mod fetcher;
 pub mod heartbeat;
-pub mod periodic;
\ No newline at end of file
+pub mod parser;
+pub mod periodic;
+pub mod rpc;
\ No newline at end of file
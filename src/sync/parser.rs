@@ -0,0 +1,444 @@
+//! Per-DEX conversion of raw pool objects into pool states
+
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use serde_json::Value;
+
+use crate::{
+    client::sui_rpc::SuiObjectData,
+    dex::{aftermath, cetus, kriya},
+    types::{BotError, DexId, PoolState, Result, TokenInfo, normalize_address},
+    utils::config::PoolConfig,
+};
+
+/// Builds a pool state from its object and the tokens configured for it
+pub type PoolParser = fn(&PoolConfig, SuiObjectData) -> Result<PoolState>;
+
+/// Checks whether an object is a pool the parser understands
+pub type PoolMatcher = fn(&SuiObjectData) -> bool;
+
+/// Parse counts since the registry was created
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParserMetrics {
+    pub total_parsed: u64,
+    pub total_failed: u64,
+    pub failures_by_dex: HashMap<DexId, u64>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct RegisteredParser {
+    parse: PoolParser,
+    can_parse: PoolMatcher,
+}
+
+/// Pool object parsers keyed by DEX
+#[derive(Debug, Clone)]
+pub struct PoolParserRegistry {
+    parsers: HashMap<DexId, RegisteredParser>,
+    /// Tokens by normalized coin type, for objects parsed without a pool config
+    tokens: HashMap<String, TokenInfo>,
+    total_parsed: Arc<AtomicU64>,
+    total_failed: Arc<AtomicU64>,
+    failures_by_dex: Arc<HashMap<DexId, AtomicU64>>,
+}
+
+impl Default for PoolParserRegistry {
+    /// Registry with every DEX whose pool layout is known
+    fn default() -> Self {
+        Self::empty()
+            .with_parser(DexId::Cetus, parse_cetus_pool, is_cetus_pool)
+            .with_parser(DexId::Kriya, parse_kriya_pool, is_kriya_pool)
+            .with_parser(DexId::Aftermath, parse_aftermath_pool, is_aftermath_pool)
+    }
+}
+
+impl PoolParserRegistry {
+    pub fn empty() -> Self {
+        Self {
+            parsers: HashMap::new(),
+            tokens: HashMap::new(),
+            total_parsed: Arc::new(AtomicU64::new(0)),
+            total_failed: Arc::new(AtomicU64::new(0)),
+            failures_by_dex: Arc::new(DexId::all().into_iter().map(|dex_id| (dex_id, AtomicU64::new(0))).collect()),
+        }
+    }
+
+    pub fn with_parser(mut self, dex_id: DexId, parser: PoolParser, can_parse: PoolMatcher) -> Self {
+        self.parsers.insert(dex_id, RegisteredParser { parse: parser, can_parse });
+        self
+    }
+
+    /// Register a token so `parse_all_dexes` can resolve it from a pool's coin types
+    pub fn with_token(mut self, token: TokenInfo) -> Self {
+        if let Some(address) = &token.address {
+            self.tokens.insert(normalize_address(address), token);
+        }
+        self
+    }
+
+    pub fn supports(&self, dex_id: DexId) -> bool {
+        self.parsers.contains_key(&dex_id)
+    }
+
+    pub fn parse(&self, dex_id: DexId, pool: &PoolConfig, object: SuiObjectData) -> Result<PoolState> {
+        let parser = self.parsers
+            .get(&dex_id)
+            .ok_or_else(|| BotError::dex(dex_id, "Pool parsing is not supported"))?;
+        self.record(dex_id, (parser.parse)(pool, object))
+    }
+
+    /// Parse an object without knowing its DEX, asking every registered
+    /// parser whether it claims the object. Exactly one must.
+    pub fn parse_all_dexes(&self, sui_object: &SuiObjectData) -> Result<PoolState> {
+        let mut claimants: Vec<DexId> = self.parsers
+            .iter()
+            .filter(|(_, parser)| (parser.can_parse)(sui_object))
+            .map(|(dex_id, _)| *dex_id)
+            .collect();
+        claimants.sort_by_key(|dex_id| dex_id.name());
+
+        let dex_id = match claimants[..] {
+            [dex_id] => dex_id,
+            [] => {
+                return Err(BotError::Parse(format!("No parser found for object type {:?}", sui_object.object_type)));
+            }
+            _ => return Err(BotError::Parse(format!("Ambiguous: parsers {:?} all claim object", claimants))),
+        };
+
+        let pool = match self.pool_config(sui_object) {
+            Ok(pool) => pool,
+            Err(e) => return self.record(dex_id, Err(e)),
+        };
+        self.parse(dex_id, &pool, sui_object.clone())
+    }
+
+    pub fn get_metrics(&self) -> ParserMetrics {
+        ParserMetrics {
+            total_parsed: self.total_parsed.load(Ordering::Relaxed),
+            total_failed: self.total_failed.load(Ordering::Relaxed),
+            failures_by_dex: self.failures_by_dex
+                .iter()
+                .map(|(dex_id, count)| (*dex_id, count.load(Ordering::Relaxed)))
+                .filter(|(_, count)| *count > 0)
+                .collect(),
+        }
+    }
+
+    fn record(&self, dex_id: DexId, result: Result<PoolState>) -> Result<PoolState> {
+        match &result {
+            Ok(_) => {
+                self.total_parsed.fetch_add(1, Ordering::Relaxed);
+            }
+            Err(_) => {
+                self.total_failed.fetch_add(1, Ordering::Relaxed);
+                if let Some(count) = self.failures_by_dex.get(&dex_id) {
+                    count.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        }
+        result
+    }
+
+    /// Pool config built from the object's coin types and the registered tokens
+    fn pool_config(&self, sui_object: &SuiObjectData) -> Result<PoolConfig> {
+        let coin_types = coin_types(sui_object);
+        let [coin_a, coin_b] = &coin_types[..] else {
+            return Err(BotError::Parse(format!(
+                "Pool {} has {} coin types, expected two", sui_object.object_id, coin_types.len()
+            )));
+        };
+        let token = |coin_type: &str| {
+            self.tokens
+                .get(&normalize_address(coin_type))
+                .cloned()
+                .ok_or_else(|| BotError::Parse(format!("Unknown token {} in pool {}", coin_type, sui_object.object_id)))
+        };
+
+        Ok(PoolConfig {
+            address: sui_object.object_id.clone(),
+            token_a: token(coin_a)?,
+            token_b: token(coin_b)?,
+        })
+    }
+}
+
+/// Coin types of a pool: the type arguments of `Pool<A, B>`, or the
+/// `type_names` field for pools whose type argument is an LP coin
+fn coin_types(sui_object: &SuiObjectData) -> Vec<String> {
+    let type_arguments = sui_object.object_type
+        .as_deref()
+        .and_then(|object_type| object_type.split_once('<'))
+        .and_then(|(_, arguments)| arguments.strip_suffix('>'))
+        .map(split_type_arguments)
+        .unwrap_or_default();
+    if type_arguments.len() > 1 {
+        return type_arguments;
+    }
+
+    sui_object.fields
+        .get("type_names")
+        .and_then(Value::as_array)
+        .map(|names| names.iter().filter_map(Value::as_str).map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+/// Split top-level type arguments, leaving nested generics intact
+fn split_type_arguments(arguments: &str) -> Vec<String> {
+    let mut split = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (i, c) in arguments.char_indices() {
+        match c {
+            '<' => depth += 1,
+            '>' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                split.push(arguments[start..i].trim().to_string());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    split.push(arguments[start..].trim().to_string());
+    split.retain(|argument| !argument.is_empty());
+    split
+}
+
+fn is_cetus_pool(object: &SuiObjectData) -> bool {
+    object.object_type.as_deref().is_some_and(|object_type| cetus::pools::is_pool_object(object_type, &object.fields))
+}
+
+fn is_kriya_pool(object: &SuiObjectData) -> bool {
+    object.object_type.as_deref().is_some_and(kriya::pools::is_pool_type)
+}
+
+fn is_aftermath_pool(object: &SuiObjectData) -> bool {
+    object.object_type.as_deref().is_some_and(|object_type| aftermath::pools::is_pool_object(object_type, &object.fields))
+}
+
+fn parse_cetus_pool(pool: &PoolConfig, object: SuiObjectData) -> Result<PoolState> {
+    cetus::pools::parse_pool(object.object_id, pool.token_a.clone(), pool.token_b.clone(), &object.fields, object.version)
+}
+
+fn parse_kriya_pool(pool: &PoolConfig, object: SuiObjectData) -> Result<PoolState> {
+    kriya::pools::parse_pool(object.object_id, pool.token_a.clone(), pool.token_b.clone(), &object.fields, object.version)
+}
+
+fn parse_aftermath_pool(pool: &PoolConfig, object: SuiObjectData) -> Result<PoolState> {
+    aftermath::pools::parse_pool(object.object_id, pool.token_a.clone(), pool.token_b.clone(), &object.fields, object.version)
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use rust_decimal::Decimal;
+    use serde_json::json;
+
+    use crate::testing::test_token;
+
+    use super::*;
+
+    fn pool() -> PoolConfig {
+        PoolConfig {
+            address: "0xp1".into(),
+            token_a: test_token("SUI"),
+            token_b: test_token("USDC"),
+        }
+    }
+
+    fn object(fields: serde_json::Value) -> SuiObjectData {
+        SuiObjectData {
+            object_id: "0xp1".into(),
+            version: 3,
+            object_type: None,
+            fields,
+        }
+    }
+
+    #[test]
+    fn test_default_registry_parses_kriya_pools() -> Result<()> {
+        let registry = PoolParserRegistry::default();
+        let fields = json!({
+            "reserve_x": "4000",
+            "reserve_y": "9000",
+            "lp_fee_percent": "25",
+            "protocol_fee_percent": "5",
+        });
+
+        let state = registry.parse(DexId::Kriya, &pool(), object(fields))?;
+
+        assert!(registry.supports(DexId::Cetus));
+        assert!(registry.supports(DexId::Aftermath));
+        assert_eq!(state.dex_id, DexId::Kriya);
+        assert_eq!(state.reserve_b, Decimal::from(9_000));
+        assert_eq!(state.version, 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_unregistered_dex_is_rejected() {
+        let result = PoolParserRegistry::default().parse(DexId::Turbos, &pool(), object(json!({})));
+
+        assert!(result.is_err());
+    }
+
+    fn typed_object(object_type: &str, fields: serde_json::Value) -> SuiObjectData {
+        SuiObjectData {
+            object_type: Some(object_type.into()),
+            ..object(fields)
+        }
+    }
+
+    fn registry_with_tokens() -> PoolParserRegistry {
+        PoolParserRegistry::default()
+            .with_token(TokenInfo::new("SUI", "0x2::sui::SUI", 9))
+            .with_token(TokenInfo::new("USDC", "0xdba3::usdc::USDC", 6))
+    }
+
+    #[test]
+    fn test_parse_all_dexes_finds_the_owning_parser() -> Result<()> {
+        let registry = registry_with_tokens();
+        let kriya = typed_object(
+            "0xa0eba1::amm_v2::Pool<0x2::sui::SUI, 0xdba3::usdc::USDC>",
+            json!({ "reserve_x": "4000", "reserve_y": "9000", "lp_fee_percent": "25", "protocol_fee_percent": "5" }),
+        );
+        let cetus = typed_object(
+            "0x1eabed::pool::Pool<0x2::sui::SUI, 0xdba3::usdc::USDC>",
+            json!({ "coin_a": "100", "coin_b": "200", "fee_rate": "2500", "current_sqrt_price": "18446744073709551616" }),
+        );
+
+        let kriya = registry.parse_all_dexes(&kriya)?;
+        let cetus = registry.parse_all_dexes(&cetus)?;
+
+        assert_eq!(kriya.dex_id, DexId::Kriya);
+        assert_eq!(kriya.token_b.symbol, "USDC");
+        assert_eq!(cetus.dex_id, DexId::Cetus);
+        assert_eq!(cetus.reserve_a, Decimal::from(100));
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_all_dexes_without_claimant() {
+        let result = registry_with_tokens().parse_all_dexes(&typed_object("0x2::coin::Coin<0x2::sui::SUI>", json!({})));
+
+        assert!(matches!(result, Err(BotError::Parse(message)) if message.starts_with("No parser found")));
+    }
+
+    #[test]
+    fn test_parse_all_dexes_rejects_ambiguous_objects() {
+        let registry = registry_with_tokens().with_parser(DexId::Turbos, parse_kriya_pool, is_kriya_pool);
+        let kriya = typed_object(
+            "0xa0eba1::amm_v2::Pool<0x2::sui::SUI, 0xdba3::usdc::USDC>",
+            json!({ "reserve_x": "4000", "reserve_y": "9000", "lp_fee_percent": "25", "protocol_fee_percent": "5" }),
+        );
+
+        let result = registry.parse_all_dexes(&kriya);
+
+        assert!(matches!(result, Err(BotError::Parse(message)) if message.starts_with("Ambiguous: parsers")));
+    }
+
+    #[test]
+    fn test_metrics_count_parses_and_failures() -> Result<()> {
+        let registry = PoolParserRegistry::default();
+        let fields = json!({ "reserve_x": "4000", "reserve_y": "9000", "lp_fee_percent": "25", "protocol_fee_percent": "5" });
+
+        registry.parse(DexId::Kriya, &pool(), object(fields))?;
+        assert!(registry.parse(DexId::Cetus, &pool(), object(json!({}))).is_err());
+        assert!(registry.clone().parse(DexId::Cetus, &pool(), object(json!({}))).is_err());
+
+        let metrics = registry.get_metrics();
+        assert_eq!(metrics.total_parsed, 1);
+        assert_eq!(metrics.total_failed, 2);
+        assert_eq!(metrics.failures_by_dex, HashMap::from([(DexId::Cetus, 2)]));
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_type_arguments_keeps_nested_generics() {
+        assert_eq!(
+            split_type_arguments("0x2::sui::SUI, 0xa::lp::LP<0x2::sui::SUI, 0xb::c::C>"),
+            vec!["0x2::sui::SUI".to_string(), "0xa::lp::LP<0x2::sui::SUI, 0xb::c::C>".to_string()],
+        );
+    }
+
+    /// Fields any of the parsers reads
+    const POOL_FIELDS: [&str; 14] = [
+        "coin_a", "coin_b", "fee_rate", "current_sqrt_price", "current_tick_index", "is_pause",
+        "reserve_x", "reserve_y", "lp_fee_percent", "protocol_fee_percent",
+        "normalized_balances", "fees_swap_in", "amp_factor", "type_names",
+    ];
+
+    const PARSED_DEXES: [DexId; 3] = [DexId::Cetus, DexId::Kriya, DexId::Aftermath];
+
+    fn json_value() -> impl Strategy<Value = serde_json::Value> {
+        let leaf = prop_oneof![
+            Just(serde_json::Value::Null),
+            any::<bool>().prop_map(serde_json::Value::from),
+            any::<i64>().prop_map(serde_json::Value::from),
+            any::<f64>().prop_map(serde_json::Value::from),
+            "-?[0-9]{0,45}".prop_map(serde_json::Value::from),
+            ".*".prop_map(serde_json::Value::from),
+        ];
+        leaf.prop_recursive(3, 16, 4, |inner| {
+            prop_oneof![
+                proptest::collection::vec(inner.clone(), 0..4).prop_map(serde_json::Value::from),
+                proptest::collection::vec(("[a-z_]{1,8}", inner), 0..4)
+                    .prop_map(|fields| serde_json::Value::Object(fields.into_iter().collect())),
+            ]
+        })
+    }
+
+    /// Objects mixing the fields of every supported pool layout
+    fn pool_object() -> impl Strategy<Value = SuiObjectData> {
+        let object_type = prop_oneof![
+            Just(None),
+            proptest::sample::select(vec![
+                "0x1eabed::pool::Pool<0x2::sui::SUI, 0xdba3::usdc::USDC>",
+                "0xa0eba1::amm_v2::Pool<0x2::sui::SUI, 0xdba3::usdc::USDC>",
+                "0xefe8b3::pool::Pool<0xaf::af_lp::AF_LP>",
+                "0x2::coin::Coin<0x2::sui::SUI>",
+            ])
+            .prop_map(|object_type| Some(object_type.to_string())),
+            ".*".prop_map(Some),
+        ];
+        let fields = proptest::collection::vec((proptest::sample::select(POOL_FIELDS.to_vec()), json_value()), 0..POOL_FIELDS.len())
+            .prop_map(|fields| serde_json::Value::Object(fields.into_iter().map(|(name, value)| (name.to_string(), value)).collect()));
+        (object_type, fields).prop_map(|(object_type, fields)| SuiObjectData { object_type, ..object(fields) })
+    }
+
+    proptest! {
+        #[test]
+        fn prop_parsers_never_panic(object in pool_object()) {
+            let registry = registry_with_tokens();
+            for dex_id in PARSED_DEXES {
+                let _ = registry.parse(dex_id, &pool(), object.clone());
+            }
+            let _ = registry.parse_all_dexes(&object);
+        }
+
+        #[test]
+        fn prop_arbitrary_bytes_never_panic(bytes in proptest::collection::vec(any::<u8>(), 0..256)) {
+            if let Ok(fields) = serde_json::from_slice::<serde_json::Value>(&bytes) {
+                let registry = registry_with_tokens();
+                for dex_id in PARSED_DEXES {
+                    let _ = registry.parse(dex_id, &pool(), object(fields.clone()));
+                }
+            }
+        }
+
+        #[test]
+        fn prop_only_the_claiming_parser_parses(object in pool_object()) {
+            let registry = registry_with_tokens();
+            if let Ok(state) = registry.parse_all_dexes(&object) {
+                for (dex_id, parser) in &registry.parsers {
+                    prop_assert_eq!((parser.can_parse)(&object), *dex_id == state.dex_id);
+                }
+            }
+        }
+    }
+}
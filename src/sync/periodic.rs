@@ -0,0 +1,973 @@
+//! Refreshing tracked pool states from chain
+
+use std::{
+    collections::{HashMap, VecDeque},
+    path::PathBuf,
+    sync::{Arc, Mutex, Weak, atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering}},
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use tokio::{sync::{Notify, RwLock}, task::JoinHandle};
+use tracing::{debug, info, warn};
+
+use crate::{
+    client::sui_rpc::SuiRpcClient,
+    dex::manager::{DexManager, EMERGENCY_SYNC_FAILURES},
+    sync::fetcher::PoolStateFetcher,
+    types::{BotError, DexId, HealthStatus, Network, PoolId, PoolState, Result, Timestamp, now},
+    utils::{alerting::{AlertSeverity, Alerter}, config::{PoolConfig, SyncConfig}, event_rate::EventRateCounter},
+};
+
+/// Number of finished sync passes kept for `get_sync_history`
+pub const SYNC_HISTORY_LIMIT: usize = 100;
+
+/// File the last full sync is recorded in, for `SyncOrchestrator::initialize`
+pub const SYNC_STATE_FILE: &str = "sync_state.json";
+
+/// Age up to which a recorded full sync lets startup sync only changed pools
+pub const SYNC_STATE_MAX_AGE_MS: u64 = 60 * 60 * 1000;
+
+/// Events per minute above which adaptive sync uses the emergency interval
+pub const BUSY_EVENTS_PER_MINUTE: u64 = 1000;
+
+/// Events per minute below which adaptive sync stretches the interval
+pub const QUIET_EVENTS_PER_MINUTE: u64 = 10;
+
+/// Factor the stale sync interval is stretched by while events are quiet
+const QUIET_INTERVAL_FACTOR: u64 = 3;
+
+/// Pools a sync pass refreshes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SyncScope {
+    All,
+    /// Pools not updated within the state TTL
+    Stale,
+    Specific(Vec<PoolId>),
+    /// Pools whose on-chain version moved past the tracked one, after a
+    /// full sync at `since_checkpoint`
+    Changed { since_checkpoint: u64 },
+}
+
+impl SyncScope {
+    /// Name recorded as the `sync_type` of a `SyncEvent`
+    pub fn name(&self) -> &'static str {
+        match self {
+            SyncScope::All => "all",
+            SyncScope::Stale => "stale",
+            SyncScope::Specific(_) => "specific",
+            SyncScope::Changed { .. } => "changed",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SyncStats {
+    /// Pool fetches repeated after a failure
+    pub sync_retry_count: u64,
+}
+
+/// A finished sync pass
+#[derive(Debug, Clone, Serialize)]
+pub struct SyncEvent {
+    pub started_at: Timestamp,
+    pub completed_at: Timestamp,
+    /// `SyncScope::name` of the pass
+    pub sync_type: String,
+    pub pools_updated: usize,
+    /// Pools of DEXs whose fetch failed every retry
+    pub pools_failed: usize,
+    pub error: Option<String>,
+}
+
+/// The last successful full sync, persisted so a restart can skip the next one
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncState {
+    pub checkpoint: u64,
+    pub synced_at: Timestamp,
+    pub pool_count: usize,
+}
+
+/// Fetches tracked pools grouped by network and DEX and merges the results
+/// into the `DexManager`. Settings sit behind atomics so a reloaded config
+/// applies while syncs run.
+pub struct SyncOrchestrator {
+    dex_manager: Arc<RwLock<DexManager>>,
+    fetcher: PoolStateFetcher,
+    state_ttl_ms: AtomicU64,
+    max_retries: AtomicU32,
+    retry_delay_ms: AtomicU64,
+    stale_sync_interval_ms: AtomicU64,
+    emergency_sync_interval_ms: AtomicU64,
+    adaptive_sync: AtomicBool,
+    /// Interval of the next stale sync wait, adjusted to the event rate
+    /// after each pass when adaptive sync is enabled
+    current_interval_ms: AtomicU64,
+    event_rate: Option<Arc<EventRateCounter>>,
+    /// Wakes the stale sync task to pick up a new interval
+    interval_changed: Notify,
+    sync_retry_count: AtomicU64,
+    sync_history: Mutex<VecDeque<SyncEvent>>,
+    alerter: Alerter,
+    /// Deferred syncs from `schedule_sync_after`, aborted on drop
+    scheduled_syncs: Mutex<Vec<JoinHandle<()>>>,
+    /// Where full syncs are recorded, not persisted when unset
+    sync_state_path: Option<PathBuf>,
+}
+
+impl SyncOrchestrator {
+    pub fn new(dex_manager: Arc<RwLock<DexManager>>, fetcher: PoolStateFetcher, config: &SyncConfig) -> Self {
+        let orchestrator = Self {
+            dex_manager,
+            fetcher,
+            state_ttl_ms: AtomicU64::new(0),
+            max_retries: AtomicU32::new(0),
+            retry_delay_ms: AtomicU64::new(0),
+            stale_sync_interval_ms: AtomicU64::new(0),
+            emergency_sync_interval_ms: AtomicU64::new(0),
+            adaptive_sync: AtomicBool::new(false),
+            current_interval_ms: AtomicU64::new(0),
+            event_rate: None,
+            interval_changed: Notify::new(),
+            sync_retry_count: AtomicU64::new(0),
+            sync_history: Mutex::new(VecDeque::with_capacity(SYNC_HISTORY_LIMIT)),
+            alerter: Alerter::new(),
+            scheduled_syncs: Mutex::new(Vec::new()),
+            sync_state_path: None,
+        };
+        orchestrator.apply_config(config);
+        orchestrator
+    }
+
+    /// Alert when a DEX starts failing heartbeats and again when it needs
+    /// an emergency sync
+    pub fn with_alerter(mut self, alerter: Alerter) -> Self {
+        self.alerter = alerter;
+        self
+    }
+
+    /// Record each successful full sync in `path`, see `initialize`
+    pub fn with_sync_state_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.sync_state_path = Some(path.into());
+        self
+    }
+
+    /// Event rate that adaptive sync bases the stale sync interval on
+    pub fn with_event_rate(mut self, event_rate: Arc<EventRateCounter>) -> Self {
+        self.event_rate = Some(event_rate);
+        self
+    }
+
+    /// Take over TTL, retry and stale sync settings from a reloaded config
+    pub fn apply_config(&self, config: &SyncConfig) {
+        self.state_ttl_ms.store(config.state_ttl_seconds * 1000, Ordering::Relaxed);
+        self.max_retries.store(config.max_retries, Ordering::Relaxed);
+        self.retry_delay_ms.store(config.retry_delay_ms, Ordering::Relaxed);
+        self.emergency_sync_interval_ms.store(config.emergency_sync_interval_seconds * 1000, Ordering::Relaxed);
+        self.adaptive_sync.store(config.enable_adaptive_sync, Ordering::Relaxed);
+        let interval_ms = config.stale_sync_interval_seconds * 1000;
+        self.stale_sync_interval_ms.store(interval_ms, Ordering::Relaxed);
+        if self.current_interval_ms.swap(interval_ms, Ordering::Relaxed) != interval_ms {
+            self.interval_changed.notify_one();
+        }
+    }
+
+    /// Stale sync interval for the current event rate: the emergency interval
+    /// while busy, three times the configured one while quiet
+    fn adapt_interval(&self) {
+        let Some(event_rate) = self.event_rate.as_ref().filter(|_| self.adaptive_sync.load(Ordering::Relaxed)) else {
+            return;
+        };
+        let events_per_minute = event_rate.events_per_minute(now());
+        let base_ms = self.stale_sync_interval_ms.load(Ordering::Relaxed);
+        let interval_ms = if events_per_minute > BUSY_EVENTS_PER_MINUTE {
+            self.emergency_sync_interval_ms.load(Ordering::Relaxed)
+        } else if events_per_minute < QUIET_EVENTS_PER_MINUTE {
+            base_ms * QUIET_INTERVAL_FACTOR
+        } else {
+            base_ms
+        };
+        if self.current_interval_ms.swap(interval_ms, Ordering::Relaxed) != interval_ms {
+            info!("{} events/min, stale sync interval now {:?}", events_per_minute, Duration::from_millis(interval_ms));
+        }
+    }
+
+    /// Alert on the first failed heartbeat of a DEX and when its failures
+    /// reach the emergency sync threshold, rather than on every pass
+    async fn alert_unhealthy(&self, dex_id: DexId, status: &HealthStatus) {
+        let severity = match status.consecutive_failures {
+            _ if status.is_healthy => return,
+            1 => AlertSeverity::Warning,
+            EMERGENCY_SYNC_FAILURES => AlertSeverity::Critical,
+            _ => return,
+        };
+        let message = format!("{}: {} ({} failures in a row)", dex_id, status.message, status.consecutive_failures);
+        self.alerter.alert("DEX heartbeat failed", &message, severity).await;
+    }
+
+    /// Time between stale syncs, at least one second
+    pub fn stale_sync_interval(&self) -> Duration {
+        Duration::from_millis(self.current_interval_ms.load(Ordering::Relaxed)).max(Duration::from_secs(1))
+    }
+
+    /// Run `periodic_pass` every `stale_sync_interval` until aborted. A new
+    /// interval restarts the wait.
+    pub fn spawn_stale_sync(self: Arc<Self>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                let interval = self.stale_sync_interval();
+                tokio::select! {
+                    _ = tokio::time::sleep(interval) => self.periodic_pass().await,
+                    _ = self.interval_changed.notified() => {
+                        info!("Stale sync interval changed to {:?}", self.stale_sync_interval());
+                    }
+                }
+            }
+        })
+    }
+
+    /// Heartbeat every DEX, then refresh stale pools, or all pools when a
+    /// DEX has failed enough heartbeats to need an emergency sync.
+    /// Adjusts the interval to the event rate afterwards.
+    async fn periodic_pass(&self) {
+        let (statuses, needs_emergency_sync) = {
+            let mut manager = self.dex_manager.write().await;
+            let statuses = manager.heartbeat_all().await.unwrap_or_else(|e| {
+                warn!("DEX heartbeats failed: {}", e);
+                Default::default()
+            });
+            (statuses, manager.needs_emergency_sync())
+        };
+        for (dex_id, status) in &statuses {
+            self.alert_unhealthy(*dex_id, status).await;
+        }
+        let scope = if needs_emergency_sync {
+            warn!("Running emergency full sync");
+            SyncScope::All
+        } else {
+            SyncScope::Stale
+        };
+        match self.sync_pools(scope).await {
+            Ok(synced) => debug!("Periodic sync refreshed {} pools", synced),
+            Err(e) => warn!("Periodic sync failed: {}", e),
+        }
+        self.adapt_interval();
+    }
+
+    pub fn stats(&self) -> SyncStats {
+        SyncStats {
+            sync_retry_count: self.sync_retry_count.load(Ordering::Relaxed),
+        }
+    }
+
+    /// The most recent sync passes, oldest first
+    pub fn get_sync_history(&self) -> Vec<SyncEvent> {
+        self.sync_history.lock().unwrap_or_else(|e| e.into_inner()).iter().cloned().collect()
+    }
+
+    fn record_sync(&self, event: SyncEvent) {
+        let mut history = self.sync_history.lock().unwrap_or_else(|e| e.into_inner());
+        if history.len() == SYNC_HISTORY_LIMIT {
+            history.pop_front();
+        }
+        history.push_back(event);
+    }
+
+    /// Sync at startup: only changed pools when the recorded full sync is
+    /// younger than an hour, every pool otherwise
+    pub async fn initialize(&self) -> Result<usize> {
+        match self.load_sync_state() {
+            Some(state) if now().saturating_sub(state.synced_at) < SYNC_STATE_MAX_AGE_MS => {
+                info!("Full sync at checkpoint {} is recent, syncing changed pools only", state.checkpoint);
+                self.sync_pools(SyncScope::Changed { since_checkpoint: state.checkpoint }).await
+            }
+            _ => self.sync_pools(SyncScope::All).await,
+        }
+    }
+
+    /// The recorded full sync, if there is a readable one
+    pub fn load_sync_state(&self) -> Option<SyncState> {
+        let path = self.sync_state_path.as_ref()?;
+        let content = std::fs::read_to_string(path).ok()?;
+        serde_json::from_str(&content)
+            .inspect_err(|e| warn!("Ignoring unreadable sync state {}: {}", path.display(), e))
+            .ok()
+    }
+
+    /// Forget the recorded full sync, so the next startup syncs every pool
+    pub fn clear_sync_state(&self) -> Result<()> {
+        let Some(path) = &self.sync_state_path else {
+            return Ok(());
+        };
+        match std::fs::remove_file(path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Record a successful full sync of `pool_count` pools at the current
+    /// checkpoint, replacing the file atomically
+    async fn save_sync_state(&self, pool_count: usize) -> Result<()> {
+        let Some(path) = &self.sync_state_path else {
+            return Ok(());
+        };
+        let mut networks = self.fetcher.networks();
+        networks.sort_by_key(|network| network.chain_id());
+        let network = networks
+            .first()
+            .copied()
+            .ok_or_else(|| BotError::Sync("No network to read a checkpoint from".into()))?;
+        let state = SyncState {
+            checkpoint: self.fetcher.latest_checkpoint(network).await?,
+            synced_at: now(),
+            pool_count,
+        };
+
+        let mut tmp_path = path.clone().into_os_string();
+        tmp_path.push(".tmp");
+        std::fs::write(&tmp_path, serde_json::to_string_pretty(&state)?)?;
+        std::fs::rename(&tmp_path, path)?;
+        Ok(())
+    }
+
+    /// Keep only pools whose on-chain version differs from the tracked one.
+    /// A group whose versions cannot be read is kept whole.
+    async fn changed_pools(
+        &self,
+        groups: HashMap<(Network, DexId), Vec<PoolConfig>>,
+    ) -> HashMap<(Network, DexId), Vec<PoolConfig>> {
+        let mut changed = HashMap::new();
+        for ((network, dex_id), pools) in groups {
+            let versions = match self.fetcher.fetch_versions(network, &pools).await {
+                Ok(versions) => versions,
+                Err(e) => {
+                    warn!("Reading {} pool versions failed, syncing all of them: {}", dex_id, e);
+                    changed.insert((network, dex_id), pools);
+                    continue;
+                }
+            };
+            let manager = self.dex_manager.read().await;
+            let pools: Vec<PoolConfig> = pools
+                .into_iter()
+                .zip(versions)
+                .filter(|(pool, version)| {
+                    manager.get_pool(&dex_id, &pool.address).is_none_or(|tracked| tracked.version != *version)
+                })
+                .map(|(pool, _)| pool)
+                .collect();
+            if !pools.is_empty() {
+                changed.insert((network, dex_id), pools);
+            }
+        }
+        changed
+    }
+
+    /// Refresh the pools in `scope`, returning how many states were updated.
+    /// A stale pass that reached every DEX evicts pools it still could not
+    /// refresh, so unfetchable pools are not retried forever. A successful
+    /// full pass is recorded for the next startup.
+    pub async fn sync_pools(&self, scope: SyncScope) -> Result<usize> {
+        let state_ttl_ms = self.state_ttl_ms.load(Ordering::Relaxed);
+        let mut groups = {
+            let manager = self.dex_manager.read().await;
+            let now = now();
+            manager.group_pools_by_network_and_dex(|pool| match &scope {
+                SyncScope::All | SyncScope::Changed { .. } => true,
+                SyncScope::Stale => now.saturating_sub(pool.block_timestamp) > state_ttl_ms,
+                SyncScope::Specific(pool_ids) => pool_ids.contains(&pool.pool_id),
+            })
+        };
+        if matches!(scope, SyncScope::Changed { .. }) {
+            groups = self.changed_pools(groups).await;
+        }
+        let pool_count = groups.values().map(Vec::len).sum();
+        let synced = self.sync_pools_grouped(scope.name(), groups).await?;
+        
+        if scope == SyncScope::All && let Err(e) = self.save_sync_state(pool_count).await {
+            warn!("Failed to record sync state: {}", e);
+        }
+        if scope == SyncScope::Stale {
+            let evicted = self.dex_manager.write().await.remove_stale_pools();
+            if !evicted.is_empty() {
+                warn!("Evicted {} pools that could not be refreshed", evicted.len());
+            }
+        }
+        Ok(synced)
+    }
+
+    /// Run a sync of `scope` after `delay` without waiting for it. The task
+    /// holds no strong reference, so it ends if the orchestrator is dropped.
+    pub fn schedule_sync_after(self: &Arc<Self>, delay: Duration, scope: SyncScope) {
+        let orchestrator = Arc::downgrade(self);
+        let task = tokio::spawn(async move {
+            tokio::time::sleep(delay).await;
+            let Some(orchestrator) = Weak::upgrade(&orchestrator) else {
+                return;
+            };
+            if let Err(e) = orchestrator.sync_pools(scope).await {
+                warn!("Scheduled sync failed: {}", e);
+            }
+        });
+        let mut scheduled = self.scheduled_syncs.lock().unwrap_or_else(|e| e.into_inner());
+        scheduled.retain(|task| !task.is_finished());
+        scheduled.push(task);
+    }
+
+    /// Scheduled syncs that have not finished yet
+    pub fn pending_scheduled_syncs(&self) -> usize {
+        self.scheduled_syncs
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .filter(|task| !task.is_finished())
+            .count()
+    }
+
+    /// Abort every scheduled sync that has not run yet
+    pub fn cancel_scheduled_syncs(&self) {
+        for task in self.scheduled_syncs.lock().unwrap_or_else(|e| e.into_inner()).drain(..) {
+            task.abort();
+        }
+    }
+
+    /// Refresh only `pool_ids`, eg the pool a large swap just went through
+    pub async fn sync_specific_pools(&self, pool_ids: Vec<PoolId>) -> Result<usize> {
+        self.sync_pools(SyncScope::Specific(pool_ids)).await
+    }
+
+    /// Fetch every group, retrying failed DEXs with exponential backoff,
+    /// and record the pass in the sync history. States that were fetched
+    /// are merged even when some DEX failed.
+    async fn sync_pools_grouped(
+        &self,
+        sync_type: &str,
+        groups: impl IntoIterator<Item = ((Network, DexId), Vec<PoolConfig>)>,
+    ) -> Result<usize> {
+        let started_at = now();
+        let mut states = Vec::new();
+        let mut failed_dexes = Vec::new();
+        let mut pools_failed = 0;
+        for ((network, dex_id), pools) in groups {
+            match self.fetch_with_retry(network, dex_id, &pools).await {
+                Ok(fetched) => states.extend(fetched),
+                Err(e) => {
+                    warn!("Sync of {} pools on {:?} failed: {}", dex_id, network, e);
+                    failed_dexes.push(dex_id);
+                    pools_failed += pools.len();
+                }
+            }
+        }
+
+        let merged = self.dex_manager.write().await.merge_pool_states(states);
+        debug!(
+            "Synced {} pools ({} stale, {} invalid)",
+            merged.accepted, merged.rejected_stale, merged.rejected_invalid
+        );
+        let error = (!failed_dexes.is_empty()).then(|| format!("Failed DEXes: {:?}", failed_dexes));
+        self.record_sync(SyncEvent {
+            started_at,
+            completed_at: now(),
+            sync_type: sync_type.to_string(),
+            pools_updated: merged.accepted,
+            pools_failed,
+            error: error.clone(),
+        });
+        match error {
+            Some(error) => Err(BotError::Sync(error)),
+            None => Ok(merged.accepted),
+        }
+    }
+
+    async fn fetch_with_retry(&self, network: Network, dex_id: DexId, pools: &[PoolConfig]) -> Result<Vec<PoolState>> {
+        let max_retries = self.max_retries.load(Ordering::Relaxed);
+        let retry_delay = Duration::from_millis(self.retry_delay_ms.load(Ordering::Relaxed));
+        let mut attempt = 0;
+        loop {
+            match self.fetcher.fetch_batch(network, dex_id, pools).await {
+                Ok(states) => return Ok(states),
+                Err(e) if attempt < max_retries => {
+                    let delay = retry_delay * 2u32.saturating_pow(attempt);
+                    debug!("Fetching {} pools failed ({}), retrying in {:?}", dex_id, e, delay);
+                    tokio::time::sleep(delay).await;
+                    self.sync_retry_count.fetch_add(1, Ordering::Relaxed);
+                    attempt += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+/// Builds a `SyncOrchestrator` after checking that every network's RPC
+/// client reaches a node, so a bad endpoint fails startup
+pub struct SyncOrchestratorBuilder {
+    dex_manager: Arc<RwLock<DexManager>>,
+    config: SyncConfig,
+    fetcher: PoolStateFetcher,
+    network_clients: Vec<Arc<SuiRpcClient>>,
+    alerter: Option<Alerter>,
+    event_rate: Option<Arc<EventRateCounter>>,
+    sync_state_path: Option<PathBuf>,
+    health_check: bool,
+}
+
+impl SyncOrchestratorBuilder {
+    pub fn new(dex_manager: Arc<RwLock<DexManager>>, config: &SyncConfig) -> Self {
+        Self {
+            dex_manager,
+            config: config.clone(),
+            fetcher: PoolStateFetcher::default(),
+            network_clients: Vec::new(),
+            alerter: None,
+            event_rate: None,
+            sync_state_path: None,
+            health_check: true,
+        }
+    }
+
+    /// Fetch pools of `network` through `client`, whose fallback URLs are
+    /// tried when its primary endpoint is down
+    pub fn with_network_client(mut self, network: Network, client: Arc<SuiRpcClient>) -> Self {
+        self.fetcher = self.fetcher.with_network_client(network, client.clone());
+        self.network_clients.push(client);
+        self
+    }
+
+    pub fn with_alerter(mut self, alerter: Alerter) -> Self {
+        self.alerter = Some(alerter);
+        self
+    }
+
+    pub fn with_event_rate(mut self, event_rate: Arc<EventRateCounter>) -> Self {
+        self.event_rate = Some(event_rate);
+        self
+    }
+
+    pub fn with_sync_state_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.sync_state_path = Some(path.into());
+        self
+    }
+
+    /// Skip the node health check, eg when replaying events offline
+    pub fn with_health_check(mut self, enabled: bool) -> Self {
+        self.health_check = enabled;
+        self
+    }
+
+    /// Fails with `BotError::Config` when no endpoint of a network client answers
+    pub async fn build(self) -> Result<SyncOrchestrator> {
+        if self.health_check {
+            for client in &self.network_clients {
+                client.check_node_health().await?;
+            }
+        }
+
+        let mut orchestrator = SyncOrchestrator::new(self.dex_manager, self.fetcher, &self.config);
+        if let Some(alerter) = self.alerter {
+            orchestrator = orchestrator.with_alerter(alerter);
+        }
+        if let Some(event_rate) = self.event_rate {
+            orchestrator = orchestrator.with_event_rate(event_rate);
+        }
+        if let Some(path) = self.sync_state_path {
+            orchestrator = orchestrator.with_sync_state_path(path);
+        }
+        Ok(orchestrator)
+    }
+}
+
+impl Drop for SyncOrchestrator {
+    fn drop(&mut self) {
+        self.cancel_scheduled_syncs();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+
+    use rust_decimal::Decimal;
+    use serde_json::{Value, json};
+
+    use crate::{
+        testing::{MockDexAdapter, cetus_pool_objects, manager_with, rpc_server, snapshot_with, test_pool, test_token},
+        types::{BotError, PoolState},
+        utils::{alerting::AlertSink, metrics::{MetricsServer, register_metrics}},
+    };
+
+    use super::*;
+
+    /// Orchestrator over Cetus pools `p1` (fresh) and `p2` (past the TTL)
+    /// whose RPC answers with `handler`
+    async fn orchestrator<F>(handler: F, config: SyncConfig) -> Result<SyncOrchestrator>
+    where
+        F: Fn(Value) -> Value + Send + Sync + 'static,
+    {
+        let (sui, usdc) = (test_token("SUI"), test_token("USDC"));
+        let mut stale = test_pool("p2", DexId::Cetus, &sui, &usdc, 1_000, 2_000);
+        stale.block_timestamp = now() - 600_000;
+        let snapshot = snapshot_with(vec![test_pool("p1", DexId::Cetus, &sui, &usdc, 1_000, 2_000), stale]);
+
+        let url = rpc_server(handler).await?;
+        let fetcher = PoolStateFetcher::default().with_network_client(Network::SuiMainnet, Arc::new(SuiRpcClient::new(url)));
+        let manager = Arc::new(RwLock::new(manager_with(&snapshot)?));
+        Ok(SyncOrchestrator::new(manager, fetcher, &config))
+    }
+
+    /// Pool objects for requests of exactly `expected`, an error otherwise
+    fn expecting(expected: Value) -> impl Fn(Value) -> Value + Send + Sync + 'static {
+        move |request| {
+            if request["params"][0] != expected {
+                return json!([{ "error": { "code": "unexpected" } }]);
+            }
+            cetus_pool_objects(request)
+        }
+    }
+
+    /// Pool objects after the first `failures` requests have failed
+    fn failing(failures: usize) -> impl Fn(Value) -> Value + Send + Sync + 'static {
+        let requests = AtomicUsize::new(0);
+        move |request| {
+            if requests.fetch_add(1, Ordering::Relaxed) < failures {
+                return json!([{ "error": { "code": "unavailable" } }]);
+            }
+            cetus_pool_objects(request)
+        }
+    }
+
+    fn fast_retries() -> SyncConfig {
+        SyncConfig {
+            max_retries: 2,
+            retry_delay_ms: 1,
+            ..SyncConfig::default()
+        }
+    }
+
+    async fn pool(orchestrator: &SyncOrchestrator, pool_id: &str) -> Result<PoolState> {
+        orchestrator.dex_manager
+            .read()
+            .await
+            .get_pool(&DexId::Cetus, &pool_id.to_string())
+            .cloned()
+            .ok_or_else(|| BotError::NotFound(pool_id.into()))
+    }
+
+    #[tokio::test]
+    async fn test_sync_specific_pools() -> Result<()> {
+        let orchestrator = orchestrator(expecting(json!(["p1"])), SyncConfig::default()).await?;
+
+        assert_eq!(orchestrator.sync_specific_pools(vec!["p1".into()]).await?, 1);
+        assert_eq!(pool(&orchestrator, "p1").await?.reserve_a, Decimal::from(5000));
+        assert_eq!(pool(&orchestrator, "p2").await?.version, 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_sync_stale_pools() -> Result<()> {
+        let orchestrator = orchestrator(expecting(json!(["p2"])), SyncConfig::default()).await?;
+
+        assert_eq!(orchestrator.sync_pools(SyncScope::Stale).await?, 1);
+        assert_eq!(pool(&orchestrator, "p2").await?.version, 9);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_history_records_each_pass() -> Result<()> {
+        let orchestrator = orchestrator(failing(1), SyncConfig { max_retries: 0, ..SyncConfig::default() }).await?;
+
+        assert!(orchestrator.sync_pools(SyncScope::All).await.is_err());
+        assert_eq!(orchestrator.sync_pools(SyncScope::Stale).await?, 1);
+
+        let history = orchestrator.get_sync_history();
+        assert_eq!(history.len(), 2);
+        assert_eq!((history[0].sync_type.as_str(), history[0].pools_failed), ("all", 2));
+        assert!(history[0].error.is_some());
+        assert_eq!(
+            (history[1].sync_type.as_str(), history[1].pools_updated, history[1].pools_failed, history[1].error.as_deref()),
+            ("stale", 1, 0, None)
+        );
+        assert!(history[1].completed_at >= history[1].started_at);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_sync_history_is_served() -> Result<()> {
+        let orchestrator = Arc::new(orchestrator(cetus_pool_objects, SyncConfig::default()).await?);
+        orchestrator.sync_pools(SyncScope::All).await?;
+
+        let metrics = register_metrics()?;
+        let address = MetricsServer::start(0, metrics, None, Some(orchestrator)).await?;
+        let history: Value = reqwest::get(format!("http://127.0.0.1:{}/sync-history", address.port()))
+            .await
+            .map_err(|e| BotError::Network(e.to_string()))?
+            .json()
+            .await
+            .map_err(|e| BotError::Network(e.to_string()))?;
+
+        assert_eq!(history[0]["sync_type"], "all");
+        assert_eq!(history[0]["pools_updated"], 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_stale_pass_evicts_unrefreshed_pools() -> Result<()> {
+        // Every returned state has zero reserves, so the merge rejects it
+        let orchestrator = orchestrator(
+            |request| {
+                let mut response = cetus_pool_objects(request);
+                response[0]["data"]["content"]["fields"]["coin_a"] = json!("0");
+                response[0]["data"]["content"]["fields"]["coin_b"] = json!("0");
+                response
+            },
+            SyncConfig::default(),
+        )
+        .await?;
+
+        assert_eq!(orchestrator.sync_pools(SyncScope::Stale).await?, 0);
+        assert!(pool(&orchestrator, "p2").await.is_err());
+        assert!(pool(&orchestrator, "p1").await.is_ok());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_stale_pass_keeps_unchanged_pools() -> Result<()> {
+        let orchestrator = orchestrator(cetus_pool_objects, SyncConfig::default()).await?;
+        let mut quiet = pool(&orchestrator, "p2").await?;
+        // Already at the version the node returns
+        quiet.version = 9;
+        {
+            let mut manager = orchestrator.dex_manager.write().await;
+            manager.remove_pool(&quiet.pool_id);
+            manager.register_pool(quiet)?;
+        }
+
+        assert_eq!(orchestrator.sync_pools(SyncScope::Stale).await?, 0);
+        assert!(pool(&orchestrator, "p2").await.is_ok());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_retries_failed_fetch() -> Result<()> {
+        let orchestrator = orchestrator(failing(2), fast_retries()).await?;
+
+        assert_eq!(orchestrator.sync_pools(SyncScope::All).await?, 2);
+        assert_eq!(orchestrator.stats().sync_retry_count, 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_reloaded_config_applies() -> Result<()> {
+        let orchestrator = orchestrator(failing(2), SyncConfig { max_retries: 0, ..fast_retries() }).await?;
+        assert!(orchestrator.sync_pools(SyncScope::All).await.is_err());
+
+        orchestrator.apply_config(&SyncConfig { state_ttl_seconds: 0, stale_sync_interval_seconds: 5, ..fast_retries() });
+        tokio::time::sleep(Duration::from_millis(5)).await;
+
+        // Retried past the second failure, and without a TTL the fresh p1 is stale too
+        assert_eq!(orchestrator.sync_pools(SyncScope::Stale).await?, 2);
+        assert_eq!(orchestrator.stats().sync_retry_count, 1);
+        assert_eq!(orchestrator.stale_sync_interval(), Duration::from_secs(5));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_stale_sync_task_follows_interval() -> Result<()> {
+        let orchestrator = Arc::new(orchestrator(cetus_pool_objects, SyncConfig::default()).await?);
+        let task = orchestrator.clone().spawn_stale_sync();
+
+        // Cuts the default minute-long wait short
+        orchestrator.apply_config(&SyncConfig { stale_sync_interval_seconds: 1, ..SyncConfig::default() });
+        let synced = tokio::time::timeout(Duration::from_secs(3), async {
+            while orchestrator.get_sync_history().is_empty() {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+        })
+        .await;
+        task.abort();
+
+        assert!(synced.is_ok());
+        assert_eq!(orchestrator.get_sync_history()[0].sync_type, "stale");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_failing_heartbeats_trigger_full_sync() -> Result<()> {
+        let orchestrator = orchestrator(cetus_pool_objects, SyncConfig::default()).await?;
+        orchestrator.dex_manager.write().await.register_dex(Box::new(MockDexAdapter::new(DexId::Turbos).unhealthy()))?;
+
+        for _ in 0..EMERGENCY_SYNC_FAILURES {
+            orchestrator.periodic_pass().await;
+        }
+
+        let sync_types: Vec<String> = orchestrator.get_sync_history().into_iter().map(|event| event.sync_type).collect();
+        assert_eq!(sync_types, vec!["stale", "stale", "all"]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_adaptive_sync_follows_event_rate() -> Result<()> {
+        let event_rate = Arc::new(EventRateCounter::new());
+        let config = SyncConfig { enable_adaptive_sync: true, ..SyncConfig::default() };
+        let orchestrator = orchestrator(cetus_pool_objects, config.clone()).await?.with_event_rate(event_rate.clone());
+
+        orchestrator.periodic_pass().await;
+        assert_eq!(orchestrator.stale_sync_interval(), Duration::from_secs(180));
+
+        for _ in 0..=BUSY_EVENTS_PER_MINUTE {
+            event_rate.record(now());
+        }
+        orchestrator.periodic_pass().await;
+        assert_eq!(orchestrator.stale_sync_interval(), Duration::from_secs(30));
+
+        orchestrator.apply_config(&SyncConfig { enable_adaptive_sync: false, ..config });
+        orchestrator.periodic_pass().await;
+        assert_eq!(orchestrator.stale_sync_interval(), Duration::from_secs(60));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_scheduled_sync_runs_after_delay() -> Result<()> {
+        let orchestrator = Arc::new(orchestrator(cetus_pool_objects, SyncConfig::default()).await?);
+
+        orchestrator.schedule_sync_after(Duration::from_millis(50), SyncScope::All);
+        assert_eq!(orchestrator.pending_scheduled_syncs(), 1);
+        assert!(orchestrator.get_sync_history().is_empty());
+        let synced = tokio::time::timeout(Duration::from_secs(3), async {
+            while orchestrator.get_sync_history().is_empty() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await;
+
+        assert!(synced.is_ok());
+        assert_eq!(orchestrator.get_sync_history()[0].sync_type, "all");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_scheduled_sync_never_runs() -> Result<()> {
+        let orchestrator = Arc::new(orchestrator(cetus_pool_objects, SyncConfig::default()).await?);
+
+        orchestrator.schedule_sync_after(Duration::from_millis(50), SyncScope::All);
+        orchestrator.cancel_scheduled_syncs();
+        tokio::time::sleep(Duration::from_millis(150)).await;
+
+        assert_eq!(orchestrator.pending_scheduled_syncs(), 0);
+        assert!(orchestrator.get_sync_history().is_empty());
+        Ok(())
+    }
+
+    /// Answers checkpoint requests with 1234 and object requests with Cetus pools at version 9
+    fn with_checkpoint(request: Value) -> Value {
+        if request["method"] == "sui_getLatestCheckpointSequenceNumber" {
+            return json!("1234");
+        }
+        cetus_pool_objects(request)
+    }
+
+    fn sync_state_path() -> PathBuf {
+        std::env::temp_dir().join(format!("sync-state-{}-{:?}.json", now(), std::thread::current().id()))
+    }
+
+    #[tokio::test]
+    async fn test_full_sync_records_sync_state() -> Result<()> {
+        let path = sync_state_path();
+        let orchestrator = orchestrator(with_checkpoint, SyncConfig::default()).await?.with_sync_state_path(&path);
+
+        orchestrator.sync_pools(SyncScope::Stale).await?;
+        assert_eq!(orchestrator.load_sync_state(), None);
+        orchestrator.sync_pools(SyncScope::All).await?;
+        let state = orchestrator.load_sync_state();
+        orchestrator.clear_sync_state()?;
+
+        assert_eq!(state.map(|state| (state.checkpoint, state.pool_count)), Some((1234, 2)));
+        assert!(!path.exists());
+        assert_eq!(orchestrator.load_sync_state(), None);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_recent_sync_state_limits_startup_to_changed_pools() -> Result<()> {
+        let path = sync_state_path();
+        let orchestrator = orchestrator(with_checkpoint, SyncConfig::default()).await?.with_sync_state_path(&path);
+
+        // Tracked pools are at version 1, chain has version 9
+        assert_eq!(orchestrator.sync_pools(SyncScope::Changed { since_checkpoint: 1 }).await?, 2);
+        orchestrator.sync_pools(SyncScope::All).await?;
+        orchestrator.initialize().await?;
+        let recent = orchestrator.get_sync_history().pop();
+
+        std::fs::write(&path, serde_json::to_string(&SyncState { checkpoint: 1, synced_at: now() - SYNC_STATE_MAX_AGE_MS, pool_count: 2 })?)?;
+        orchestrator.initialize().await?;
+        let outdated = orchestrator.get_sync_history().pop();
+        orchestrator.clear_sync_state()?;
+
+        assert_eq!(recent.map(|event| (event.sync_type, event.pools_updated)), Some(("changed".to_string(), 0)));
+        assert_eq!(outdated.map(|event| event.sync_type), Some("all".to_string()));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_builder_checks_node_health() -> Result<()> {
+        let manager = Arc::new(RwLock::new(DexManager::default()));
+        let healthy = Arc::new(SuiRpcClient::new(rpc_server(with_checkpoint).await?));
+        let unreachable = Arc::new(SuiRpcClient::new("http://127.0.0.1:9"));
+
+        let built = SyncOrchestratorBuilder::new(manager.clone(), &SyncConfig::default())
+            .with_network_client(Network::SuiMainnet, healthy)
+            .build()
+            .await?;
+        let failed = SyncOrchestratorBuilder::new(manager.clone(), &SyncConfig::default())
+            .with_network_client(Network::SuiMainnet, unreachable.clone())
+            .build()
+            .await;
+        let offline = SyncOrchestratorBuilder::new(manager, &SyncConfig::default())
+            .with_network_client(Network::SuiMainnet, unreachable)
+            .with_health_check(false)
+            .build()
+            .await;
+
+        assert_eq!(built.fetcher.networks(), vec![Network::SuiMainnet]);
+        assert!(matches!(failed, Err(BotError::Config(message)) if message.contains("127.0.0.1:9")));
+        assert!(offline.is_ok());
+        Ok(())
+    }
+
+    /// Keeps the severity of every alert it receives
+    struct RecordingSink(Arc<Mutex<Vec<AlertSeverity>>>);
+
+    #[async_trait::async_trait]
+    impl AlertSink for RecordingSink {
+        async fn send_alert(&self, _title: &str, _message: &str, severity: AlertSeverity) -> Result<()> {
+            self.0.lock().unwrap_or_else(|e| e.into_inner()).push(severity);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unhealthy_heartbeats_are_alerted() -> Result<()> {
+        let alerts = Arc::new(Mutex::new(Vec::new()));
+        let alerter = Alerter::new().with_sink(Box::new(RecordingSink(alerts.clone())), AlertSeverity::Info);
+        let orchestrator = orchestrator(cetus_pool_objects, SyncConfig::default()).await?.with_alerter(alerter);
+        orchestrator.dex_manager.write().await.register_dex(Box::new(MockDexAdapter::new(DexId::Turbos).unhealthy()))?;
+
+        for _ in 0..EMERGENCY_SYNC_FAILURES + 1 {
+            orchestrator.periodic_pass().await;
+        }
+
+        let alerts = alerts.lock().unwrap_or_else(|e| e.into_inner()).clone();
+        assert_eq!(alerts, vec![AlertSeverity::Warning, AlertSeverity::Critical]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_reports_dex_failing_every_retry() -> Result<()> {
+        let orchestrator = orchestrator(failing(3), fast_retries()).await?;
+
+        let result = orchestrator.sync_pools(SyncScope::All).await;
+
+        assert!(matches!(result, Err(BotError::Sync(message)) if message.contains("Cetus")));
+        assert_eq!(orchestrator.stats().sync_retry_count, 2);
+        Ok(())
+    }
+}
@@ -0,0 +1,124 @@
+//! RPC abstraction used by the sync pipeline to read pool states
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{
+    client::sui_rpc::{SuiObjectDataOptions, SuiRpcClient},
+    sync::parser::PoolParserRegistry,
+    types::{BotError, DexId, PoolState, Result},
+    utils::config::{PoolConfig, SyncConfig},
+};
+
+#[async_trait]
+pub trait RpcClient: Send + Sync {
+    async fn fetch_pool_state(&self, dex_id: DexId, pool: &PoolConfig) -> Result<PoolState>;
+
+    /// Pool states in the order of `pools`
+    async fn fetch_multiple_pools(&self, dex_id: DexId, pools: &[PoolConfig]) -> Result<Vec<PoolState>>;
+}
+
+/// Sui JSON-RPC backed client, splitting large reads into `batch_size` requests
+pub struct DefaultRpcClient {
+    client: Arc<SuiRpcClient>,
+    registry: PoolParserRegistry,
+    batch_size: usize,
+}
+
+impl DefaultRpcClient {
+    pub fn new_with_sui_client(client: Arc<SuiRpcClient>, registry: PoolParserRegistry, config: SyncConfig) -> Self {
+        Self {
+            client,
+            registry,
+            batch_size: config.batch_size.max(1),
+        }
+    }
+}
+
+#[async_trait]
+impl RpcClient for DefaultRpcClient {
+    async fn fetch_pool_state(&self, dex_id: DexId, pool: &PoolConfig) -> Result<PoolState> {
+        self.fetch_multiple_pools(dex_id, std::slice::from_ref(pool))
+            .await?
+            .pop()
+            .ok_or_else(|| BotError::NotFound(format!("Pool {}", pool.address)))
+    }
+
+    async fn fetch_multiple_pools(&self, dex_id: DexId, pools: &[PoolConfig]) -> Result<Vec<PoolState>> {
+        // Fail before any request for a DEX that cannot be parsed
+        if !self.registry.supports(dex_id) {
+            return Err(BotError::dex(dex_id, "Pool parsing is not supported"));
+        }
+
+        let mut states = Vec::with_capacity(pools.len());
+        for batch in pools.chunks(self.batch_size) {
+            let object_ids = batch.iter().map(|pool| pool.address.clone()).collect();
+            let objects = self.client
+                .batch_get_objects(object_ids, Some(SuiObjectDataOptions::full_content()))
+                .await?;
+            for (pool, object) in batch.iter().zip(objects) {
+                states.push(self.registry.parse(dex_id, pool, object)?);
+            }
+        }
+        Ok(states)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use serde_json::Value;
+
+    use crate::testing::{cetus_pool_objects, rpc_server, test_token};
+
+    use super::*;
+
+    fn pool(address: String) -> PoolConfig {
+        PoolConfig {
+            address,
+            token_a: test_token("SUI"),
+            token_b: test_token("USDC"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fetches_in_batches() -> Result<()> {
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = requests.clone();
+        let url = rpc_server(move |request: Value| {
+            counter.fetch_add(1, Ordering::Relaxed);
+            cetus_pool_objects(request)
+        })
+        .await?;
+        let config = SyncConfig { batch_size: 2, ..SyncConfig::default() };
+        let client = DefaultRpcClient::new_with_sui_client(
+            Arc::new(SuiRpcClient::new(url)),
+            PoolParserRegistry::default(),
+            config,
+        );
+        let pools: Vec<PoolConfig> = (1..=5).map(|i| pool(format!("0xp{}", i))).collect();
+
+        let states = client.fetch_multiple_pools(DexId::Cetus, &pools).await?;
+
+        assert_eq!(requests.load(Ordering::Relaxed), 3);
+        assert_eq!(states.len(), 5);
+        assert_eq!(states[4].pool_id, "0xp5");
+        assert_eq!(client.fetch_pool_state(DexId::Cetus, &pools[0]).await?.pool_id, "0xp1");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_dex_makes_no_request() {
+        let client = DefaultRpcClient::new_with_sui_client(
+            Arc::new(SuiRpcClient::new("http://127.0.0.1:9")),
+            PoolParserRegistry::default(),
+            SyncConfig::default(),
+        );
+
+        let result = client.fetch_pool_state(DexId::Turbos, &pool("0xp1".into())).await;
+
+        assert!(matches!(result, Err(BotError::Dex { dex: DexId::Turbos, .. })));
+    }
+}
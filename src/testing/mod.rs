@@ -0,0 +1,699 @@
+//! Mock components for exercising the engine without a network
+
+use std::{collections::HashMap, sync::{Arc, Mutex}};
+
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use serde_json::{Value, json};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    sync::{RwLock, mpsc},
+};
+
+use crate::{
+    arbitrage::{
+        arbitrage_engine::{ArbitrageEngine, ArbitrageEngineBuilder},
+        calculator::ArbitrageCalculator,
+        detector::{ArbitrageDetector, DetectionStats},
+        validator::OpportunityValidator,
+    },
+    dex::{adapter::DexAdapter, manager::DexManager, state::{DexConfig, DexState}},
+    event::processor::{EventProcessor, ProcessorStatus},
+    execution::executor::TradeExecutor,
+    sync::periodic::SyncOrchestrator,
+    types::{
+        ArbitrageHop, ArbitrageOpportunity, ArbitragePath, BotError, DEX_SWAP_FEE_RATE, DexId, ExecutionResult,
+        ExecutionStatus, FeeStructure, HealthStatus, Network, PoolId, PoolState, Price, PriceUpdate, RawEvent, Result,
+        StateSnapshot, SwapEvent, SyncResult, TokenInfo, TokenPair, now,
+    },
+    utils::metrics::MetricsRegistry,
+};
+
+/// Adapter with a settable pool map and a fixed `calculate_price` answer
+pub struct MockDexAdapter {
+    state: DexState,
+    price: Option<Price>,
+    network: Network,
+}
+
+impl MockDexAdapter {
+    pub fn new(dex_id: DexId) -> Self {
+        Self {
+            state: DexState::new(DexConfig::new(dex_id, String::new())),
+            price: None,
+            network: Network::SuiMainnet,
+        }
+    }
+
+    pub fn with_pool(mut self, pool: PoolState) -> Self {
+        self.state.pools.insert(pool.pool_id.clone(), pool);
+        self
+    }
+
+    pub fn with_network(mut self, network: Network) -> Self {
+        self.network = network;
+        self
+    }
+
+    /// Fail every heartbeat
+    pub fn unhealthy(mut self) -> Self {
+        self.state.health.is_healthy = false;
+        self
+    }
+
+    pub fn with_price(mut self, price: Price) -> Self {
+        self.price = Some(price);
+        self
+    }
+}
+
+#[async_trait]
+impl DexAdapter for MockDexAdapter {
+    fn dex_id(&self) -> DexId {
+        self.state.dex_id
+    }
+
+    fn network(&self) -> Network {
+        self.network
+    }
+
+    fn state(&self) -> &DexState {
+        &self.state
+    }
+
+    fn state_mut(&mut self) -> &mut DexState {
+        &mut self.state
+    }
+
+    async fn initialize(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn fetch_pool_state(&self, pool_id: &PoolId) -> Result<PoolState> {
+        self.state.pools
+            .get(pool_id)
+            .cloned()
+            .ok_or_else(|| BotError::NotFound(format!("Pool {}", pool_id)))
+    }
+
+    async fn fetch_all_pools(&self) -> Result<Vec<PoolState>> {
+        Ok(self.state.pools.values().cloned().collect())
+    }
+
+    fn parse_event(&self, _raw: RawEvent) -> Result<SwapEvent> {
+        Err(BotError::Parse("Mock adapter does not parse events".into()))
+    }
+
+    fn process_swap_event(&mut self, event: SwapEvent) -> Result<PriceUpdate> {
+        Err(BotError::InvalidState(format!("Mock adapter ignores event for {}", event.pool_id)))
+    }
+
+    fn calculate_price(&self, pool: &PoolState) -> Result<Price> {
+        self.price
+            .clone()
+            .ok_or_else(|| BotError::NotFound(format!("No mock price for {}", pool.pool_id)))
+    }
+
+    fn get_price(&self, _pair: &TokenPair) -> Option<Price> {
+        self.price.clone()
+    }
+
+    async fn heartbeat(&mut self) -> Result<HealthStatus> {
+        if !self.state.health.is_healthy {
+            return Err(BotError::Rpc("Mock DEX is down".into()));
+        }
+        Ok(HealthStatus::healthy("mock"))
+    }
+
+    async fn periodic_sync(&mut self) -> Result<SyncResult> {
+        Ok(SyncResult::success(self.state.pools.len(), 0))
+    }
+
+    fn is_healthy(&self) -> bool {
+        self.state.health.is_healthy
+    }
+}
+
+/// Calculator that always reports the same opportunities
+#[derive(Default)]
+pub struct MockArbitrageCalculator {
+    pub opportunities: Vec<ArbitrageOpportunity>,
+}
+
+#[async_trait]
+impl ArbitrageCalculator for MockArbitrageCalculator {
+    async fn find_opportunities(&self, _snapshot: &StateSnapshot) -> Vec<ArbitrageOpportunity> {
+        self.opportunities.clone()
+    }
+
+    async fn calculate_profitability(&self, path: &ArbitragePath, _snapshot: &StateSnapshot) -> Result<ArbitrageOpportunity> {
+        self.opportunities
+            .iter()
+            .find(|opportunity| opportunity.path.path_id == path.path_id)
+            .cloned()
+            .ok_or_else(|| BotError::NotFound(format!("Path {}", path.path_id)))
+    }
+
+    async fn find_paths(&self, _start_token: &TokenInfo, _max_hops: usize, _snapshot: &StateSnapshot) -> Vec<ArbitragePath> {
+        self.opportunities.iter().map(|opportunity| opportunity.path.clone()).collect()
+    }
+
+    async fn validate_opportunity(&self, _opportunity: &ArbitrageOpportunity, _snapshot: &StateSnapshot) -> bool {
+        true
+    }
+}
+
+/// Detector that hands out queued opportunities one by one
+#[derive(Default)]
+pub struct MockArbitrageDetector {
+    pub opportunities: Vec<ArbitrageOpportunity>,
+}
+
+#[async_trait]
+impl ArbitrageDetector for MockArbitrageDetector {
+    async fn next_opportunity(&mut self) -> Option<ArbitrageOpportunity> {
+        if self.opportunities.is_empty() {
+            return None;
+        }
+        Some(self.opportunities.remove(0))
+    }
+
+    fn get_stats(&self) -> DetectionStats {
+        DetectionStats {
+            scans_performed: 0,
+            opportunities_found: 0,
+            avg_scan_duration_ms: 0,
+            last_scan_timestamp: 0,
+            snapshot_cache_hits: 0,
+            snapshot_cache_misses: 0,
+            scanner_timeouts: 0,
+            deduplicated_count: 0,
+        }
+    }
+}
+
+/// Event processor that accepts every event without touching pool state
+pub struct MockEventProcessor;
+
+#[async_trait]
+impl EventProcessor for MockEventProcessor {
+    async fn start(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn stop(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn subscribe_swap_events(&self) -> mpsc::Receiver<SwapEvent> {
+        mpsc::channel(1).1
+    }
+
+    async fn get_status(&self) -> HashMap<DexId, ProcessorStatus> {
+        HashMap::new()
+    }
+
+    async fn process_event(&mut self, raw_event: RawEvent) -> Result<SwapEvent> {
+        Err(BotError::Event(format!("Mock processor ignores event {}", raw_event.event_type)))
+    }
+
+    fn drain_pending_events(&mut self) -> Vec<(DexId, RawEvent)> {
+        Vec::new()
+    }
+}
+
+/// Executor that records every call and returns a fixed status
+pub struct MockTradeExecutor {
+    pub status: ExecutionStatus,
+    pub profit: Decimal,
+    pub calls: Arc<Mutex<Vec<ArbitrageOpportunity>>>,
+}
+
+impl MockTradeExecutor {
+    pub fn new(status: ExecutionStatus, profit: Decimal) -> Self {
+        Self {
+            status,
+            profit,
+            calls: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+}
+
+#[async_trait]
+impl TradeExecutor for MockTradeExecutor {
+    async fn execute(&mut self, opportunity: ArbitrageOpportunity) -> ExecutionResult {
+        if let Ok(mut calls) = self.calls.lock() {
+            calls.push(opportunity.clone());
+        }
+
+        let result = ExecutionResult::new(opportunity);
+        match self.status {
+            ExecutionStatus::Success => result.success("0xmockdigest".into(), HashMap::new(), 0, self.profit),
+            ExecutionStatus::Simulated => result.simulated(HashMap::new(), 0, self.profit),
+            ExecutionStatus::Failed => result.failure(BotError::Execution("mock failure".into())),
+            ExecutionStatus::Pending => result,
+        }
+    }
+
+    async fn simulate(&self, _opportunity: &ArbitrageOpportunity) -> Result<(u64, Decimal)> {
+        Ok((0, self.profit))
+    }
+}
+
+/// Validator with a fixed verdict
+pub struct MockOpportunityValidator(pub bool);
+
+#[async_trait]
+impl OpportunityValidator for MockOpportunityValidator {
+    async fn validate(&self, _opportunity: &ArbitrageOpportunity) -> bool {
+        self.0
+    }
+}
+
+/// Builds an `ArbitrageEngine` wired entirely with mocks
+pub struct ArbitrageEngineTestBuilder {
+    opportunities: Vec<ArbitrageOpportunity>,
+    validator_result: bool,
+    execution_status: ExecutionStatus,
+    profit: Decimal,
+    sync_orchestrator: Option<Arc<SyncOrchestrator>>,
+    dex_manager: Option<Arc<RwLock<DexManager>>>,
+    metrics: Option<Arc<MetricsRegistry>>,
+}
+
+impl ArbitrageEngineTestBuilder {
+    pub fn new() -> Self {
+        Self {
+            opportunities: Vec::new(),
+            validator_result: true,
+            execution_status: ExecutionStatus::Success,
+            profit: Decimal::ONE,
+            sync_orchestrator: None,
+            dex_manager: None,
+            metrics: None,
+        }
+    }
+
+    pub fn with_opportunities(mut self, opportunities: Vec<ArbitrageOpportunity>) -> Self {
+        self.opportunities = opportunities;
+        self
+    }
+
+    pub fn with_validator_result(mut self, valid: bool) -> Self {
+        self.validator_result = valid;
+        self
+    }
+
+    pub fn with_execution_status(mut self, status: ExecutionStatus) -> Self {
+        self.execution_status = status;
+        self
+    }
+
+    pub fn with_sync_orchestrator(mut self, sync_orchestrator: Arc<SyncOrchestrator>) -> Self {
+        self.sync_orchestrator = Some(sync_orchestrator);
+        self
+    }
+
+    pub fn with_dex_manager(mut self, dex_manager: Arc<RwLock<DexManager>>) -> Self {
+        self.dex_manager = Some(dex_manager);
+        self
+    }
+
+    pub fn with_metrics(mut self, metrics: Arc<MetricsRegistry>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Build the engine and return the executor's call log alongside it
+    pub fn build(self) -> Result<(ArbitrageEngine, Arc<Mutex<Vec<ArbitrageOpportunity>>>)> {
+        let executor = MockTradeExecutor::new(self.execution_status, self.profit);
+        let calls = executor.calls.clone();
+
+        let mut builder = ArbitrageEngineBuilder::new()
+            .with_event_processor(Box::new(MockEventProcessor))
+            .with_detector(Box::new(MockArbitrageDetector { opportunities: self.opportunities }))
+            .with_executor(Box::new(executor))
+            .with_validator(Box::new(MockOpportunityValidator(self.validator_result)));
+        if let Some(sync_orchestrator) = self.sync_orchestrator {
+            builder = builder.with_sync_orchestrator(sync_orchestrator);
+        }
+        if let Some(dex_manager) = self.dex_manager {
+            builder = builder.with_dex_manager(dex_manager);
+        }
+        if let Some(metrics) = self.metrics {
+            builder = builder.with_metrics(metrics);
+        }
+        let engine = builder.build()?;
+
+        Ok((engine, calls))
+    }
+}
+
+impl Default for ArbitrageEngineTestBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Native SUI for "SUI", otherwise a token with a made-up address derived from its symbol
+pub fn test_token(symbol: &str) -> TokenInfo {
+    if symbol == "SUI" {
+        return TokenInfo::sui();
+    }
+    TokenInfo::new(symbol, format!("0x{}::coin::{}", symbol.to_lowercase(), symbol), 9)
+}
+
+/// Active pool with a 0.3% fee; reserves are given in whole tokens
+pub fn test_pool(pool_id: &str, dex_id: DexId, token_a: &TokenInfo, token_b: &TokenInfo, reserve_a: u64, reserve_b: u64) -> PoolState {
+    PoolState {
+        dex_id,
+        pool_id: pool_id.into(),
+        token_a: token_a.clone(),
+        token_b: token_b.clone(),
+        reserve_a: Decimal::from(token_a.to_raw(Decimal::from(reserve_a))),
+        reserve_b: Decimal::from(token_b.to_raw(Decimal::from(reserve_b))),
+        fee_rate: DEX_SWAP_FEE_RATE,
+        block_timestamp: now(),
+        is_active: true,
+        version: 1,
+        sqrt_price_x64: 0,
+        current_tick_index: 0,
+        amp_factor: None,
+    }
+}
+
+/// Snapshot holding `pools` and their tokens
+pub fn snapshot_with(pools: Vec<PoolState>) -> StateSnapshot {
+    let mut snapshot = StateSnapshot::new();
+    for pool in pools {
+        snapshot.insert_pool(pool);
+    }
+    snapshot
+}
+
+/// SUI -> USDC -> DEEP -> SUI multiplies out to 1.2 before fees
+pub fn triangle_snapshot() -> StateSnapshot {
+    let (sui, usdc, deep) = (test_token("SUI"), test_token("USDC"), test_token("DEEP"));
+    snapshot_with(vec![
+        test_pool("p1", DexId::Cetus, &sui, &usdc, 1_000_000, 2_000_000),
+        test_pool("p2", DexId::Turbos, &usdc, &deep, 2_000_000, 4_000_000),
+        test_pool("p3", DexId::Cetus, &deep, &sui, 4_000_000, 1_200_000),
+    ])
+}
+
+/// Manager with one mock adapter per DEX, tracking every pool in `snapshot`
+pub fn manager_with(snapshot: &StateSnapshot) -> Result<DexManager> {
+    let mut adapters: HashMap<DexId, MockDexAdapter> = HashMap::new();
+    for pool in snapshot.pools.values() {
+        let adapter = adapters.remove(&pool.dex_id).unwrap_or_else(|| MockDexAdapter::new(pool.dex_id));
+        adapters.insert(pool.dex_id, adapter.with_pool(pool.clone()));
+    }
+
+    let mut manager = DexManager::default();
+    for adapter in adapters.into_values() {
+        manager.register_dex(Box::new(adapter))?;
+    }
+    Ok(manager)
+}
+
+/// `rpc_server` handler answering `sui_multiGetObjects` with a Cetus pool object for each requested ID
+pub fn cetus_pool_objects(request: Value) -> Value {
+    let ids = request["params"][0].as_array().cloned().unwrap_or_default();
+    ids.iter()
+        .map(|id| json!({
+            "data": {
+                "objectId": id,
+                "version": "9",
+                "content": {
+                    "fields": {
+                        "coin_a": "5000",
+                        "coin_b": "7000",
+                        "current_sqrt_price": "18446744073709551616",
+                        "fee_rate": "2500",
+                        "is_pause": false
+                    }
+                }
+            }
+        }))
+        .collect()
+}
+
+/// Local JSON-RPC endpoint answering every request with `handler(request)`
+/// as its `result`. Returns the URL to point a client at.
+pub async fn rpc_server<F>(handler: F) -> Result<String>
+where
+    F: Fn(Value) -> Value + Send + Sync + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("http://{}", listener.local_addr()?);
+    let handler = Arc::new(handler);
+
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            let handler = handler.clone();
+            tokio::spawn(async move {
+                let _ = answer_rpc(stream, handler.as_ref()).await;
+            });
+        }
+    });
+    Ok(url)
+}
+
+/// Read one HTTP request and reply with the handler's result
+async fn answer_rpc(mut stream: TcpStream, handler: &(dyn Fn(Value) -> Value + Send + Sync)) -> Result<()> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let (header_end, content_length) = loop {
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            return Ok(());
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+        if let Some(end) = buffer.windows(4).position(|window| window == b"\r\n\r\n") {
+            let headers = String::from_utf8_lossy(&buffer[..end]).to_lowercase();
+            let content_length = headers
+                .lines()
+                .find_map(|line| line.strip_prefix("content-length:"))
+                .and_then(|length| length.trim().parse::<usize>().ok())
+                .unwrap_or(0);
+            break (end + 4, content_length);
+        }
+    };
+    while buffer.len() < header_end + content_length {
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            break;
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    }
+
+    let request: Value = serde_json::from_slice(&buffer[header_end..])?;
+    let body = serde_json::json!({ "jsonrpc": "2.0", "id": request["id"].clone(), "result": handler(request) }).to_string();
+    let response = format!(
+        "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    Ok(())
+}
+
+/// Two-hop SUI -> USDC -> SUI opportunity discovered just now
+pub fn sample_opportunity() -> ArbitrageOpportunity {
+    let sui = TokenInfo::new("SUI", "0x2::sui::SUI", 9);
+    let usdc = TokenInfo::new("USDC", "0xusdc", 6);
+    let hop = |dex_id, pool_id: &str, token_in: &TokenInfo, token_out: &TokenInfo, amount_in, amount_out| ArbitrageHop {
+        dex_id,
+        pool_id: pool_id.into(),
+        pair: TokenPair::new(sui.clone(), usdc.clone()),
+        sell_base: token_in.matches(&sui),
+        token_in: token_in.clone(),
+        token_out: token_out.clone(),
+        amount_in,
+        expected_amount_out: amount_out,
+        min_amount_out: amount_out,
+        price_impact: Decimal::ZERO,
+        fee_rate: DEX_SWAP_FEE_RATE,
+    };
+
+    let discovered_at = now();
+    ArbitrageOpportunity {
+        path: ArbitragePath {
+            path_id: "sample".into(),
+            start_token: sui.clone(),
+            end_token: sui.clone(),
+            hops: vec![
+                hop(DexId::Cetus, "0xpool1", &sui, &usdc, 1_000_000_000, 2_000_000),
+                hop(DexId::Turbos, "0xpool2", &usdc, &sui, 2_000_000, 1_010_000_000),
+            ],
+            initial_amount: 1_000_000_000,
+            expected_final_amount: 1_010_000_000,
+            min_final_amount: 1_010_000_000,
+            calculated_at: discovered_at,
+            networks: Vec::new(),
+        },
+        gross_profit: Decimal::new(1, 2),
+        estimated_gas_cost: Decimal::new(1, 3),
+        total_dex_fees: Decimal::ZERO,
+        net_profit: Decimal::new(9, 3),
+        net_profit_percent: Decimal::new(9, 1),
+        discovered_at,
+    }
+}
+
+mod tests {
+    use crate::{
+        client::sui_rpc::SuiRpcClient,
+        sync::fetcher::PoolStateFetcher,
+        utils::{config::SyncConfig, metrics::{MetricsServer, STATE_SECRET_HEADER, StateEndpoint, register_metrics}},
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_opportunity_is_validated_and_executed() -> Result<()> {
+        let (mut engine, calls) = ArbitrageEngineTestBuilder::new()
+            .with_opportunities(vec![sample_opportunity()])
+            .build()?;
+
+        engine.poll_once().await;
+
+        assert_eq!(engine.get_stats().opportunities_found, 1);
+        assert_eq!(engine.get_stats().execution_successes, 1);
+        assert_eq!(engine.get_stats().success_rate(), 100.0);
+        assert_eq!(engine.export_state().await.recent_opportunities.len(), 1);
+        assert_eq!(calls.lock().map(|calls| calls.len()).unwrap_or(0), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_stats_handle_sees_live_counters() -> Result<()> {
+        let (mut engine, _) = ArbitrageEngineTestBuilder::new()
+            .with_opportunities(vec![sample_opportunity(), sample_opportunity()])
+            .build()?;
+        let stats = engine.stats_handle();
+
+        engine.poll_once().await;
+        engine.poll_once().await;
+
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.opportunities_found, 2);
+        assert_eq!(snapshot.execution_successes, 2);
+        assert_eq!(snapshot.total_profit, engine.get_stats().total_profit);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_simulated_execution_is_not_a_success() -> Result<()> {
+        let (mut engine, _) = ArbitrageEngineTestBuilder::new()
+            .with_opportunities(vec![sample_opportunity()])
+            .with_execution_status(ExecutionStatus::Simulated)
+            .build()?;
+
+        engine.poll_once().await;
+
+        let stats = engine.get_stats();
+        assert_eq!((stats.execution_successes, stats.execution_simulations), (0, 1));
+        assert_eq!((stats.total_profit, stats.simulated_profit), (Decimal::ZERO, Decimal::ONE));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_profit_metric_is_valued_in_usd() -> Result<()> {
+        let snapshot = snapshot_with(vec![test_pool("p1", DexId::Cetus, &test_token("SUI"), &test_token("USDC"), 1_000, 2_000)]);
+        let metrics = register_metrics()?;
+        let (mut engine, _) = ArbitrageEngineTestBuilder::new()
+            .with_opportunities(vec![sample_opportunity(), sample_opportunity()])
+            .with_dex_manager(Arc::new(RwLock::new(manager_with(&snapshot)?)))
+            .with_metrics(metrics.clone())
+            .build()?;
+
+        engine.poll_once().await;
+        engine.poll_once().await;
+
+        // One SUI of profit per trade at 2 USD per SUI
+        assert!(metrics.render()?.contains("arbitrage_net_profit_usd 4"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_rejected_opportunity_is_not_executed() -> Result<()> {
+        let (mut engine, calls) = ArbitrageEngineTestBuilder::new()
+            .with_opportunities(vec![sample_opportunity()])
+            .with_validator_result(false)
+            .build()?;
+
+        engine.poll_once().await;
+
+        assert_eq!(engine.get_stats().execution_successes, 0);
+        assert_eq!(engine.get_stats().opportunities_skipped, 1);
+        assert_eq!(calls.lock().map(|calls| calls.len()).unwrap_or(0), 0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_force_sync_refreshes_all_pools() -> Result<()> {
+        let (sui, usdc) = (test_token("SUI"), test_token("USDC"));
+        let snapshot = snapshot_with(vec![
+            test_pool("p1", DexId::Cetus, &sui, &usdc, 1_000, 2_000),
+            test_pool("p2", DexId::Cetus, &sui, &usdc, 1_000, 2_000),
+        ]);
+        let url = rpc_server(cetus_pool_objects).await?;
+        let fetcher = PoolStateFetcher::default().with_network_client(Network::SuiMainnet, Arc::new(SuiRpcClient::new(url)));
+        let manager = Arc::new(RwLock::new(manager_with(&snapshot)?));
+        let (mut engine, _) = ArbitrageEngineTestBuilder::new()
+            .with_sync_orchestrator(Arc::new(SyncOrchestrator::new(manager, fetcher, &SyncConfig::default())))
+            .build()?;
+
+        assert_eq!(engine.force_sync().await?, 2);
+        assert!(engine.last_sync_time().is_some());
+        assert_eq!(engine.export_state().await.sync_history.len(), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_state_endpoint_requires_secret() -> Result<()> {
+        let (mut engine, _) = ArbitrageEngineTestBuilder::new()
+            .with_opportunities(vec![sample_opportunity()])
+            .build()?;
+        let state = StateEndpoint { handle: engine.state_handle(), secret: "letmein".into() };
+        let address = MetricsServer::start(0, register_metrics()?, Some(state), None).await?;
+        let url = format!("http://127.0.0.1:{}/state", address.port());
+        let get = |secret: &'static str| {
+            let url = url.clone();
+            async move {
+                let response = reqwest::Client::new()
+                    .get(url)
+                    .header(STATE_SECRET_HEADER, secret)
+                    .send()
+                    .await
+                    .map_err(|e| BotError::Network(e.to_string()))?;
+                let status = response.status();
+                let body = response.text().await.map_err(|e| BotError::Network(e.to_string()))?;
+                Ok::<_, BotError>((status, body))
+            }
+        };
+
+        let (denied, state) = tokio::select! {
+            result = engine.start() => return result.and(Err(BotError::InvalidState("Engine stopped".into()))),
+            responses = async { Ok::<_, BotError>((get("wrong").await?, get("letmein").await?)) } => responses?,
+        };
+
+        assert_eq!(denied.0, reqwest::StatusCode::UNAUTHORIZED);
+        assert_eq!(state.0, reqwest::StatusCode::OK);
+        let state: Value = serde_json::from_str(&state.1)?;
+        assert_eq!(state["recent_opportunities"].as_array().map(Vec::len), Some(1));
+        assert!(state["sync_history"].is_array());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_force_sync_without_orchestrator_fails() -> Result<()> {
+        let (mut engine, _) = ArbitrageEngineTestBuilder::new().build()?;
+
+        assert!(matches!(engine.force_sync().await, Err(BotError::Config(_))));
+        assert!(engine.last_sync_time().is_none());
+        Ok(())
+    }
+}
@@ -1,7 +1,13 @@
+use std::collections::HashMap;
+
 use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
 use serde::{Deserialize, Serialize};
 
-use crate::types::{BotError, DexId, Network, PoolId, Result, Timestamp, TokenInfo, TokenPair, now};
+use crate::{
+    types::{BotError, DexId, Network, PoolId, PoolState, Result, Timestamp, TokenInfo, TokenPair, now},
+    utils::math::{apply_slippage_tolerance, calculate_amm_output, calculate_price_impact},
+};
 
 /// A single hop in an arbitrage path
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -50,7 +56,7 @@ pub struct ArbitragePath {
 impl ArbitragePath {
     /// Check if this is a closed loop (ends with same token)
     pub fn is_closed_loop(&self) -> bool {
-        self.start_token.address == self.end_token.address
+        self.start_token.matches(&self.end_token)
     }
     
     /// Get number of hops
@@ -58,6 +64,36 @@ impl ArbitragePath {
         self.hops.len()
     }
     
+    /// Copy of the path with amounts filled in hop by hop: `initial_amount`
+    /// goes into the first pool, each hop's output feeds the next, and every
+    /// minimum output allows `slippage_tolerance` percent below expected
+    pub fn with_optimal_amounts(
+        &self,
+        initial_amount: u64,
+        pool_states: &HashMap<PoolId, PoolState>,
+        slippage_tolerance: Decimal,
+    ) -> Result<Self> {
+        let mut priced = self.clone();
+        let mut amount = initial_amount;
+        for hop in &mut priced.hops {
+            let pool = pool_states
+                .get(&hop.pool_id)
+                .ok_or_else(|| BotError::InsufficientLiquidity { pool_id: hop.pool_id.clone() })?;
+            let (reserve_in, reserve_out, fee_bps) = pool.swap_params(hop.sell_base);
+            
+            hop.amount_in = amount;
+            hop.expected_amount_out = calculate_amm_output(amount, reserve_in, reserve_out, fee_bps);
+            hop.min_amount_out = apply_slippage_tolerance(hop.expected_amount_out, slippage_tolerance);
+            hop.price_impact = calculate_price_impact(amount, reserve_in, reserve_out);
+            amount = hop.expected_amount_out;
+        }
+        
+        priced.initial_amount = initial_amount;
+        priced.expected_final_amount = amount;
+        priced.min_final_amount = priced.hops.last().map(|hop| hop.min_amount_out).unwrap_or(0);
+        Ok(priced)
+    }
+    
     /// Check if path is triangular (3 hops)
     pub fn is_triangular(&self) -> bool {
         self.hop_count() == 3 && self.is_closed_loop()
@@ -87,6 +123,44 @@ impl ArbitragePath {
         Decimal::from_f64_retain(profit / initial * 100.0).unwrap_or(Decimal::ZERO)
     }
     
+    /// Total DEX fees in start token units. Each hop's fee is
+    /// `amount_in * fee_rate` in its input token, converted to the start
+    /// token at the rates the earlier hops trade at.
+    pub fn total_fees(&self) -> Decimal {
+        // Start token per unit of the current hop's input token
+        let mut rate = Decimal::ONE;
+        let mut fees = Decimal::ZERO;
+        for hop in &self.hops {
+            let amount_in = hop.token_in.to_decimal(hop.amount_in);
+            fees += amount_in * hop.fee_rate * rate;
+
+            let amount_out = hop.token_out.to_decimal(hop.expected_amount_out);
+            if amount_out.is_zero() {
+                break;
+            }
+            rate = rate * amount_in / amount_out;
+        }
+        fees
+    }
+    
+    /// Distinct DEXes the path trades on, in first-use order
+    pub fn dex_ids(&self) -> Vec<DexId> {
+        let mut dex_ids = Vec::new();
+        for hop in &self.hops {
+            if !dex_ids.contains(&hop.dex_id) {
+                dex_ids.push(hop.dex_id);
+            }
+        }
+        dex_ids
+    }
+    
+    /// Sorted, comma-joined pool IDs; equal for paths over the same pools
+    pub fn pool_signature(&self) -> String {
+        let mut pool_ids: Vec<&str> = self.hops.iter().map(|hop| hop.pool_id.as_str()).collect();
+        pool_ids.sort_unstable();
+        pool_ids.join(",")
+    }
+    
     /// Get all tokens in the path
     pub fn all_tokens(&self) -> Vec<TokenInfo> {
         let mut tokens = vec![self.start_token.clone()];
@@ -130,7 +204,7 @@ impl ArbitragePath {
             let current_out = &self.hops[i].token_out;
             let next_in = &self.hops[i + 1].token_in;
             
-            if current_out.address != next_in.address {
+            if !current_out.matches(next_in) {
                 return Err(BotError::InvalidState(
                     format!(
                         "Hop {} output ({}) doesn't match hop {} input ({})",
@@ -141,14 +215,14 @@ impl ArbitragePath {
         }
         
         // Check first hop input matches start token
-        if self.hops[0].token_in.address != self.start_token.address {
+        if !self.hops[0].token_in.matches(&self.start_token) {
             return Err(BotError::InvalidState(
                 "First hop input doesn't match start token".into()
             ));
         }
         
         // Check last hop output matches end token
-        if self.hops.last().unwrap().token_out.address != self.end_token.address {
+        if self.hops.last().is_some_and(|hop| !hop.token_out.matches(&self.end_token)) {
             return Err(BotError::InvalidState(
                 "Last hop output doesn't match end token".into()
             ));
@@ -156,6 +230,49 @@ impl ArbitragePath {
         
         Ok(())
     }
+    
+    /// Check the numeric sanity of a priced path: every hop moves a
+    /// non-zero amount with headroom for intermediate math, minimums do not
+    /// exceed expected outputs, and amounts chain from hop to hop
+    pub fn validate_amounts(&self) -> Result<()> {
+        let invalid = |message: String| Err(BotError::InvalidState(format!("Path {}: {}", self.path_id, message)));
+        let (Some(first), Some(last)) = (self.hops.first(), self.hops.last()) else {
+            return invalid("no hops".into());
+        };
+        
+        for (i, hop) in self.hops.iter().enumerate() {
+            if hop.amount_in == 0 || hop.amount_in > u64::MAX / 2 {
+                return invalid(format!("hop {} amount in {} is out of range", i, hop.amount_in));
+            }
+            if hop.min_amount_out == 0 {
+                return invalid(format!("hop {} has no minimum output", i));
+            }
+            if hop.expected_amount_out < hop.min_amount_out {
+                return invalid(format!(
+                    "hop {} expects {} but requires at least {}", i, hop.expected_amount_out, hop.min_amount_out
+                ));
+            }
+        }
+        
+        // Allow one unit of rounding between a hop's output and the next input
+        for (i, pair) in self.hops.windows(2).enumerate() {
+            if pair[0].expected_amount_out.abs_diff(pair[1].amount_in) > 1 {
+                return invalid(format!(
+                    "hop {} outputs {} but hop {} takes {}", i, pair[0].expected_amount_out, i + 1, pair[1].amount_in
+                ));
+            }
+        }
+        
+        if self.initial_amount != first.amount_in {
+            return invalid(format!("initial amount {} differs from first hop input {}", self.initial_amount, first.amount_in));
+        }
+        if self.expected_final_amount != last.expected_amount_out {
+            return invalid(format!(
+                "final amount {} differs from last hop output {}", self.expected_final_amount, last.expected_amount_out
+            ));
+        }
+        Ok(())
+    }
 }
 
 /// Arbitrage opportunity (profitable path)
@@ -170,12 +287,50 @@ pub struct ArbitrageOpportunity {
     pub discovered_at: Timestamp,
 }
 
+/// Path data older than this gets zero freshness confidence
+pub const CONFIDENCE_STALE_AFTER_MS: u64 = 5_000;
+
+/// Price impact (percent) at which impact/depth confidence reaches zero
+pub const CONFIDENCE_MAX_IMPACT_PERCENT: Decimal = Decimal::from_parts(5, 0, 0, false, 0);
+
 impl ArbitrageOpportunity {
     /// Check if opportunity is still profitable
     pub fn is_profitable(&self, min_profit: Decimal) -> bool {
         self.net_profit >= min_profit
     }
     
+    /// Timestamp after which the opportunity is considered expired
+    pub fn expires_at(&self, max_age_ms: u64) -> Timestamp {
+        self.discovered_at + max_age_ms
+    }
+    
+    /// Milliseconds left before expiry, `None` if already expired
+    pub fn time_remaining_ms(&self, max_age_ms: u64) -> Option<u64> {
+        self.expires_at(max_age_ms).checked_sub(now()).filter(|remaining| *remaining > 0)
+    }
+    
+    /// Confidence (0.0-1.0) that the opportunity is real and executable.
+    /// Averages three scores: freshness of the pool data, average price
+    /// impact across hops, and depth of the shallowest pool (worst hop
+    /// impact) relative to the trade size.
+    pub fn confidence_score(&self) -> f64 {
+        let age_ms = now().saturating_sub(self.path.calculated_at);
+        let freshness = 1.0 - (age_ms as f64 / CONFIDENCE_STALE_AFTER_MS as f64).min(1.0);
+        
+        let impacts: Vec<Decimal> = self.path.hops.iter().map(|hop| hop.price_impact.abs()).collect();
+        if impacts.is_empty() {
+            return 0.0;
+        }
+        
+        let avg_impact = impacts.iter().sum::<Decimal>() / Decimal::from(impacts.len());
+        let max_impact = impacts.iter().copied().max().unwrap_or(Decimal::ZERO);
+        
+        let impact_score = impact_confidence(avg_impact);
+        let depth_score = impact_confidence(max_impact);
+        
+        ((freshness + impact_score + depth_score) / 3.0).clamp(0.0, 1.0)
+    }
+    
     /// Pretty print summary
     pub fn summary(&self) -> String {
         format!(
@@ -197,6 +352,12 @@ impl ArbitrageOpportunity {
     }
 }
 
+/// Map a price impact percentage to a 0.0-1.0 score (lower impact = higher)
+fn impact_confidence(impact_percent: Decimal) -> f64 {
+    let ratio = (impact_percent / CONFIDENCE_MAX_IMPACT_PERCENT).min(Decimal::ONE);
+    (Decimal::ONE - ratio).to_f64().unwrap_or(0.0)
+}
+
 #[cfg(test)]
 mod tests {
     use rust_decimal::Decimal;
@@ -277,4 +438,80 @@ mod tests {
         println!("Path: {}", path.path_description());
         println!("Profit: {:.2}%", path.profit_percent());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_total_fees_below_profit_for_profitable_path() {
+        let opportunity = crate::testing::sample_opportunity();
+        let path = &opportunity.path;
+
+        // Two hops at 0.3% on 1 SUI
+        assert_eq!(path.total_fees(), Decimal::new(6, 3));
+        assert!(path.gross_profit_decimal() > Decimal::ZERO);
+        assert!(path.total_fees() < path.gross_profit_decimal());
+    }
+
+    #[test]
+    fn test_total_fees_converts_each_hop_input() {
+        let mut path = crate::testing::sample_opportunity().path;
+        // 1 SUI buys 2 USDC, then only 1 of those USDC is sold back
+        path.hops[1].amount_in = 1_000_000;
+        path.hops[1].expected_amount_out = 505_000_000;
+
+        // 0.3% of 1 SUI, plus 0.3% of 1 USDC at 0.5 SUI per USDC
+        assert_eq!(path.total_fees(), Decimal::new(45, 4));
+    }
+
+    #[test]
+    fn test_with_optimal_amounts_chains_hops() -> Result<()> {
+        let path = crate::testing::sample_opportunity().path;
+        let (sui, usdc) = (&path.start_token, &path.hops[0].token_out);
+        let pools: HashMap<PoolId, PoolState> = [
+            crate::testing::test_pool("0xpool1", DexId::Cetus, sui, usdc, 1_000, 2_000),
+            crate::testing::test_pool("0xpool2", DexId::Turbos, sui, usdc, 1_000, 1_900),
+        ]
+        .into_iter()
+        .map(|pool| (pool.pool_id.clone(), pool))
+        .collect();
+
+        let priced = path.with_optimal_amounts(1_000_000_000, &pools, Decimal::ONE)?;
+
+        assert_eq!(priced.hops[0].amount_in, 1_000_000_000);
+        assert_eq!(priced.hops[1].amount_in, priced.hops[0].expected_amount_out);
+        assert_eq!(priced.expected_final_amount, priced.hops[1].expected_amount_out);
+        assert!(priced.expected_final_amount > priced.initial_amount);
+        for hop in &priced.hops {
+            assert_eq!(hop.min_amount_out, apply_slippage_tolerance(hop.expected_amount_out, Decimal::ONE));
+            assert!(hop.price_impact > Decimal::ZERO);
+        }
+
+        assert!(priced.validate_amounts().is_ok());
+
+        let mut missing = pools.clone();
+        missing.remove("0xpool2");
+        assert!(matches!(
+            path.with_optimal_amounts(1_000_000_000, &missing, Decimal::ONE),
+            Err(BotError::InsufficientLiquidity { pool_id }) if pool_id == "0xpool2"
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_amounts_rejects_inconsistent_paths() {
+        let path = crate::testing::sample_opportunity().path;
+        assert!(path.validate_amounts().is_ok());
+
+        let broken = |edit: fn(&mut ArbitragePath)| {
+            let mut path = path.clone();
+            edit(&mut path);
+            path.validate_amounts()
+        };
+        assert!(broken(|path| path.hops[0].amount_in = 0).is_err());
+        assert!(broken(|path| path.hops[0].amount_in = u64::MAX).is_err());
+        assert!(broken(|path| path.hops[1].min_amount_out = 0).is_err());
+        assert!(broken(|path| path.hops[1].min_amount_out = path.hops[1].expected_amount_out + 1).is_err());
+        assert!(broken(|path| path.hops[1].amount_in += 2).is_err());
+        assert!(broken(|path| path.hops[1].amount_in += 1).is_ok());
+        assert!(broken(|path| path.initial_amount += 1).is_err());
+        assert!(broken(|path| path.expected_final_amount += 1).is_err());
+    }
+}
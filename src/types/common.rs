@@ -1,9 +1,14 @@
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
 use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::str::FromStr;
 use rust_decimal::prelude::ToPrimitive;
 
+use crate::dex::{cetus, kriya};
+use crate::types::{BotError, Result};
+
 pub type Timestamp = u64;
 pub type PoolId = String;  
 
@@ -14,7 +19,6 @@ pub fn now() -> Timestamp {
 /// Supported DEX identifiers
 /// 
 /// TODO Turbos
-/// TODO Aftermath
 /// TODO Kriya
 /// TODO FlowX
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -22,6 +26,7 @@ pub enum DexId {
     Cetus,
     Turbos,
     Kriya,
+    Aftermath,
 }
 
 impl DexId {
@@ -30,6 +35,7 @@ impl DexId {
             DexId::Cetus,
             DexId::Turbos,
             DexId::Kriya,
+            DexId::Aftermath,
         ]
     }
 
@@ -38,8 +44,28 @@ impl DexId {
             DexId::Cetus => "Cetus",
             DexId::Turbos => "Turbos",
             DexId::Kriya => "Kriya",
+            DexId::Aftermath => "Aftermath",
+        }
+    }
+
+    /// Known package ID for this DEX on the given network
+    pub fn package_id(&self, network: Network) -> Option<&'static str> {
+        match (self, network) {
+            (DexId::Cetus, Network::SuiMainnet) => Some(cetus::CETUS_PACKAGE_ID),
+            (DexId::Cetus, Network::SuiTestnet) => Some(cetus::CETUS_TESTNET_PACKAGE_ID),
+            (DexId::Kriya, Network::SuiMainnet) => Some(kriya::KRIYA_PACKAGE_ID),
+            _ => None,
         }
     }
+
+    /// Identify the DEX that owns a package on the given network
+    pub fn from_package_id(package_id: &str, network: Network) -> Option<DexId> {
+        DexId::all().into_iter().find(|dex_id| {
+            dex_id
+                .package_id(network)
+                .is_some_and(|known| known.eq_ignore_ascii_case(package_id))
+        })
+    }
 }
 
 impl fmt::Display for DexId {
@@ -54,6 +80,9 @@ impl FromStr for DexId {
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
         match s.to_lowercase().as_str() {
             "cetus" => Ok(DexId::Cetus),
+            "turbos" => Ok(DexId::Turbos),
+            "kriya" => Ok(DexId::Kriya),
+            "aftermath" => Ok(DexId::Aftermath),
             _ => Err(format!("Unknown DEX: {}", s)),
         }
     }
@@ -78,6 +107,42 @@ impl Network {
     pub fn is_mainnet(&self) -> bool {
         matches!(self, Network::SuiMainnet)
     }
+
+    /// Default public fullnode RPC endpoint
+    pub fn rpc_url(&self) -> &'static str {
+        match self {
+            Network::SuiTestnet => "https://fullnode.testnet.sui.io:443",
+            Network::SuiMainnet => "https://fullnode.mainnet.sui.io:443",
+        }
+    }
+
+    /// Default public fullnode WebSocket endpoint
+    pub fn ws_url(&self) -> &'static str {
+        match self {
+            Network::SuiTestnet => "wss://fullnode.testnet.sui.io:443",
+            Network::SuiMainnet => "wss://fullnode.mainnet.sui.io:443",
+        }
+    }
+
+    /// Reverse lookup of a known public endpoint (RPC or WebSocket)
+    pub fn from_rpc_url(url: &str) -> Option<Network> {
+        let url = url.to_lowercase();
+        if url.contains("fullnode.mainnet.sui.io") {
+            Some(Network::SuiMainnet)
+        } else if url.contains("fullnode.testnet.sui.io") {
+            Some(Network::SuiTestnet)
+        } else {
+            None
+        }
+    }
+
+    /// Published chain identifier (`sui_getChainIdentifier`) as an integer
+    pub fn chain_id(&self) -> u64 {
+        match self {
+            Network::SuiTestnet => 0x4c78adac,
+            Network::SuiMainnet => 0x35834a8a,
+        }
+    }
 }
 
 impl fmt::Display for Network {
@@ -93,6 +158,12 @@ impl fmt::Display for Network {
 // Token Information
 // ============================================================================
 
+/// Coin type of the native SUI token, which pays for gas
+pub const SUI_COIN_TYPE: &str = "0x2::sui::SUI";
+
+/// Symbols of tokens pegged to the US dollar
+pub const USD_STABLECOIN_SYMBOLS: [&str; 5] = ["USDC", "USDT", "WUSDC", "WUSDT", "AUSD"];
+
 /// Token metadata
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct TokenInfo {
@@ -114,11 +185,30 @@ impl TokenInfo {
         }
     }
 
+    /// The native SUI token
+    pub fn sui() -> Self {
+        Self::new("SUI", SUI_COIN_TYPE, 9)
+    }
+
+    /// Check if the token is a USD stablecoin, judged by its symbol
+    pub fn is_usd_stablecoin(&self) -> bool {
+        USD_STABLECOIN_SYMBOLS.iter().any(|symbol| self.symbol.eq_ignore_ascii_case(symbol))
+    }
+
     pub fn with_name(mut self, name: impl Into<String>) -> Self {
         self.name = Some(name.into());
         self
     }
 
+    /// Check if both refer to the same token, comparing normalized addresses
+    /// and falling back to the symbol when either address is unknown
+    pub fn matches(&self, other: &TokenInfo) -> bool {
+        match (&self.address, &other.address) {
+            (Some(a), Some(b)) => normalize_address(a) == normalize_address(b),
+            _ => self.symbol == other.symbol,
+        }
+    }
+
     /// Convert raw amount to decimal
     pub fn to_decimal(&self, raw_amount: u64) -> Decimal {
         Decimal::from(raw_amount) / Decimal::from(10u64.pow(self.decimals as u32))
@@ -132,6 +222,16 @@ impl TokenInfo {
     }
 }
 
+/// Lowercase an address and make sure it carries the `0x` prefix
+pub fn normalize_address(address: &str) -> String {
+    let address = address.trim().to_lowercase();
+    if address.starts_with("0x") {
+        address
+    } else {
+        format!("0x{}", address)
+    }
+}
+
 impl fmt::Display for TokenInfo {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.symbol)
@@ -159,13 +259,42 @@ impl TokenPair {
         format!("{}/{}", self.base.symbol, self.quote.symbol)
     }
 
+    /// Same pair with the lexicographically smaller address as base, so
+    /// SUI/USDC and USDC/SUI share one key
+    pub fn canonical(&self) -> TokenPair {
+        if self.is_canonical() {
+            self.clone()
+        } else {
+            Self::new(self.quote.clone(), self.base.clone())
+        }
+    }
+
+    /// Whether base already sorts before quote
+    pub fn is_canonical(&self) -> bool {
+        sort_key(&self.base) <= sort_key(&self.quote)
+    }
+
+    /// Order-independent hash of the two token identities
+    pub fn canonical_hash(&self) -> u64 {
+        let mut keys = [sort_key(&self.base), sort_key(&self.quote)];
+        keys.sort();
+        let mut hasher = DefaultHasher::new();
+        keys.hash(&mut hasher);
+        hasher.finish()
+    }
+
     /// Check if tokens match (order-independent)
     pub fn matches(&self, other: &TokenPair) -> bool {
-        (self.base == other.base && self.quote == other.quote)
-            || (self.base == other.quote && self.quote == other.base)
+        let (this, other) = (self.canonical(), other.canonical());
+        this.base.matches(&other.base) && this.quote.matches(&other.quote)
     }
 }
 
+/// Normalized address, or the symbol for tokens without one
+fn sort_key(token: &TokenInfo) -> String {
+    token.address.as_deref().map_or_else(|| token.symbol.clone(), normalize_address)
+}
+
 impl fmt::Display for TokenPair {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.symbol())
@@ -199,15 +328,47 @@ impl TokenAmount {
         self.token.to_decimal(self.raw_amount)
     }
 
-    /// Get human-readable string (eg "10.5 SUI")
-    pub fn to_string(&self) -> String {
-        format!("{} {}", self.to_decimal(), self.token.symbol)
+    /// Add an amount of the same token
+    pub fn add(&self, other: &TokenAmount) -> Result<TokenAmount> {
+        self.ensure_same_token(other)?;
+        let raw_amount = self.raw_amount
+            .checked_add(other.raw_amount)
+            .ok_or_else(|| BotError::InvalidState(format!("Overflow adding {} to {}", other, self)))?;
+        Ok(Self::new(self.token.clone(), raw_amount))
+    }
+
+    /// Subtract an amount of the same token
+    pub fn sub(&self, other: &TokenAmount) -> Result<TokenAmount> {
+        self.ensure_same_token(other)?;
+        let raw_amount = self.raw_amount
+            .checked_sub(other.raw_amount)
+            .ok_or_else(|| BotError::InvalidState(format!("Underflow subtracting {} from {}", other, self)))?;
+        Ok(Self::new(self.token.clone(), raw_amount))
+    }
+
+    /// Scale the amount by a factor, rounding down
+    pub fn mul_decimal(&self, factor: Decimal) -> TokenAmount {
+        let raw_amount = (Decimal::from(self.raw_amount) * factor)
+            .floor()
+            .to_u64()
+            .unwrap_or(0);
+        Self::new(self.token.clone(), raw_amount)
+    }
+
+    fn ensure_same_token(&self, other: &TokenAmount) -> Result<()> {
+        if !self.token.matches(&other.token) {
+            return Err(BotError::InvalidState(format!(
+                "Token mismatch: {} vs {}", self.token, other.token
+            )));
+        }
+        Ok(())
     }
 }
 
+/// Human-readable form (eg "10.5 SUI")
 impl fmt::Display for TokenAmount {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.to_string())
+        write!(f, "{} {}", self.to_decimal(), self.token.symbol)
     }
 }
 
@@ -240,6 +401,14 @@ impl Price {
         }
         ((other.value - self.value) / self.value) * Decimal::from(100)
     }
+
+    /// Price of the reversed pair, `None` when the value is zero
+    pub fn inverse(&self) -> Option<Price> {
+        (!self.value.is_zero()).then(|| Price {
+            value: Decimal::ONE / self.value,
+            ..self.clone()
+        })
+    }
 }
 
 /// Source of price information
@@ -256,4 +425,108 @@ pub enum PriceSource {
     External {
         source: String,
     },
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dex_id_parses_its_name() {
+        for dex_id in DexId::all() {
+            assert_eq!(dex_id.name().parse::<DexId>(), Ok(dex_id));
+        }
+        assert!("uniswap".parse::<DexId>().is_err());
+    }
+
+    #[test]
+    fn test_token_matches_normalized_address() {
+        let sui = TokenInfo::new("SUI", "0x2::sui::SUI", 9);
+        let lower = TokenInfo::new("SUI", "0x2::sui::sui", 9);
+        let unprefixed = TokenInfo::new("SUI", "2::SUI::SUI", 9);
+        let usdc = TokenInfo::new("USDC", "0x5d4b::coin::COIN", 6);
+
+        assert!(sui.matches(&lower));
+        assert!(sui.matches(&unprefixed));
+        assert!(!sui.matches(&usdc));
+    }
+
+    #[test]
+    fn test_token_matches_falls_back_to_symbol() {
+        let sui = TokenInfo::new("SUI", "0x2::sui::SUI", 9);
+        let no_address = TokenInfo {
+            symbol: "SUI".into(),
+            address: None,
+            decimals: 9,
+            name: None,
+        };
+
+        assert!(sui.matches(&no_address));
+        assert!(!no_address.matches(&TokenInfo { symbol: "USDC".into(), ..no_address.clone() }));
+    }
+
+    #[test]
+    fn test_pair_matches_either_order() {
+        let sui = TokenInfo::new("SUI", "0x2::sui::SUI", 9);
+        let usdc = TokenInfo::new("USDC", "0x5D4B::coin::COIN", 6);
+        let usdc_lower = TokenInfo::new("USDC", "5d4b::coin::coin", 6);
+
+        let pair = TokenPair::new(sui.clone(), usdc);
+        let reversed = TokenPair::new(usdc_lower, sui);
+
+        assert!(pair.matches(&reversed));
+    }
+
+    #[test]
+    fn test_pair_canonical_ignores_order() {
+        let sui = TokenInfo::new("SUI", "0x2::sui::SUI", 9);
+        let usdc = TokenInfo::new("USDC", "0x5d4b::coin::COIN", 6);
+
+        let pair = TokenPair::new(sui.clone(), usdc.clone());
+        let reversed = TokenPair::new(usdc, sui.clone());
+
+        assert!(pair.is_canonical());
+        assert!(!reversed.is_canonical());
+        assert_eq!(reversed.canonical(), pair);
+        assert_eq!(reversed.canonical().base, sui);
+        assert_eq!(pair.canonical_hash(), reversed.canonical_hash());
+    }
+
+    #[test]
+    fn test_price_inverse() {
+        let price = Price::new(Decimal::from(4), PriceSource::Calculated);
+
+        assert_eq!(price.inverse().map(|price| price.value), Some(Decimal::new(25, 2)));
+        assert!(Price::new(Decimal::ZERO, PriceSource::Calculated).inverse().is_none());
+    }
+
+    #[test]
+    fn test_token_amount_arithmetic() {
+        let sui = TokenInfo::new("SUI", "0x2::sui::SUI", 9);
+        let a = TokenAmount::new(sui.clone(), 1_500);
+        let b = TokenAmount::new(sui.clone(), 500);
+
+        assert_eq!(a.add(&b).map(|x| x.raw_amount).ok(), Some(2_000));
+        assert_eq!(a.sub(&b).map(|x| x.raw_amount).ok(), Some(1_000));
+        assert_eq!(a.mul_decimal(Decimal::from_parts(5, 0, 0, false, 1)).raw_amount, 750);
+    }
+
+    #[test]
+    fn test_token_amount_overflow_and_underflow() {
+        let sui = TokenInfo::new("SUI", "0x2::sui::SUI", 9);
+        let max = TokenAmount::new(sui.clone(), u64::MAX);
+        let one = TokenAmount::new(sui.clone(), 1);
+
+        assert!(max.add(&one).is_err());
+        assert!(one.sub(&max).is_err());
+    }
+
+    #[test]
+    fn test_token_amount_cross_token() {
+        let sui = TokenAmount::new(TokenInfo::new("SUI", "0x2::sui::SUI", 9), 1);
+        let usdc = TokenAmount::new(TokenInfo::new("USDC", "0x5d4b::coin::COIN", 6), 1);
+
+        assert!(sui.add(&usdc).is_err());
+        assert!(sui.sub(&usdc).is_err());
+    }
+}
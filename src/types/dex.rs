@@ -94,6 +94,47 @@ impl HealthStatus {
             details: None,
         }
     }
+    
+    /// Combine per-DEX statuses into one: healthy only if every DEX is,
+    /// with the worst failure count and summed pool counts
+    pub fn aggregate(statuses: &[(DexId, HealthStatus)]) -> HealthStatus {
+        let unhealthy: Vec<String> = statuses
+            .iter()
+            .filter(|(_, status)| !status.is_healthy)
+            .map(|(dex_id, _)| dex_id.to_string())
+            .collect();
+        
+        let message = if unhealthy.is_empty() {
+            "All DEXes healthy".to_string()
+        } else {
+            unhealthy.join(", ")
+        };
+        
+        let details: Vec<&HealthDetails> = statuses
+            .iter()
+            .filter_map(|(_, status)| status.details.as_ref())
+            .collect();
+        let response_times: Vec<u64> = details
+            .iter()
+            .filter_map(|details| details.avg_response_time_ms)
+            .collect();
+        
+        HealthStatus {
+            is_healthy: unhealthy.is_empty(),
+            last_event: statuses.iter().filter_map(|(_, status)| status.last_event).max(),
+            last_heartbeat: statuses.iter().map(|(_, status)| status.last_heartbeat).min().unwrap_or_else(now),
+            consecutive_failures: statuses.iter().map(|(_, status)| status.consecutive_failures).max().unwrap_or(0),
+            message,
+            details: (!details.is_empty()).then(|| HealthDetails {
+                last_error: details.iter().find_map(|details| details.last_error.clone()),
+                time_since_last_success_ms: details.iter().map(|details| details.time_since_last_success_ms).max().unwrap_or(0),
+                pools_monitored: details.iter().map(|details| details.pools_monitored).sum(),
+                stale_pools: details.iter().map(|details| details.stale_pools).sum(),
+                avg_response_time_ms: (!response_times.is_empty())
+                    .then(|| response_times.iter().sum::<u64>() / response_times.len() as u64),
+            }),
+        }
+    }
 }
 
 /// Additional health details
@@ -162,6 +203,8 @@ pub struct RawEvent {
     pub package_id: String,
     pub event_type: String,
     pub transaction_digest: Option<String>,
+    /// Position of the event within its transaction
+    pub event_seq: Option<String>,
     pub sender: Option<String>,
 }
 
@@ -177,6 +220,7 @@ impl RawEvent {
             package_id,
             event_type,
             transaction_digest: None,
+            event_seq: None,
             sender: None,
         }
     }
@@ -210,6 +254,11 @@ pub struct SwapEvent {
     pub sender: Option<String>,
     pub block_height: Option<u64>,
     pub sequence: Option<u64>,
+    /// Fee charged on the input amount, when the DEX reports it
+    pub fee_amount: Option<u64>,
+    /// CLMM square root prices (Q64.64) around the swap
+    pub sqrt_price_before: Option<u128>,
+    pub sqrt_price_after: Option<u128>,
 }
 
 impl SwapEvent {
@@ -408,4 +457,27 @@ mod tests {
         assert!(update.is_increase());
         assert_eq!(update.price_change_percent, Decimal::from(5)); // 5% increase
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_health_status_aggregate() {
+        let mut cetus = HealthStatus::healthy("ok");
+        cetus.details = Some(HealthDetails {
+            last_error: None,
+            time_since_last_success_ms: 100,
+            pools_monitored: 3,
+            stale_pools: 1,
+            avg_response_time_ms: Some(40),
+        });
+        let turbos = HealthStatus::unhealthy("timeout", 4);
+        
+        let all_healthy = HealthStatus::aggregate(&[(DexId::Cetus, cetus.clone())]);
+        assert!(all_healthy.is_healthy);
+        assert_eq!(all_healthy.message, "All DEXes healthy");
+        
+        let mixed = HealthStatus::aggregate(&[(DexId::Cetus, cetus), (DexId::Turbos, turbos)]);
+        assert!(!mixed.is_healthy);
+        assert_eq!(mixed.consecutive_failures, 4);
+        assert_eq!(mixed.message, DexId::Turbos.to_string());
+        assert_eq!(mixed.details.map(|details| details.pools_monitored), Some(3));
+    }
+}
@@ -1,4 +1,5 @@
 use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -19,6 +20,10 @@ pub struct ExecutionResult {
     /// Actual amounts at each hop (may differ from expected due to slippage)
     pub actual_amounts: HashMap<usize, u64>, // hop_index -> actual_amount
     
+    /// Realized amounts and costs per hop, in hop order
+    #[serde(default)]
+    pub hop_profits: Vec<HopProfit>,
+    
     /// Gas used for the transaction
     pub gas_used: u64,
     
@@ -50,6 +55,7 @@ impl ExecutionResult {
             status: ExecutionStatus::Pending,
             transaction_digest: None,
             actual_amounts: HashMap::new(),
+            hop_profits: Vec::new(),
             gas_used: 0,
             actual_profit: Decimal::ZERO,
             execution_duration_ms: 0,
@@ -119,8 +125,116 @@ impl ExecutionResult {
         (self.actual_profit / initial_decimal) * Decimal::from(100)
     }
     
+    /// Worst per-hop slippage in percent: `(expected - actual) / expected * 100`.
+    /// Hops without a recorded actual amount are ignored.
+    pub fn slippage_realized(&self) -> Decimal {
+        self.opportunity.path.hops
+            .iter()
+            .enumerate()
+            .filter(|(_, hop)| hop.expected_amount_out > 0)
+            .filter_map(|(index, hop)| {
+                let actual = Decimal::from(*self.actual_amounts.get(&index)?);
+                let expected = Decimal::from(hop.expected_amount_out);
+                Some((expected - actual) / expected * Decimal::ONE_HUNDRED)
+            })
+            .max()
+            .unwrap_or(Decimal::ZERO)
+    }
+    
+    /// Fill `hop_profits` from the recorded actual amounts. Each hop spends
+    /// the previous hop's actual output; hops without one are left out.
+    pub fn record_hop_profits(&mut self) {
+        let path = &self.opportunity.path;
+        let mut amount_in = path.initial_amount;
+        self.hop_profits = Vec::with_capacity(path.hops.len());
+        for (hop_index, hop) in path.hops.iter().enumerate() {
+            let Some(&amount_out) = self.actual_amounts.get(&hop_index) else {
+                break;
+            };
+            let shortfall_bps = if hop.expected_amount_out == 0 {
+                0
+            } else {
+                hop.expected_amount_out.saturating_sub(amount_out) as u128 * 10_000 / hop.expected_amount_out as u128
+            };
+            self.hop_profits.push(HopProfit {
+                hop_index,
+                amount_in,
+                amount_out,
+                fee_paid: (Decimal::from(amount_in) * hop.fee_rate).floor().to_u64().unwrap_or(0),
+                price_impact_bps: shortfall_bps.min(u16::MAX as u128) as u16,
+            });
+            amount_in = amount_out;
+        }
+    }
+    
+    /// Hop whose output fell furthest below its quote
+    pub fn worst_hop(&self) -> Option<&HopProfit> {
+        self.hop_profits
+            .iter()
+            .max_by_key(|hop| hop.price_impact_bps)
+    }
+    
+    /// Swap fees paid across all hops in start token base units. Each fee
+    /// is paid in its hop's input token and converted at the rates the
+    /// earlier hops actually traded at.
+    pub fn total_fees_paid(&self) -> u64 {
+        // Start token units per unit of the current hop's input token
+        let mut rate = Decimal::ONE;
+        let mut total = Decimal::ZERO;
+        for hop in &self.hop_profits {
+            total += Decimal::from(hop.fee_paid) * rate;
+            if hop.amount_out == 0 {
+                break;
+            }
+            rate = rate * Decimal::from(hop.amount_in) / Decimal::from(hop.amount_out);
+        }
+        total.floor().to_u64().unwrap_or(0)
+    }
+    
+    /// Whether every hop delivered at least its minimum output
+    pub fn all_hops_succeeded(&self) -> bool {
+        self.opportunity.path.hops
+            .iter()
+            .enumerate()
+            .all(|(index, hop)| {
+                self.actual_amounts
+                    .get(&index)
+                    .is_some_and(|actual| *actual >= hop.min_amount_out)
+            })
+    }
+    
+    /// Serialize as a CSV row matching `EXECUTION_CSV_HEADER`
+    pub fn to_csv_row(&self) -> String {
+        [
+            self.completed_at.to_string(),
+            self.opportunity.path.path_id.clone(),
+            format!("{:?}", self.status),
+            self.opportunity.path.initial_amount.to_string(),
+            self.actual_profit.to_string(),
+            self.profit_percentage().round_dp(4).to_string(),
+            self.gas_used.to_string(),
+            self.execution_duration_ms.to_string(),
+            self.slippage_realized().round_dp(4).to_string(),
+            self.transaction_digest.clone().unwrap_or_default(),
+            self.error.clone().unwrap_or_default(),
+        ]
+        .iter()
+        .map(|field| csv_field(field))
+        .collect::<Vec<_>>()
+        .join(",")
+    }
+    
     /// Get execution summary
     pub fn summary(&self) -> String {
+        let summary = self.status_summary();
+        if self.hop_profits.is_empty() {
+            return summary;
+        }
+        let hops: Vec<String> = self.hop_profits.iter().map(HopProfit::summary).collect();
+        format!("{} | {}", summary, hops.join("; "))
+    }
+    
+    fn status_summary(&self) -> String {
         match self.status {
             ExecutionStatus::Success => {
                 format!(
@@ -155,6 +269,39 @@ impl ExecutionResult {
     }
 }
 
+/// What one hop of an executed path actually returned
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HopProfit {
+    pub hop_index: usize,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    /// Swap fee in the hop's input token
+    pub fee_paid: u64,
+    /// Shortfall of the actual output against the quoted output
+    pub price_impact_bps: u16,
+}
+
+impl HopProfit {
+    pub fn summary(&self) -> String {
+        format!(
+            "hop {}: {} -> {} (fee {}, impact {}bps)",
+            self.hop_index, self.amount_in, self.amount_out, self.fee_paid, self.price_impact_bps
+        )
+    }
+}
+
+/// Column names for `ExecutionResult::to_csv_row`
+pub const EXECUTION_CSV_HEADER: &str = "timestamp,path_id,status,initial_amount,actual_profit,profit_percent,gas_used,execution_duration_ms,slippage_realized,transaction_digest,error";
+
+/// Quote a CSV field when it contains a delimiter, quote or newline
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
 /// Execution status
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ExecutionStatus {
@@ -178,6 +325,8 @@ pub struct ExecutionStats {
     pub avg_execution_time_ms: u64,
     pub success_rate: f64,
     pub last_execution_time: Option<Timestamp>,
+    /// Executions skipped because the pre-trade simulation was unprofitable
+    pub simulation_rejections: u64,
 }
 
 impl ExecutionStats {
@@ -190,7 +339,28 @@ impl ExecutionStats {
             avg_execution_time_ms: 0,
             success_rate: 0.0,
             last_execution_time: None,
+            simulation_rejections: 0,
+        }
+    }
+}
+
+impl ExecutionStats {
+    /// Fold a finished execution into the running totals
+    pub fn record(&mut self, result: &ExecutionResult) {
+        self.total_executions += 1;
+        match result.status {
+            ExecutionStatus::Success | ExecutionStatus::Simulated => {
+                self.successful_executions += 1;
+                self.total_profit += result.actual_profit;
+            }
+            ExecutionStatus::Failed => self.failed_executions += 1,
+            ExecutionStatus::Pending => {}
         }
+        
+        let previous_total = self.avg_execution_time_ms * (self.total_executions - 1);
+        self.avg_execution_time_ms = (previous_total + result.execution_duration_ms) / self.total_executions;
+        self.success_rate = self.successful_executions as f64 / self.total_executions as f64;
+        self.last_execution_time = Some(result.completed_at);
     }
 }
 
@@ -198,4 +368,54 @@ impl Default for ExecutionStats {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::sample_opportunity;
+
+    #[test]
+    fn test_slippage_and_hop_success() {
+        let opportunity = sample_opportunity();
+        let expected_second_hop = opportunity.path.hops[1].expected_amount_out;
+        let amounts = HashMap::from([
+            (0, opportunity.path.hops[0].expected_amount_out),
+            (1, expected_second_hop / 100 * 98),
+        ]);
+
+        let result = ExecutionResult::new(opportunity).simulated(amounts, 0, Decimal::ZERO);
+        assert_eq!(result.slippage_realized(), Decimal::TWO);
+        assert!(!result.all_hops_succeeded());
+    }
+
+    #[test]
+    fn test_hop_profits_follow_actual_amounts() {
+        let opportunity = sample_opportunity();
+        let hops = opportunity.path.hops.clone();
+        let second_hop_out = hops[1].expected_amount_out / 100 * 98;
+        let amounts = HashMap::from([(0, hops[0].expected_amount_out), (1, second_hop_out)]);
+
+        let mut result = ExecutionResult::new(opportunity).simulated(amounts, 0, Decimal::ZERO);
+        result.record_hop_profits();
+
+        assert_eq!(result.hop_profits.len(), 2);
+        assert_eq!(result.hop_profits[1].amount_in, hops[0].expected_amount_out);
+        assert_eq!(result.worst_hop().map(|hop| (hop.hop_index, hop.price_impact_bps)), Some((1, 200)));
+        // 0.3% of 1 SUI, plus 0.3% of 2 USDC worth 1 SUI
+        assert_eq!(result.hop_profits[1].fee_paid, 6_000);
+        assert_eq!(result.total_fees_paid(), 6_000_000);
+        assert!(result.summary().contains("hop 1:"));
+    }
+
+    #[test]
+    fn test_csv_row_quotes_errors() {
+        let result = ExecutionResult::new(sample_opportunity())
+            .failure(BotError::Execution("gas, too low".into()));
+        let row = result.to_csv_row();
+
+        assert_eq!(EXECUTION_CSV_HEADER.split(',').count(), 11);
+        assert!(row.contains(",sample,Failed,"));
+        assert!(row.ends_with("\"Execution error: gas, too low\""));
+    }
+}
@@ -1,7 +1,10 @@
+use std::collections::HashMap;
+
 use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
 use serde::{Deserialize, Serialize};
 
-use crate::types::{DexId, PoolId, Timestamp, TokenInfo};
+use crate::{types::{BotError, DexId, PRICE_CHANGE_NOISE_PERCENT, PoolId, Result, SwapEvent, Timestamp, TokenInfo}, utils::math::BPS_DENOMINATOR};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PoolState {
@@ -13,6 +16,19 @@ pub struct PoolState {
     pub reserve_b: Decimal,
     pub fee_rate: Decimal,
     pub block_timestamp: Timestamp,
+    /// Whether the pool accepts swaps (false when paused on-chain)
+    pub is_active: bool,
+    /// Sui object version the state was read at
+    pub version: u64,
+    /// CLMM square root price as Q64.64 (zero for constant-product pools)
+    #[serde(default)]
+    pub sqrt_price_x64: u128,
+    /// CLMM tick the pool currently trades in
+    #[serde(default)]
+    pub current_tick_index: i32,
+    /// Stable-swap amplification, `None` for non-stable pools
+    #[serde(default)]
+    pub amp_factor: Option<u64>,
 }
 
 impl PoolState {
@@ -27,4 +43,252 @@ impl PoolState {
     pub fn constant_product(&self) -> Decimal {
         self.reserve_a * self.reserve_b
     }
+
+    /// Raw `(reserve_in, reserve_out, fee_bps)` for a swap, selling token A
+    /// when `a_to_b`. Reserves that do not fit a `u64` read as zero.
+    pub fn swap_params(&self, a_to_b: bool) -> (u64, u64, u32) {
+        let (reserve_in, reserve_out) = if a_to_b {
+            (self.reserve_a, self.reserve_b)
+        } else {
+            (self.reserve_b, self.reserve_a)
+        };
+        let fee_bps = (self.fee_rate * Decimal::from(BPS_DENOMINATOR)).round().to_u32().unwrap_or(0);
+
+        (reserve_in.to_u64().unwrap_or(0), reserve_out.to_u64().unwrap_or(0), fee_bps)
+    }
+
+    /// Check if the pool has ever received liquidity
+    pub fn is_initialized(&self) -> bool {
+        !(self.reserve_a.is_zero() && self.reserve_b.is_zero())
+    }
+
+    /// Check if the pool is currently accepting swaps
+    pub fn is_active(&self) -> bool {
+        self.is_active
+    }
+
+    /// USD value of both reserves. `token_prices` is keyed by token address
+    /// (or symbol when the token has no address); unpriced tokens count as zero.
+    pub fn total_value_locked_usd(&self, token_prices: &HashMap<String, Decimal>) -> Decimal {
+        reserve_value_usd(&self.token_a, self.reserve_a, token_prices)
+            + reserve_value_usd(&self.token_b, self.reserve_b, token_prices)
+    }
+
+    /// Reserve A at the pool's spot price, in whole units of token B
+    pub fn quote_value(&self) -> Decimal {
+        if self.reserve_a.is_zero() {
+            return Decimal::ZERO;
+        }
+        self.reserve_a * self.spot_price_a_to_b() / Decimal::from(10u64.pow(self.token_b.decimals as u32))
+    }
+
+    /// USD liquidity, falling back to `quote_value` when neither token is priced
+    pub fn liquidity_usd(&self, token_prices: &HashMap<String, Decimal>) -> Decimal {
+        let tvl = self.total_value_locked_usd(token_prices);
+        if tvl.is_zero() { self.quote_value() } else { tvl }
+    }
+
+    /// Apply a swap's token flows to the reserves. The input side grows by
+    /// the full amount in, since the fee stays in the pool.
+    pub fn apply_swap(&mut self, event: &SwapEvent) -> Result<()> {
+        let amount_in = Decimal::from(event.amount_in);
+        let amount_out = Decimal::from(event.amount_out);
+        let (reserve_in, reserve_out) = if event.base_to_quote {
+            (&mut self.reserve_a, &mut self.reserve_b)
+        } else {
+            (&mut self.reserve_b, &mut self.reserve_a)
+        };
+
+        if amount_out >= *reserve_out {
+            return Err(BotError::InvalidState(format!(
+                "Swap in {} takes {} out of a reserve of {}", event.transaction_digest, amount_out, reserve_out
+            )));
+        }
+        *reserve_in += amount_in;
+        *reserve_out -= amount_out;
+
+        if let Some(sqrt_price) = event.sqrt_price_after {
+            self.sqrt_price_x64 = sqrt_price;
+        }
+        self.block_timestamp = event.timestamp;
+        Ok(())
+    }
+
+    /// Describe how this state differs from `previous` (if any)
+    pub fn delta_from(&self, previous: Option<&PoolState>) -> PoolStateDelta {
+        let (old_reserve_a, old_reserve_b) = previous
+            .map(|pool| (pool.reserve_a, pool.reserve_b))
+            .unwrap_or((Decimal::ZERO, Decimal::ZERO));
+        let old_price = price_or_zero(old_reserve_a, old_reserve_b);
+        let new_price = price_or_zero(self.reserve_a, self.reserve_b);
+        let price_change_percent = (new_price - old_price)
+            .checked_div(old_price)
+            .map(|change| change * Decimal::ONE_HUNDRED)
+            .unwrap_or(Decimal::ZERO);
+
+        PoolStateDelta {
+            pool_id: self.pool_id.clone(),
+            dex_id: self.dex_id,
+            old_reserve_a,
+            new_reserve_a: self.reserve_a,
+            old_reserve_b,
+            new_reserve_b: self.reserve_b,
+            old_price,
+            new_price,
+            price_change_percent,
+            changed_at: self.block_timestamp,
+        }
+    }
+
+    /// Largest input amount (raw units) that keeps the post-trade spot price
+    /// of the output token at or above `target_price`. Capped at half the input reserve.
+    pub fn depth_at_price(&self, target_price: Decimal, side: Side) -> u64 {
+        let (reserve_in, reserve_out) = match side {
+            Side::AToB => (self.reserve_a, self.reserve_b),
+            Side::BToA => (self.reserve_b, self.reserve_a),
+        };
+        if reserve_in.is_zero() || reserve_out.is_zero() {
+            return 0;
+        }
+
+        let price_after = |amount_in: u64| -> Decimal {
+            let amount_in = Decimal::from(amount_in);
+            let amount_in_with_fee = amount_in * (Decimal::ONE - self.fee_rate);
+            let amount_out = reserve_out * amount_in_with_fee / (reserve_in + amount_in_with_fee);
+            (reserve_out - amount_out) / (reserve_in + amount_in)
+        };
+
+        let mut low = 0u64;
+        let mut high = (reserve_in / Decimal::TWO).to_u64().unwrap_or(u64::MAX);
+        if price_after(low) < target_price {
+            return 0;
+        }
+        while low < high {
+            let mid = low + (high - low).div_ceil(2);
+            if price_after(mid) >= target_price {
+                low = mid;
+            } else {
+                high = mid - 1;
+            }
+        }
+        low
+    }
+}
+
+/// Change in a pool's reserves and A->B price between two states
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PoolStateDelta {
+    pub pool_id: PoolId,
+    pub dex_id: DexId,
+    pub old_reserve_a: Decimal,
+    pub new_reserve_a: Decimal,
+    pub old_reserve_b: Decimal,
+    pub new_reserve_b: Decimal,
+    pub old_price: Decimal,
+    pub new_price: Decimal,
+    pub price_change_percent: Decimal,
+    pub changed_at: Timestamp,
+}
+
+impl PoolStateDelta {
+    /// Whether the change can affect arbitrage paths: the pool had no
+    /// price before, or its price moved by more than the noise threshold
+    pub fn is_significant(&self) -> bool {
+        self.old_price.is_zero() || self.price_change_percent.abs() >= PRICE_CHANGE_NOISE_PERCENT
+    }
+}
+
+fn price_or_zero(reserve_a: Decimal, reserve_b: Decimal) -> Decimal {
+    reserve_b.checked_div(reserve_a).unwrap_or(Decimal::ZERO)
+}
+
+/// Swap direction through a pool
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Side {
+    AToB,
+    BToA,
+}
+
+fn reserve_value_usd(token: &TokenInfo, reserve: Decimal, token_prices: &HashMap<String, Decimal>) -> Decimal {
+    let key = token.address.as_deref().unwrap_or(&token.symbol);
+    let Some(price) = token_prices.get(key) else {
+        return Decimal::ZERO;
+    };
+    reserve / Decimal::from(10u64.pow(token.decimals as u32)) * price
+}
+
+/// Pool lifecycle notification broadcast by the `DexManager`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PoolEvent {
+    Registered(PoolId, DexId),
+    Removed(PoolId),
+    StateUpdated(PoolStateDelta),
+    MarkedStale(PoolId),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::DEX_SWAP_FEE_RATE;
+
+    fn pool(reserve_a: u64, reserve_b: u64) -> PoolState {
+        PoolState {
+            dex_id: DexId::Cetus,
+            pool_id: "0xpool".into(),
+            token_a: TokenInfo::new("SUI", "0x2::sui::SUI", 9),
+            token_b: TokenInfo::new("USDC", "0xusdc", 6),
+            reserve_a: Decimal::from(reserve_a),
+            reserve_b: Decimal::from(reserve_b),
+            fee_rate: DEX_SWAP_FEE_RATE,
+            block_timestamp: 0,
+            is_active: true,
+            version: 1,
+            sqrt_price_x64: 0,
+            current_tick_index: 0,
+            amp_factor: None,
+        }
+    }
+
+    #[test]
+    fn test_total_value_locked_usd() {
+        let pool = pool(10_000_000_000, 20_000_000);
+        let prices = HashMap::from([
+            ("0x2::sui::SUI".to_string(), Decimal::TWO),
+            ("0xusdc".to_string(), Decimal::ONE),
+        ]);
+
+        // 10 SUI * $2 + 20 USDC * $1
+        assert_eq!(pool.total_value_locked_usd(&prices), Decimal::from(40));
+        assert_eq!(pool.total_value_locked_usd(&HashMap::new()), Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_delta_from_previous_state() {
+        let old = pool(1_000, 2_000);
+        let new = pool(1_000, 2_200);
+
+        let delta = new.delta_from(Some(&old));
+        assert_eq!(delta.old_price, Decimal::TWO);
+        assert_eq!(delta.new_price, Decimal::new(22, 1));
+        assert_eq!(delta.price_change_percent, Decimal::TEN);
+        assert!(delta.is_significant());
+
+        assert!(!new.delta_from(Some(&new)).is_significant());
+        assert!(new.delta_from(None).is_significant());
+    }
+
+    #[test]
+    fn test_depth_at_price() {
+        let pool = pool(1_000_000, 1_000_000);
+        let target = Decimal::new(9, 1);
+
+        let depth = pool.depth_at_price(target, Side::AToB);
+        assert!(depth > 0);
+        assert!(depth <= 500_000);
+
+        // Already below target: no depth
+        assert_eq!(pool.depth_at_price(Decimal::TWO, Side::AToB), 0);
+        // Target never reached: capped at half the reserve
+        assert_eq!(pool.depth_at_price(Decimal::ZERO, Side::BToA), 500_000);
+    }
 }
@@ -1,7 +1,14 @@
 use std::collections::HashMap;
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
-use crate::types::{DexId, PoolId, PoolState, Price, Timestamp, TokenInfo, TokenPair, now};
+use crate::{
+    dex::manager::DexManager,
+    types::{DexId, PoolId, PoolState, Price, PriceSource, Timestamp, TokenInfo, TokenPair, now},
+};
+
+/// 0.001% - smaller price moves are treated as noise
+pub const PRICE_CHANGE_NOISE_PERCENT: Decimal = Decimal::from_parts(1, 0, 0, false, 3);
 
 /// Atomic snapshot of all DEX states for consistent arbitrage calculations
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -48,6 +55,61 @@ impl StateSnapshot {
             pool_count: 0,
         }
     }
+    
+    /// Add a pool with its tokens and spot price
+    pub fn insert_pool(&mut self, pool: PoolState) {
+        for token in [&pool.token_a, &pool.token_b] {
+            let key = token.address.clone().unwrap_or_else(|| token.symbol.clone());
+            self.tokens.entry(key).or_insert_with(|| token.clone());
+        }
+        let pair = TokenPair::new(pool.token_a.clone(), pool.token_b.clone());
+        let price = pool_price(&pool).and_then(|price| if pair.is_canonical() { Some(price) } else { price.inverse() });
+        if let Some(price) = price {
+            self.prices.insert(PriceKey::new(pool.dex_id, pair.canonical()), price);
+        }
+        self.pools.insert(pool.pool_id.clone(), pool);
+        self.pool_count = self.pools.len();
+    }
+    
+    /// Price of one whole `base` in whole units of `quote`, from the pool
+    /// pairing them that holds the most `base`
+    pub fn price_in(&self, base: &TokenInfo, quote: &TokenInfo) -> Option<Decimal> {
+        self.deepest_price(base, |token| token.matches(quote))
+    }
+    
+    /// USD price of one whole `token`: one for stablecoins, otherwise taken
+    /// from its deepest stablecoin pool, or through SUI when it has none
+    pub fn usd_price(&self, token: &TokenInfo) -> Option<Decimal> {
+        if token.is_usd_stablecoin() {
+            return Some(Decimal::ONE);
+        }
+        self.deepest_price(token, TokenInfo::is_usd_stablecoin).or_else(|| {
+            let sui = TokenInfo::sui();
+            let sui_usd = self.deepest_price(&sui, TokenInfo::is_usd_stablecoin)?;
+            Some(self.price_in(token, &sui)? * sui_usd)
+        })
+    }
+    
+    /// Spot price of one whole `base` from the pool holding the most `base`
+    /// among those pairing it with a token accepted by `quote`
+    fn deepest_price(&self, base: &TokenInfo, quote: impl Fn(&TokenInfo) -> bool) -> Option<Decimal> {
+        let whole = |reserve: Decimal, token: &TokenInfo| reserve / Decimal::from(10u64.pow(token.decimals as u32));
+        self.pools
+            .values()
+            .filter_map(|pool| {
+                let (base_reserve, quote_reserve) = if pool.token_a.matches(base) && quote(&pool.token_b) {
+                    (whole(pool.reserve_a, &pool.token_a), whole(pool.reserve_b, &pool.token_b))
+                } else if pool.token_b.matches(base) && quote(&pool.token_a) {
+                    (whole(pool.reserve_b, &pool.token_b), whole(pool.reserve_a, &pool.token_a))
+                } else {
+                    return None;
+                };
+                Some((base_reserve, quote_reserve.checked_div(base_reserve)?))
+            })
+            .max_by_key(|(base_reserve, _)| *base_reserve)
+            .map(|(_, price)| price)
+    }
+    
     /// Get statistics about the snapshot
     pub fn get_stats(&self) -> SnapshotStats {
         SnapshotStats {
@@ -59,6 +121,89 @@ impl StateSnapshot {
             age_ms: now() - self.timestamp,
         }
     }
+    
+    /// Compare against a newer snapshot and report what changed
+    pub fn diff(&self, other: &StateSnapshot) -> SnapshotDiff {
+        let added_pools = other.pools
+            .keys()
+            .filter(|pool_id| !self.pools.contains_key(*pool_id))
+            .cloned()
+            .collect();
+        
+        let removed_pools = self.pools
+            .keys()
+            .filter(|pool_id| !other.pools.contains_key(*pool_id))
+            .cloned()
+            .collect();
+        
+        let price_changes = self.pools
+            .iter()
+            .filter_map(|(pool_id, old_pool)| {
+                let new_pool = other.pools.get(pool_id)?;
+                let old_price = pool_price(old_pool)?;
+                let new_price = pool_price(new_pool)?;
+                let change_percent = old_price.diff_percent(&new_price);
+                
+                (change_percent.abs() > PRICE_CHANGE_NOISE_PERCENT).then(|| PriceChange {
+                    pool_id: pool_id.clone(),
+                    old_price,
+                    new_price,
+                    change_percent,
+                })
+            })
+            .collect();
+        
+        SnapshotDiff {
+            added_pools,
+            removed_pools,
+            price_changes,
+        }
+    }
+}
+
+/// Spot price (token A in token B) of a pool at its block timestamp
+fn pool_price(pool: &PoolState) -> Option<Price> {
+    if pool.reserve_a.is_zero() {
+        return None;
+    }
+    
+    Some(Price {
+        value: pool.spot_price_a_to_b(),
+        timestamp: pool.block_timestamp,
+        source: PriceSource::Calculated,
+    })
+}
+
+/// Differences between two snapshots
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SnapshotDiff {
+    pub added_pools: Vec<PoolId>,
+    pub removed_pools: Vec<PoolId>,
+    pub price_changes: Vec<PriceChange>,
+}
+
+impl SnapshotDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added_pools.is_empty() && self.removed_pools.is_empty() && self.price_changes.is_empty()
+    }
+    
+    /// All pools that were added or had their price move
+    pub fn affected_pools(&self) -> Vec<PoolId> {
+        self.added_pools
+            .iter()
+            .cloned()
+            .chain(self.price_changes.iter().map(|change| change.pool_id.clone()))
+            .collect()
+    }
+}
+
+/// Price movement of a single pool between snapshots
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceChange {
+    pub pool_id: PoolId,
+    pub old_price: Price,
+    pub new_price: Price,
+    pub change_percent: Decimal,
 }
 
 /// Statistics about a state snapshot
@@ -78,9 +223,125 @@ impl Default for StateSnapshot {
     }
 }
 
+/// Builds a snapshot of every tracked pool in one pass over a borrowed
+/// `DexManager`, so all pools come from the same lock acquisition and
+/// share a single timestamp
+#[derive(Debug, Clone, Default)]
+pub struct StateSnapshotBuilder {
+    min_liquidity_usd: Option<Decimal>,
+    token_prices: HashMap<String, Decimal>,
+}
+
+impl StateSnapshotBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Leave out pools with less liquidity than `usd`
+    pub fn with_min_liquidity(mut self, usd: Decimal) -> Self {
+        self.min_liquidity_usd = Some(usd);
+        self
+    }
+    
+    /// USD prices keyed by token address, used to value pool liquidity
+    pub fn with_token_prices(mut self, token_prices: HashMap<String, Decimal>) -> Self {
+        self.token_prices = token_prices;
+        self
+    }
+    
+    pub fn build(&self, manager: &DexManager) -> StateSnapshot {
+        let mut snapshot = StateSnapshot::new();
+        for pool in manager.all_pools() {
+            if let Some(min_liquidity) = self.min_liquidity_usd
+                && pool.liquidity_usd(&self.token_prices) < min_liquidity
+            {
+                continue;
+            }
+            snapshot.insert_pool(pool.clone());
+        }
+        snapshot.dex_count = manager.dex_count();
+        snapshot
+    }
+}
+
 // Helper implementations for PriceKey
 impl std::fmt::Display for PriceKey {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}:{}", self.dex_id, self.pair)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool(pool_id: &str, reserve_a: i64, reserve_b: i64) -> PoolState {
+        PoolState {
+            dex_id: DexId::Cetus,
+            pool_id: pool_id.into(),
+            token_a: TokenInfo::new("SUI", "0x2::sui::SUI", 9),
+            token_b: TokenInfo::new("USDC", "0x5d4b::coin::COIN", 6),
+            reserve_a: Decimal::from(reserve_a),
+            reserve_b: Decimal::from(reserve_b),
+            fee_rate: Decimal::ZERO,
+            block_timestamp: now(),
+            is_active: true,
+            version: 1,
+            sqrt_price_x64: 0,
+            current_tick_index: 0,
+            amp_factor: None,
+        }
+    }
+
+    fn snapshot(pools: Vec<PoolState>) -> StateSnapshot {
+        let mut snapshot = StateSnapshot::new();
+        for pool in pools {
+            snapshot.pools.insert(pool.pool_id.clone(), pool);
+        }
+        snapshot
+    }
+
+    #[test]
+    fn test_snapshot_diff() {
+        let old = snapshot(vec![pool("a", 1000, 2000), pool("b", 1000, 2000), pool("c", 1000, 2000)]);
+        let new = snapshot(vec![pool("a", 1000, 2000), pool("b", 1000, 2100), pool("d", 1000, 2000)]);
+
+        let diff = old.diff(&new);
+
+        assert_eq!(diff.added_pools, vec!["d".to_string()]);
+        assert_eq!(diff.removed_pools, vec!["c".to_string()]);
+        assert_eq!(diff.price_changes.len(), 1);
+        assert_eq!(diff.price_changes[0].pool_id, "b");
+        assert_eq!(diff.price_changes[0].change_percent, Decimal::from(5));
+    }
+
+    #[test]
+    fn test_builder_filters_illiquid_pools() -> crate::types::Result<()> {
+        let manager = crate::testing::manager_with(&crate::testing::triangle_snapshot())?;
+
+        let all = StateSnapshotBuilder::new().build(&manager);
+        assert_eq!((all.pool_count, all.dex_count), (3, 2));
+        assert!(all.pools.values().all(|pool| pool.block_timestamp <= all.timestamp));
+
+        // p3 holds 1.2M SUI of quote value, the others 2M and 4M
+        let liquid = StateSnapshotBuilder::new()
+            .with_min_liquidity(Decimal::from(1_500_000))
+            .build(&manager);
+        assert_eq!(liquid.pool_count, 2);
+        assert!(!liquid.pools.contains_key("p3"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_usd_price_through_sui() {
+        let snapshot = crate::testing::triangle_snapshot();
+        let (sui, usdc, deep) = (TokenInfo::sui(), crate::testing::test_token("USDC"), crate::testing::test_token("DEEP"));
+
+        assert_eq!(snapshot.usd_price(&usdc), Some(Decimal::ONE));
+        assert_eq!(snapshot.usd_price(&sui), Some(Decimal::from(2)));
+        // Priced from the USDC pool p2 rather than through SUI in p3
+        assert_eq!(snapshot.usd_price(&deep), Some(Decimal::new(5, 1)));
+        assert_eq!(snapshot.price_in(&deep, &sui), Some(Decimal::new(3, 1)));
+        assert_eq!(snapshot.usd_price(&crate::testing::test_token("WBTC")), None);
+    }
+}
@@ -0,0 +1,155 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tracing::warn;
+
+use crate::{types::{BotError, Result}, utils::config::AlertConfig};
+
+/// Severity of an alert, ordered from least to most severe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum AlertSeverity {
+    Info,
+    Warning,
+    Critical,
+}
+
+impl AlertSeverity {
+    pub fn label(&self) -> &'static str {
+        match self {
+            AlertSeverity::Info => "INFO",
+            AlertSeverity::Warning => "WARNING",
+            AlertSeverity::Critical => "CRITICAL",
+        }
+    }
+}
+
+/// Supported alert destinations
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertSinkType {
+    Telegram,
+    Discord,
+}
+
+#[async_trait]
+pub trait AlertSink: Send + Sync {
+    async fn send_alert(&self, title: &str, message: &str, severity: AlertSeverity) -> Result<()>;
+}
+
+/// Sends alerts through the Telegram Bot API
+pub struct TelegramAlertSink {
+    client: reqwest::Client,
+    /// `https://api.telegram.org/bot<token>/sendMessage`
+    endpoint: String,
+    chat_id: String,
+}
+
+impl TelegramAlertSink {
+    pub fn new(endpoint: impl Into<String>, chat_id: impl Into<String>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            endpoint: endpoint.into(),
+            chat_id: chat_id.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl AlertSink for TelegramAlertSink {
+    async fn send_alert(&self, title: &str, message: &str, severity: AlertSeverity) -> Result<()> {
+        let body = json!({
+            "chat_id": self.chat_id,
+            "text": format!("[{}] {}\n{}", severity.label(), title, message),
+        });
+
+        post_json(&self.client, &self.endpoint, &body).await
+    }
+}
+
+/// Sends alerts to a Discord webhook
+pub struct DiscordAlertSink {
+    client: reqwest::Client,
+    webhook_url: String,
+}
+
+impl DiscordAlertSink {
+    pub fn new(webhook_url: impl Into<String>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            webhook_url: webhook_url.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl AlertSink for DiscordAlertSink {
+    async fn send_alert(&self, title: &str, message: &str, severity: AlertSeverity) -> Result<()> {
+        let body = json!({
+            "content": format!("**[{}] {}**\n{}", severity.label(), title, message),
+        });
+
+        post_json(&self.client, &self.webhook_url, &body).await
+    }
+}
+
+async fn post_json(client: &reqwest::Client, url: &str, body: &serde_json::Value) -> Result<()> {
+    client
+        .post(url)
+        .json(body)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| BotError::Network(format!("Failed to send alert: {}", e)))?;
+    Ok(())
+}
+
+/// Fans alerts out to every configured sink that accepts the severity
+#[derive(Default)]
+pub struct Alerter {
+    sinks: Vec<(AlertSeverity, Box<dyn AlertSink>)>,
+}
+
+impl Alerter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build sinks from config entries
+    pub fn from_config(configs: &[AlertConfig]) -> Result<Self> {
+        let mut alerter = Self::new();
+        for config in configs {
+            let sink: Box<dyn AlertSink> = match config.sink_type {
+                AlertSinkType::Telegram => {
+                    let chat_id = config.chat_id.clone().ok_or_else(|| {
+                        BotError::Config("Telegram alert sink requires chat_id".into())
+                    })?;
+                    Box::new(TelegramAlertSink::new(config.endpoint.clone(), chat_id))
+                }
+                AlertSinkType::Discord => Box::new(DiscordAlertSink::new(config.endpoint.clone())),
+            };
+            alerter = alerter.with_sink(sink, config.min_severity);
+        }
+        Ok(alerter)
+    }
+
+    pub fn with_sink(mut self, sink: Box<dyn AlertSink>, min_severity: AlertSeverity) -> Self {
+        self.sinks.push((min_severity, sink));
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sinks.is_empty()
+    }
+
+    /// Send to all matching sinks; delivery failures are logged, not returned
+    pub async fn alert(&self, title: &str, message: &str, severity: AlertSeverity) {
+        for (min_severity, sink) in &self.sinks {
+            if severity < *min_severity {
+                continue;
+            }
+            if let Err(e) = sink.send_alert(title, message, severity).await {
+                warn!("Alert delivery failed: {}", e);
+            }
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use rust_decimal::{Decimal, MathematicalOps};
+use rust_decimal::prelude::ToPrimitive;
+
+/// Q64.64 fixed point scale (2^64)
+const Q64: u128 = 1 << 64;
+
+/// Price ratio between adjacent ticks
+const TICK_BASE: Decimal = Decimal::from_parts(10001, 0, 0, false, 4);
+
+/// Human-readable price of token A in token B from a Q64.64 square root price:
+/// `(sqrt_price / 2^64)^2 * 10^(decimals_a - decimals_b)`.
+/// The squared ratio is a raw-unit price, so it is scaled up by A's decimals
+/// and down by B's. Returns `None` when the price does not fit a `Decimal`.
+pub fn sqrt_price_x64_to_price(sqrt_price_x64: u128, decimals_a: u8, decimals_b: u8) -> Option<Decimal> {
+    let whole = Decimal::from((sqrt_price_x64 >> 64) as u64);
+    let fraction = Decimal::from((sqrt_price_x64 % Q64) as u64) / Decimal::from(Q64);
+    let ratio = whole + fraction;
+
+    ratio.checked_mul(ratio)?.checked_mul(decimal_scale(decimals_a, decimals_b))
+}
+
+/// Human-readable price of token A in token B at `tick`:
+/// `1.0001^tick * 10^(decimals_a - decimals_b)`, or zero when out of range
+pub fn tick_to_price(tick: i32, decimals_a: u8, decimals_b: u8) -> Decimal {
+    TICK_BASE
+        .checked_powi(tick as i64)
+        .and_then(|raw| raw.checked_mul(decimal_scale(decimals_a, decimals_b)))
+        .unwrap_or(Decimal::ZERO)
+}
+
+/// Q64.64 square root of a raw-unit price, zero for non-positive prices
+pub fn price_to_sqrt_x64(price: Decimal) -> u128 {
+    if price <= Decimal::ZERO {
+        return 0;
+    }
+    let Some(sqrt) = price.sqrt() else {
+        return 0;
+    };
+
+    // Shift the integer and fractional parts separately so the product
+    // never exceeds the Decimal range
+    let whole = sqrt.trunc().to_u128().unwrap_or(0) << 64;
+    let fraction = (sqrt.fract() * Decimal::from(Q64)).trunc().to_u128().unwrap_or(0);
+    whole + fraction
+}
+
+/// `10^(decimals_a - decimals_b)`
+fn decimal_scale(decimals_a: u8, decimals_b: u8) -> Decimal {
+    if decimals_a >= decimals_b {
+        Decimal::from(10u64.pow((decimals_a - decimals_b) as u32))
+    } else {
+        Decimal::new(1, (decimals_b - decimals_a) as u32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Within `tolerance` relative to `expected`
+    fn assert_close(actual: Decimal, expected: Decimal, tolerance: Decimal) {
+        let error = ((actual - expected) / expected).abs();
+        assert!(error < tolerance, "{} is not close to {}", actual, expected);
+    }
+
+    #[test]
+    fn test_unit_sqrt_price() {
+        assert_eq!(sqrt_price_x64_to_price(Q64, 9, 9), Some(Decimal::ONE));
+        assert_eq!(price_to_sqrt_x64(Decimal::ONE), Q64);
+    }
+
+    #[test]
+    fn test_sqrt_price_scales_by_decimals() {
+        // 0.002 raw USDC (6 decimals) per raw SUI (9 decimals) is 2 USDC per SUI
+        let sqrt_price = price_to_sqrt_x64(Decimal::new(2, 3));
+        let price = sqrt_price_x64_to_price(sqrt_price, 9, 6).unwrap_or_default();
+        assert_close(price, Decimal::from(2), Decimal::new(1, 12));
+    }
+
+    #[test]
+    fn test_tick_to_price() {
+        assert_eq!(tick_to_price(0, 6, 6), Decimal::ONE);
+        assert_eq!(tick_to_price(1, 6, 6), Decimal::new(10001, 4));
+        assert_close(tick_to_price(-1, 6, 6), Decimal::ONE / Decimal::new(10001, 4), Decimal::new(1, 20));
+
+        // 1.0001^6932 is roughly 2
+        assert_close(tick_to_price(6932, 9, 6), Decimal::from(2000), Decimal::new(1, 3));
+    }
+
+    #[test]
+    fn test_tick_and_sqrt_price_agree() {
+        let tick = 23_028;
+        let sqrt_price = price_to_sqrt_x64(tick_to_price(tick, 9, 9));
+        let price = sqrt_price_x64_to_price(sqrt_price, 9, 9).unwrap_or_default();
+        assert_close(price, tick_to_price(tick, 9, 9), Decimal::new(1, 12));
+    }
+
+    #[test]
+    fn test_invalid_prices() {
+        assert_eq!(price_to_sqrt_x64(Decimal::ZERO), 0);
+        assert_eq!(price_to_sqrt_x64(-Decimal::ONE), 0);
+        assert_eq!(sqrt_price_x64_to_price(0, 9, 6), Some(Decimal::ZERO));
+    }
+}
@@ -1,12 +1,14 @@
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
 use tracing::{debug, error, info, warn};
-use std::str::FromStr;
+use std::{fmt, path::Path, str::FromStr};
+use tokio::sync::watch;
 
-use crate::types::{BotError, DexId, MIN_PROFIT_PERCENT, Network, Result, TokenInfo};
+use crate::{dex::cetus, types::{BotError, DexId, MIN_PROFIT_PERCENT, Network, Result, TokenInfo}, utils::alerting::{AlertSeverity, AlertSinkType}};
 
 /// Simple, focused configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Config {
     /// Network settings
     pub network: NetworkConfig,
@@ -20,8 +22,33 @@ pub struct Config {
     /// Opportunity validation settings
     pub validation: ValidationConfig,
 
+    /// Sync and event pipeline settings
+    #[serde(default)]
+    pub sync: SyncConfig,
+
     /// Logging settings
     pub logging: LoggingConfig, 
+
+    /// Alert destinations
+    #[serde(default)]
+    pub alerts: Vec<AlertConfig>,
+
+    /// Secret the `/state` endpoint expects in its `x-state-secret` header;
+    /// the endpoint is disabled without one
+    #[serde(default)]
+    pub state_api_secret: Option<String>,
+}
+
+/// Alert sink configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertConfig {
+    pub sink_type: AlertSinkType,
+    /// Telegram `sendMessage` URL or Discord webhook URL
+    pub endpoint: String,
+    pub min_severity: AlertSeverity,
+    /// Telegram chat to post into
+    #[serde(default)]
+    pub chat_id: Option<String>,
 }
 
 /// Network configuration
@@ -29,21 +56,83 @@ pub struct Config {
 pub struct NetworkConfig {
     pub network: Network,
     pub rpc_url: String,
+    /// Tried in order when `rpc_url` is unreachable
+    #[serde(default)]
+    pub fallback_rpc_urls: Vec<String>,
     pub ws_url: String,
     pub dexes: Vec<DexConfig>,
+    /// Event channel capacity for DEXs without `event_buffer_size`
+    #[serde(default = "default_event_buffer_size")]
+    pub default_event_buffer_size: usize,
+    /// What to do when a DEX event channel is full
+    #[serde(default)]
+    pub backpressure_policy: BackpressurePolicy,
+    /// WebSocket keepalive settings
+    #[serde(default)]
+    pub ws: WsConfig,
+}
+
+fn default_event_buffer_size() -> usize {
+    5000
 }
 
 impl Default for NetworkConfig {
     fn default() -> Self {
         Self {
             network: Network::SuiTestnet,
-            rpc_url: "https://fullnode.testnet.sui.io:443".into(),
-            ws_url: "wss://fullnode.testnet.sui.io:443".into(),
+            rpc_url: Network::SuiTestnet.rpc_url().into(),
+            fallback_rpc_urls: Vec::new(),
+            ws_url: Network::SuiTestnet.ws_url().into(),
             dexes: vec![],
+            default_event_buffer_size: default_event_buffer_size(),
+            backpressure_policy: BackpressurePolicy::default(),
+            ws: WsConfig::default(),
         }
     }
 }
 
+impl NetworkConfig {
+    /// Get config of a specific DEX
+    pub fn dex_config(&self, dex_id: DexId) -> Option<&DexConfig> {
+        self.dexes.iter().find(|d| d.id == dex_id)
+    }
+    
+    /// Event channel capacity for a DEX
+    pub fn event_buffer_size(&self, dex_id: DexId) -> usize {
+        self.dex_config(dex_id)
+            .and_then(|d| d.event_buffer_size)
+            .unwrap_or(self.default_event_buffer_size)
+    }
+}
+
+/// WebSocket keepalive configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WsConfig {
+    /// Send a ping this often
+    pub ping_interval_secs: u64,
+    /// Reconnect when nothing arrives for this long
+    pub read_timeout_secs: u64,
+}
+
+impl Default for WsConfig {
+    fn default() -> Self {
+        Self {
+            ping_interval_secs: 30,
+            read_timeout_secs: 60,
+        }
+    }
+}
+
+/// Handling of events arriving while a channel is full
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BackpressurePolicy {
+    /// Drop the incoming event
+    #[default]
+    Drop,
+    /// Wait until the consumer frees up space
+    Block,
+}
+
 /// Simplified DEX config
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DexConfig {
@@ -52,6 +141,9 @@ pub struct DexConfig {
     pub event_type: String,
     pub enabled: bool,
     pub pools: Vec<PoolConfig>,
+    /// Event channel capacity, overrides `NetworkConfig::default_event_buffer_size`
+    #[serde(default)]
+    pub event_buffer_size: Option<usize>,
 }
 
 /// Pool configuration
@@ -70,6 +162,127 @@ pub struct ArbitrageConfig {
     pub max_price_impact_percent: Decimal,
     pub min_profit_threshold: Decimal,
     pub min_profit_percent: Decimal,
+    /// Reuse the cached state snapshot while it is younger than this
+    #[serde(default = "default_max_snapshot_age_ms")]
+    pub max_snapshot_age_ms: u64,
+    /// Slippage tiers, sorted by `min_profit_percent` descending
+    #[serde(default = "default_profit_targets")]
+    pub profit_targets: Vec<ProfitTarget>,
+    /// Upper bound for one opportunity scan; partial results are returned on timeout
+    #[serde(default = "default_simulate_timeout_ms")]
+    pub simulate_timeout_ms: u64,
+    /// Algorithm used to enumerate candidate paths
+    #[serde(default)]
+    pub path_algorithm: PathAlgorithm,
+    /// Paths touching fewer DEXes are dropped (single-DEX loops lose to fees)
+    #[serde(default = "default_min_dex_count_per_path")]
+    pub min_dex_count_per_path: usize,
+    /// Only trade on these DEXes; empty allows all
+    #[serde(default)]
+    pub whitelist_dexes: Vec<DexId>,
+    /// Opportunities over the same pools seen within this window are dropped
+    #[serde(default = "default_duplicate_window_ms")]
+    pub duplicate_window_ms: u64,
+    /// How often the engine logs a P&L summary
+    #[serde(default = "default_pnl_report_interval_secs")]
+    pub pnl_report_interval_secs: u64,
+}
+
+fn default_min_dex_count_per_path() -> usize {
+    2
+}
+
+fn default_duplicate_window_ms() -> u64 {
+    500
+}
+
+fn default_pnl_report_interval_secs() -> u64 {
+    3600
+}
+
+/// Path finding strategy
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PathAlgorithm {
+    /// Enumerate every closed loop up to `max_hops`
+    #[default]
+    Dfs,
+    /// Detect negative cycles on the log-price graph
+    BellmanFord,
+}
+
+fn default_simulate_timeout_ms() -> u64 {
+    50
+}
+
+fn default_max_snapshot_age_ms() -> u64 {
+    100
+}
+
+/// Slippage allowed for opportunities at or above a profit level
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProfitTarget {
+    pub min_profit_percent: Decimal,
+    pub max_slippage_percent: Decimal,
+}
+
+fn default_profit_targets() -> Vec<ProfitTarget> {
+    vec![
+        ProfitTarget {
+            min_profit_percent: Decimal::from(2),
+            max_slippage_percent: Decimal::ONE,
+        },
+        ProfitTarget {
+            min_profit_percent: Decimal::from_parts(5, 0, 0, false, 1),
+            max_slippage_percent: Decimal::from_parts(3, 0, 0, false, 1),
+        },
+        ProfitTarget {
+            min_profit_percent: Decimal::ZERO,
+            max_slippage_percent: Decimal::from_parts(1, 0, 0, false, 1),
+        },
+    ]
+}
+
+impl ArbitrageConfig {
+    /// Highest tier whose profit requirement is met, or the tightest
+    /// tier when the opportunity is below all of them
+    pub fn profit_target_for(&self, profit_percent: Decimal) -> Option<&ProfitTarget> {
+        self.profit_targets
+            .iter()
+            .filter(|target| profit_percent >= target.min_profit_percent)
+            .max_by_key(|target| target.min_profit_percent)
+            .or_else(|| self.profit_targets.iter().min_by_key(|target| target.min_profit_percent))
+    }
+    
+    /// Validate arbitrage settings
+    pub fn validate(&self) -> Result<()> {
+        // A closed loop needs at least two pools
+        if !(2..=MAX_HOPS_LIMIT).contains(&self.max_hops) {
+            return Err(BotError::Config(format!(
+                "max_hops must be between 2 and {}, got {}", MAX_HOPS_LIMIT, self.max_hops
+            )));
+        }
+        if self.min_profit_percent < Decimal::ZERO {
+            return Err(BotError::Config("min_profit_percent cannot be negative".into()));
+        }
+        if self.min_dex_count_per_path == 0 || self.min_dex_count_per_path > self.max_hops {
+            return Err(BotError::Config(format!(
+                "min_dex_count_per_path must be between 1 and max_hops ({}), got {}",
+                self.max_hops, self.min_dex_count_per_path
+            )));
+        }
+        for (i, dex_id) in self.whitelist_dexes.iter().enumerate() {
+            if self.whitelist_dexes[..i].contains(dex_id) {
+                return Err(BotError::Config(format!("whitelist_dexes lists {} twice", dex_id)));
+            }
+        }
+        if !self.whitelist_dexes.is_empty() && self.whitelist_dexes.len() < self.min_dex_count_per_path {
+            return Err(BotError::Config(format!(
+                "whitelist_dexes allows {} DEXes but min_dex_count_per_path is {}",
+                self.whitelist_dexes.len(), self.min_dex_count_per_path
+            )));
+        }
+        Ok(())
+    }
 }
 
 impl Default for ArbitrageConfig {
@@ -80,6 +293,14 @@ impl Default for ArbitrageConfig {
             max_price_impact_percent: Decimal::from(5),
             min_profit_threshold: Decimal::from(1),
             min_profit_percent: MIN_PROFIT_PERCENT,
+            max_snapshot_age_ms: default_max_snapshot_age_ms(),
+            simulate_timeout_ms: default_simulate_timeout_ms(),
+            path_algorithm: PathAlgorithm::default(),
+            min_dex_count_per_path: default_min_dex_count_per_path(),
+            whitelist_dexes: Vec::new(),
+            duplicate_window_ms: default_duplicate_window_ms(),
+            pnl_report_interval_secs: default_pnl_report_interval_secs(),
+            profit_targets: default_profit_targets(),
         }
     }
 }
@@ -96,8 +317,99 @@ pub struct ExecutionConfig {
     /// Gas budget per transaction
     pub gas_budget: u64,
     
+    /// Estimated gas cost of one swap hop in MIST, used to price opportunities
+    #[serde(default = "default_base_gas_per_swap")]
+    pub base_gas_per_swap: u64,
+    
     /// Slippage tolerance percentage
     pub slippage_tolerance_percent: Decimal,
+    
+    /// Only submit when a simulation shows positive profit.
+    /// Costs an extra RPC call per trade, so off by default for testnet.
+    #[serde(default)]
+    pub require_profitable_simulation: bool,
+    
+    /// Trading wallets used round-robin; `private_key` is used when empty
+    #[serde(default)]
+    pub wallets: Vec<WalletConfig>,
+    
+    /// Resubmissions after a network or RPC failure
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+    
+    /// Gas budget multiplier applied on each resubmission
+    #[serde(default = "default_gas_escalation_factor")]
+    pub gas_escalation_factor: f64,
+    
+    /// Wait between submission attempts
+    #[serde(default = "default_retry_delay_seconds")]
+    pub retry_delay_seconds: u64,
+    
+    /// Failed executions in a row that halt trading
+    #[serde(default = "default_max_consecutive_failures")]
+    pub max_consecutive_failures: u32,
+    
+    /// Losing executions in a row that halt trading
+    #[serde(default = "default_max_consecutive_losses")]
+    pub max_consecutive_losses: u32,
+    
+    /// Resume trading this long after the circuit breaker trips; 0 waits for a manual reset
+    #[serde(default = "default_circuit_breaker_cooldown_secs")]
+    pub circuit_breaker_cooldown_secs: u64,
+}
+
+/// A trading wallet and its risk budget
+#[derive(Clone, Serialize, Deserialize)]
+pub struct WalletConfig {
+    pub private_key: String,
+    pub max_capital_usd: Decimal,
+    #[serde(default = "default_wallet_enabled")]
+    pub enabled: bool,
+}
+
+impl fmt::Debug for WalletConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WalletConfig")
+            .field("private_key", &REDACTED)
+            .field("max_capital_usd", &self.max_capital_usd)
+            .field("enabled", &self.enabled)
+            .finish()
+    }
+}
+
+/// Gas cost of one swap hop in MIST when none is configured
+pub const DEFAULT_BASE_GAS_PER_SWAP: u64 = 500_000;
+
+fn default_base_gas_per_swap() -> u64 {
+    DEFAULT_BASE_GAS_PER_SWAP
+}
+
+fn default_max_retries() -> u32 {
+    2
+}
+
+fn default_gas_escalation_factor() -> f64 {
+    1.5
+}
+
+fn default_retry_delay_seconds() -> u64 {
+    1
+}
+
+fn default_max_consecutive_failures() -> u32 {
+    5
+}
+
+fn default_max_consecutive_losses() -> u32 {
+    3
+}
+
+fn default_circuit_breaker_cooldown_secs() -> u64 {
+    900
+}
+
+fn default_wallet_enabled() -> bool {
+    true
 }
 
 impl Default for ExecutionConfig {
@@ -106,7 +418,16 @@ impl Default for ExecutionConfig {
             dry_run: true,
             private_key: None,
             gas_budget: 10_000_000,
-            slippage_tolerance_percent: Decimal::from_str("1.0").unwrap(),
+            base_gas_per_swap: default_base_gas_per_swap(),
+            slippage_tolerance_percent: Decimal::ONE,
+            require_profitable_simulation: false,
+            wallets: Vec::new(),
+            max_retries: default_max_retries(),
+            gas_escalation_factor: default_gas_escalation_factor(),
+            retry_delay_seconds: default_retry_delay_seconds(),
+            max_consecutive_failures: default_max_consecutive_failures(),
+            max_consecutive_losses: default_max_consecutive_losses(),
+            circuit_breaker_cooldown_secs: default_circuit_breaker_cooldown_secs(),
         }
     }
 }
@@ -127,6 +448,22 @@ pub struct ValidationConfig {
     
     /// Maximum gas cost as percentage of profit
     pub max_gas_cost_percent: Decimal,
+    
+    /// Minimum `ArbitrageOpportunity::confidence_score` required to execute
+    #[serde(default = "default_min_confidence")]
+    pub min_confidence: f64,
+    
+    /// Expected time from detection to on-chain inclusion in milliseconds
+    #[serde(default = "default_estimated_execution_time_ms")]
+    pub estimated_execution_time_ms: u64,
+}
+
+fn default_min_confidence() -> f64 {
+    0.5
+}
+
+fn default_estimated_execution_time_ms() -> u64 {
+    500
 }
 
 impl Default for ValidationConfig {
@@ -134,36 +471,159 @@ impl Default for ValidationConfig {
         Self {
             max_opportunity_age_ms: 2000,
             min_pool_liquidity_usd: Decimal::from(1000),
-            max_price_divergence_percent: Decimal::from_str("5.0").unwrap(),
+            max_price_divergence_percent: Decimal::from(5),
             revalidate_before_execution: true,
-            max_gas_cost_percent: Decimal::from_str("50.0").unwrap(),
+            max_gas_cost_percent: Decimal::from(50),
+            min_confidence: default_min_confidence(),
+            estimated_execution_time_ms: default_estimated_execution_time_ms(),
         }
     }
 }
 
+/// Sync and event pipeline configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct LoggingConfig {
-    pub level: String,
-    pub enable_metrics: bool,
+pub struct SyncConfig {
+    /// Warn when p95 event processing lag exceeds this many milliseconds
+    #[serde(default = "default_event_lag_alert_ms")]
+    pub event_lag_alert_ms: u64,
+    
+    /// Buffer size of the pool lifecycle broadcast channel
+    #[serde(default = "default_event_channel_capacity")]
+    pub event_channel_capacity: usize,
+    
+    /// Upper bound in seconds for an on-demand full sync
+    #[serde(default = "default_emergency_sync_interval_seconds")]
+    pub emergency_sync_interval_seconds: u64,
+    
+    /// Pool states and prices older than this many seconds are stale
+    #[serde(default = "default_state_ttl_seconds")]
+    pub state_ttl_seconds: u64,
+    
+    /// Seconds between background refreshes of stale pools
+    #[serde(default = "default_stale_sync_interval_seconds")]
+    pub stale_sync_interval_seconds: u64,
+    
+    /// Most pool objects requested in one RPC call
+    #[serde(default = "default_batch_size")]
+    pub batch_size: usize,
+    
+    /// Retries of a failed pool fetch per DEX before giving up on it
+    #[serde(default = "default_sync_max_retries")]
+    pub max_retries: u32,
+    
+    /// Wait before the first retry, doubled on each further attempt
+    #[serde(default = "default_sync_retry_delay_ms")]
+    pub retry_delay_ms: u64,
+    
+    /// Shorten the stale sync interval to the emergency interval while
+    /// events are busy and stretch it threefold while they are quiet
+    #[serde(default)]
+    pub enable_adaptive_sync: bool,
 }
 
-impl Default for LoggingConfig {
+fn default_event_lag_alert_ms() -> u64 {
+    5000
+}
+
+fn default_event_channel_capacity() -> usize {
+    1024
+}
+
+fn default_emergency_sync_interval_seconds() -> u64 {
+    30
+}
+
+fn default_state_ttl_seconds() -> u64 {
+    300
+}
+
+fn default_stale_sync_interval_seconds() -> u64 {
+    60
+}
+
+/// Upper limit of `sui_multiGetObjects`
+fn default_batch_size() -> usize {
+    50
+}
+
+fn default_sync_max_retries() -> u32 {
+    3
+}
+
+fn default_sync_retry_delay_ms() -> u64 {
+    250
+}
+
+impl Default for SyncConfig {
     fn default() -> Self {
         Self {
-            level: "info".into(),
-            enable_metrics: true,
+            event_lag_alert_ms: default_event_lag_alert_ms(),
+            event_channel_capacity: default_event_channel_capacity(),
+            emergency_sync_interval_seconds: default_emergency_sync_interval_seconds(),
+            state_ttl_seconds: default_state_ttl_seconds(),
+            stale_sync_interval_seconds: default_stale_sync_interval_seconds(),
+            batch_size: default_batch_size(),
+            max_retries: default_sync_max_retries(),
+            retry_delay_ms: default_sync_retry_delay_ms(),
+            enable_adaptive_sync: false,
         }
     }
 }
 
-impl Default for Config {
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoggingConfig {
+    pub level: String,
+    pub enable_metrics: bool,
+    /// Buffer size of the execution result broadcast channel
+    #[serde(default = "default_execution_broadcast_capacity")]
+    pub execution_broadcast_capacity: usize,
+    /// Port serving Prometheus metrics when `enable_metrics` is set
+    #[serde(default = "default_metrics_port")]
+    pub metrics_port: u16,
+    #[serde(default)]
+    pub format: LogFormat,
+    #[serde(default)]
+    pub output: LogOutput,
+}
+
+/// Layout of log lines
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LogFormat {
+    /// Human-readable text
+    #[default]
+    Text,
+    /// One JSON object per line, for log shippers
+    Json,
+}
+
+/// Where log lines are written
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LogOutput {
+    #[default]
+    Stdout,
+    /// File rotated daily, with the date appended to its name
+    File(String),
+}
+
+pub const DEFAULT_EXECUTION_BROADCAST_CAPACITY: usize = 100;
+
+fn default_execution_broadcast_capacity() -> usize {
+    DEFAULT_EXECUTION_BROADCAST_CAPACITY
+}
+
+fn default_metrics_port() -> u16 {
+    9090
+}
+
+impl Default for LoggingConfig {
     fn default() -> Self {
         Self {
-            network: NetworkConfig::default(),
-            arbitrage: ArbitrageConfig::default(),
-            execution: ExecutionConfig::default(),
-            validation: ValidationConfig::default(),
-            logging: LoggingConfig::default()
+            level: "info".into(),
+            enable_metrics: true,
+            execution_broadcast_capacity: DEFAULT_EXECUTION_BROADCAST_CAPACITY,
+            metrics_port: default_metrics_port(),
+            format: LogFormat::default(),
+            output: LogOutput::default(),
         }
     }
 }
@@ -185,23 +645,92 @@ impl Config {
         &self.execution
     }
 
-    /// Load config from file or use defaults
+    pub fn sync_config(&self) -> &SyncConfig {
+        &self.sync
+    }
+    
+    pub fn logging_config(&self) -> &LoggingConfig {
+        &self.logging
+    }
+
+    /// Load config from `config.json` or `config.toml`, whichever exists first,
+    /// falling back to defaults when neither exists or the file cannot be loaded
     pub fn load() -> Result<Self> {
-        info!("Loading configuration...");
+        let Some(path) = Self::find_config_file() else {
+            info!("No config file found");
+            return Self::from_defaults();
+        };
+        Self::load_from(path).or_else(|e| {
+            warn!("Failed to load config from file: {}", e);
+            warn!("Using default configuration instead");
+            Self::from_defaults()
+        })
+    }
+    
+    /// The first of the default config files that exists
+    pub fn find_config_file() -> Option<&'static str> {
+        CONFIG_FILES.into_iter().find(|path| Path::new(path).exists())
+    }
+    
+    /// Reload `path` whenever it changes and send the new config on
+    /// `notify_tx` once it validates; invalid edits are logged and skipped.
+    /// Watching stops when the returned watcher is dropped.
+    pub fn watch_file(path: &str, notify_tx: watch::Sender<Config>) -> Result<RecommendedWatcher> {
+        let watch_error = |e: notify::Error| BotError::Config(format!("Failed to watch config file {}: {}", path, e));
+        let file = Path::new(path);
+        let file_name = file.file_name()
+            .map(|name| name.to_os_string())
+            .ok_or_else(|| BotError::Config(format!("Config path {} is not a file", path)))?;
+        // Watch the directory so files replaced by rename, as `save` does, keep being seen
+        let dir = match file.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+            _ => Path::new(".").to_path_buf(),
+        };
         
-        // Try to load from config file
-        match Self::load_from_file("config.toml") {
-            Ok(config) => {
-                info!("Config loaded from config.toml");
-                config.log_loaded_config();
-                return Ok(config);
+        let owned_path = path.to_string();
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+            let event = match event {
+                Ok(event) => event,
+                Err(e) => {
+                    warn!("Config watcher error: {}", e);
+                    return;
+                }
+            };
+            let touches_config = event.paths.iter().any(|changed| changed.file_name() == Some(file_name.as_os_str()));
+            if !touches_config || !(event.kind.is_modify() || event.kind.is_create()) {
+                return;
             }
-            Err(e) => {
-                warn!("Failed to load config from file: {}", e);
-                warn!("Using default configuration instead");
+            
+            match Self::load_from_file(&owned_path).and_then(|config| config.validate().map(|_| config)) {
+                Ok(config) => {
+                    info!("Config reloaded from {}", owned_path);
+                    if notify_tx.send(config).is_err() {
+                        debug!("No subscribers for config reloads");
+                    }
+                }
+                Err(e) => warn!("Ignoring invalid config change in {}: {}", owned_path, e),
             }
-        }
+        })
+        .map_err(watch_error)?;
+        watcher.watch(&dir, RecursiveMode::NonRecursive).map_err(watch_error)?;
+        
+        info!("Watching {} for config changes", path);
+        Ok(watcher)
+    }
+    
+    /// Load config from the given file. Unlike `load`, a missing or invalid
+    /// file is an error, since the caller asked for this file explicitly.
+    pub fn load_from(path: &str) -> Result<Self> {
+        info!("Loading configuration...");
         
+        let config = Self::load_from_file(path)?;
+        info!("Config loaded from {}", path);
+        config.log_loaded_config();
+        Ok(config)
+    }
+    
+    /// Default config with environment overrides
+    fn from_defaults() -> Result<Self> {
         let mut config = Self::default();
         config.apply_env_vars()?;
         
@@ -209,31 +738,88 @@ impl Config {
         config.log_loaded_config();
         
         Ok(config)
-    }    
-    /// Load from TOML file
+    }
+    
+    /// Load from a JSON or TOML file, picked by extension
     fn load_from_file(path: &str) -> Result<Self> {
+        if path.ends_with(".json") {
+            Self::load_from_json(path)
+        } else {
+            Self::load_from_toml(path)
+        }
+    }
+    
+    /// Load from JSON file
+    pub fn load_from_json(path: &str) -> Result<Self> {
+        let content = Self::read_config_file(path)?;
+        let config = Self::from_json_str(&content).inspect_err(|e| {
+            error!("Failed to parse JSON config {}: {}", path, e);
+        })?;
+        
+        info!("✅ Config parsed successfully from: {}", path);
+        Ok(config)
+    }
+    
+    /// Load from TOML file
+    pub fn load_from_toml(path: &str) -> Result<Self> {
+        let content = Self::read_config_file(path)?;
+        let config = Self::from_toml_str(&content).inspect_err(|e| {
+            error!("Failed to parse TOML config {}: {}", path, e);
+        })?;
+        
+        info!("✅ Config parsed successfully from: {}", path);
+        Ok(config)
+    }
+    
+    fn read_config_file(path: &str) -> Result<String> {
         info!("Loading config from: {}", path);
         
         // Check if file exists first
-        if !std::path::Path::new(path).exists() {
+        if !Path::new(path).exists() {
             return Err(BotError::Config(format!("Config file not found: {}", path)));
         }
         
-        // Read the file content
         let content = std::fs::read_to_string(path)
             .map_err(|e| BotError::Config(format!("Failed to read config file {}: {}", path, e)))?;
         
         info!("Config file found, size: {} bytes", content.len());
-        
-        // Parse the TOML content
-        let config: Config = toml::from_str(&content)
-            .map_err(|e| {
-                error!("Failed to parse TOML config: {}", e);
-                BotError::Config(format!("Failed to parse config file {}: {}", path, e))
-            })?;
-        
-        info!("✅ Config parsed successfully from: {}", path);
-        Ok(config)
+        Ok(content)
+    }
+    
+    /// Write the full config, including secrets, as pretty JSON
+    pub fn save_as_json(&self, path: &str) -> Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+    
+    /// Config as JSON with private keys and alert endpoints redacted
+    pub fn redacted_summary(&self) -> Result<serde_json::Value> {
+        let mut config = self.clone();
+        if config.execution.private_key.is_some() {
+            config.execution.private_key = Some(REDACTED.into());
+        }
+        for wallet in &mut config.execution.wallets {
+            wallet.private_key = REDACTED.into();
+        }
+        for alert in &mut config.alerts {
+            alert.endpoint = REDACTED.into();
+        }
+        if config.state_api_secret.is_some() {
+            config.state_api_secret = Some(REDACTED.into());
+        }
+        Ok(serde_json::to_value(config)?)
+    }
+    
+    /// Parse config from a TOML string
+    pub fn from_toml_str(content: &str) -> Result<Self> {
+        toml::from_str(content)
+            .map_err(|e| BotError::Config(format!("Failed to parse config: {}", e)))
+    }
+    
+    /// Parse config from a JSON string
+    pub fn from_json_str(content: &str) -> Result<Self> {
+        serde_json::from_str(content)
+            .map_err(|e| BotError::Config(format!("Failed to parse config: {}", e)))
     }
     
     /// Apply environment variable overrides
@@ -291,6 +877,7 @@ impl Config {
         
         info!("Execution Settings:");
         info!("  Dry run: {}", self.execution.dry_run);
+        info!("  Require profitable simulation: {}", self.execution.require_profitable_simulation);
         info!("  Gas budget: {}", self.execution.gas_budget);
         info!("  Slippage: {}%", self.execution.slippage_tolerance_percent);
         
@@ -298,9 +885,65 @@ impl Config {
         info!("  Max opportunity age: {}ms", self.validation.max_opportunity_age_ms);
         info!("  Min pool liquidity: ${}", self.validation.min_pool_liquidity_usd);
         
+        info!("Alert sinks: {}", self.alerts.len());
+        
+        info!("Sync Settings:");
+        info!("  Event lag alert: {}ms", self.sync.event_lag_alert_ms);
+        info!("  Emergency sync timeout: {}s", self.sync.emergency_sync_interval_seconds);
+        
         info!("=================================");
     }
     
+    /// Add pools to the config of the given DEX, skipping known addresses.
+    /// Returns the number of pools added.
+    pub fn merge_pools(&mut self, new_pools: Vec<PoolConfig>, dex_id: DexId) -> usize {
+        let Some(dex) = self.network.dexes.iter_mut().find(|d| d.id == dex_id) else {
+            warn!("Cannot merge pools: DEX {} is not configured", dex_id);
+            return 0;
+        };
+        
+        let mut added = 0;
+        for pool in new_pools {
+            let exists = dex.pools
+                .iter()
+                .any(|p| p.address.eq_ignore_ascii_case(&pool.address));
+            if !exists {
+                dex.pools.push(pool);
+                added += 1;
+            }
+        }
+        
+        info!("Merged {} new pools into {} config", added, dex_id);
+        added
+    }
+    
+    /// Write config as TOML, replacing the target file atomically
+    pub fn save(&self, path: &str) -> Result<()> {
+        let content = toml::to_string_pretty(self)
+            .map_err(|e| BotError::Config(format!("Failed to serialize config: {}", e)))?;
+        
+        let tmp_path = format!("{}.tmp", path);
+        std::fs::write(&tmp_path, content)?;
+        std::fs::rename(&tmp_path, path)?;
+        
+        info!("Config saved to {}", path);
+        Ok(())
+    }
+    
+    /// Default config with a disabled Cetus entry to use as a template
+    pub fn with_example_dex() -> Config {
+        let mut config = Self::default();
+        config.network.dexes.push(DexConfig {
+            id: DexId::Cetus,
+            package_id: cetus::CETUS_TESTNET_PACKAGE_ID.into(),
+            event_type: "SwapEvent".into(),
+            enabled: false,
+            pools: vec![],
+            event_buffer_size: None,
+        });
+        config
+    }
+    
     /// Get enabled DEXs
     pub fn enabled_dexes(&self) -> Vec<&DexConfig> {
         self.network.dexes.iter().filter(|d| d.enabled).collect()
@@ -308,8 +951,68 @@ impl Config {
     
     /// Validate configuration
     pub fn validate(&self) -> Result<()> {        
-        if !self.execution.dry_run && self.execution.private_key.is_none() {
-            return Err(BotError::Config("Private key required when not in dry-run mode".into()));
+        for rpc_url in std::iter::once(&self.network.rpc_url).chain(&self.network.fallback_rpc_urls) {
+            if !rpc_url.starts_with("https://") {
+                return Err(BotError::Config(format!("RPC URLs must use https://, got {}", rpc_url)));
+            }
+        }
+        if !self.network.ws_url.starts_with("wss://") {
+            return Err(BotError::Config(format!("ws_url must use wss://, got {}", self.network.ws_url)));
+        }
+        let endpoints = [&self.network.rpc_url, &self.network.ws_url].into_iter().chain(&self.network.fallback_rpc_urls);
+        for url in endpoints {
+            if let Some(url_network) = Network::from_rpc_url(url)
+                && url_network != self.network.network
+            {
+                return Err(BotError::Config(format!(
+                    "Endpoint {} belongs to {} but network is {}",
+                    url, url_network, self.network.network
+                )));
+            }
+        }
+        
+        let has_wallet = self.execution.private_key.is_some()
+            || self.execution.wallets.iter().any(|wallet| wallet.enabled);
+        if !self.execution.dry_run && !has_wallet {
+            return Err(BotError::Config("Private key or wallet required when not in dry-run mode".into()));
+        }
+        // A factor below one would shrink the budget on every retry
+        if self.execution.gas_escalation_factor.is_nan() || self.execution.gas_escalation_factor < 1.0 {
+            return Err(BotError::Config(format!(
+                "gas_escalation_factor must be at least 1.0, got {}", self.execution.gas_escalation_factor
+            )));
+        }
+        
+        if self.execution.slippage_tolerance_percent < Decimal::new(1, 2)
+            || self.execution.slippage_tolerance_percent > Decimal::TEN
+        {
+            return Err(BotError::Config(format!(
+                "slippage_tolerance_percent must be between 0.01 and 10, got {}",
+                self.execution.slippage_tolerance_percent
+            )));
+        }
+        if !(1..=MAX_SYNC_BATCH_SIZE).contains(&self.sync.batch_size) {
+            return Err(BotError::Config(format!(
+                "batch_size must be between 1 and {}, got {}", MAX_SYNC_BATCH_SIZE, self.sync.batch_size
+            )));
+        }
+        if self.sync.stale_sync_interval_seconds == 0 {
+            return Err(BotError::Config("stale_sync_interval_seconds must be at least 1".into()));
+        }
+        
+        self.arbitrage.validate()?;
+        
+        for dex in &self.network.dexes {
+            if !is_valid_object_id(&dex.package_id) {
+                return Err(BotError::Config(format!(
+                    "Invalid package ID for {}: {}", dex.id, dex.package_id
+                )));
+            }
+            if let Some(pool) = dex.pools.iter().find(|pool| !is_valid_object_id(&pool.address)) {
+                return Err(BotError::Config(format!(
+                    "Invalid pool address for {}: {}", dex.id, pool.address
+                )));
+            }
         }
         
         let enabled_dexes = self.enabled_dexes();
@@ -323,4 +1026,345 @@ impl Config {
         
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+/// Stands in for secrets in the config summary and `Debug` output
+pub const REDACTED: &str = "<redacted>";
+
+/// Config files `Config::load` looks for, in order
+const CONFIG_FILES: [&str; 2] = ["config.json", "config.toml"];
+
+/// Longer loops explode path enumeration and rarely fit in one PTB
+const MAX_HOPS_LIMIT: usize = 6;
+
+/// Upper bound on objects per `multiGetObjects` request
+const MAX_SYNC_BATCH_SIZE: usize = 100;
+
+/// Sui object IDs are `0x` followed by up to 64 hex digits
+fn is_valid_object_id(id: &str) -> bool {
+    id.strip_prefix("0x")
+        .is_some_and(|hex| !hex.is_empty() && hex.len() <= 64 && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool(address: &str) -> PoolConfig {
+        PoolConfig {
+            address: address.into(),
+            token_a: TokenInfo::new("SUI", "0x2::sui::SUI", 9),
+            token_b: TokenInfo::new("USDC", "0x5d4b::coin::COIN", 6),
+        }
+    }
+
+    #[test]
+    fn test_merge_pools_skips_known_addresses() {
+        let mut config = Config::with_example_dex();
+        
+        assert_eq!(config.merge_pools(vec![pool("0xabc"), pool("0xdef")], DexId::Cetus), 2);
+        assert_eq!(config.merge_pools(vec![pool("0xABC"), pool("0x123")], DexId::Cetus), 1);
+        assert_eq!(config.merge_pools(vec![pool("0x456")], DexId::Turbos), 0);
+        assert_eq!(config.network.dexes[0].pools.len(), 3);
+    }
+
+    #[test]
+    fn test_profit_target_selects_highest_met_tier() {
+        let config = ArbitrageConfig::default();
+        let slippage = |profit: Decimal| {
+            config.profit_target_for(profit).map(|target| target.max_slippage_percent)
+        };
+
+        assert_eq!(slippage(Decimal::from(3)), Some(Decimal::ONE));
+        assert_eq!(slippage(Decimal::ONE), Some(Decimal::from_parts(3, 0, 0, false, 1)));
+        assert_eq!(slippage(Decimal::from(-1)), Some(Decimal::from_parts(1, 0, 0, false, 1)));
+    }
+
+    const BASE_CONFIG: &str = r#"
+        [network]
+        network = "SuiTestnet"
+        rpc_url = "https://fullnode.testnet.sui.io:443"
+        ws_url = "wss://fullnode.testnet.sui.io:443"
+
+        [[network.dexes]]
+        id = "Cetus"
+        package_id = "0x0868b71c0cba55bf0faf6c40df8c179c67a4d0ba0e79965b68b3d72d7dfbf666"
+        event_type = "SwapEvent"
+        enabled = true
+        pools = []
+
+        [arbitrage]
+        max_hops = 3
+        min_liquidity_per_pool_usd = 1000
+        max_price_impact_percent = 5.0
+        min_profit_threshold = 1.0
+        min_profit_percent = 0.5
+
+        [execution]
+        dry_run = true
+        gas_budget = 10000000
+        slippage_tolerance_percent = 1.0
+
+        [validation]
+        max_opportunity_age_ms = 2000
+        min_pool_liquidity_usd = 1000
+        max_price_divergence_percent = 5.0
+        revalidate_before_execution = true
+        max_gas_cost_percent = 50.0
+
+        [logging]
+        level = "info"
+        enable_metrics = true
+    "#;
+
+    fn config_with(from: &str, to: &str) -> Result<Config> {
+        Config::from_toml_str(&BASE_CONFIG.replace(from, to))
+    }
+
+    #[test]
+    fn test_base_config_is_valid() -> Result<()> {
+        Config::from_toml_str(BASE_CONFIG)?.validate()
+    }
+
+    const BASE_JSON_CONFIG: &str = r#"{
+        "network": {
+            "network": "SuiTestnet",
+            "rpc_url": "https://fullnode.testnet.sui.io:443",
+            "ws_url": "wss://fullnode.testnet.sui.io:443",
+            "dexes": [{
+                "id": "Cetus",
+                "package_id": "0x0868b71c0cba55bf0faf6c40df8c179c67a4d0ba0e79965b68b3d72d7dfbf666",
+                "event_type": "SwapEvent",
+                "enabled": true,
+                "pools": []
+            }]
+        },
+        "arbitrage": {
+            "max_hops": 3,
+            "min_liquidity_per_pool_usd": "1000",
+            "max_price_impact_percent": "5",
+            "min_profit_threshold": "1",
+            "min_profit_percent": "0.5"
+        },
+        "execution": {
+            "dry_run": true,
+            "gas_budget": 10000000,
+            "slippage_tolerance_percent": "1"
+        },
+        "validation": {
+            "max_opportunity_age_ms": 2000,
+            "min_pool_liquidity_usd": "1000",
+            "max_price_divergence_percent": "5",
+            "revalidate_before_execution": true,
+            "max_gas_cost_percent": "50"
+        },
+        "logging": { "level": "info", "enable_metrics": true }
+    }"#;
+
+    #[test]
+    fn test_json_config_matches_toml() -> Result<()> {
+        let json = Config::from_json_str(BASE_JSON_CONFIG)?;
+        json.validate()?;
+        assert_eq!(json.redacted_summary()?, Config::from_toml_str(BASE_CONFIG)?.redacted_summary()?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_loads_by_extension() -> Result<()> {
+        let dir = std::env::temp_dir();
+        let json_path = dir.join(format!("config-{}.json", crate::types::now())).to_string_lossy().into_owned();
+        let toml_path = json_path.replace(".json", ".toml");
+        let config = Config::from_toml_str(BASE_CONFIG)?;
+
+        config.save_as_json(&json_path)?;
+        std::fs::write(&toml_path, BASE_CONFIG)?;
+        let from_json = Config::load_from_file(&json_path);
+        let from_toml = Config::load_from_file(&toml_path);
+        std::fs::remove_file(&json_path)?;
+        std::fs::remove_file(&toml_path)?;
+
+        assert_eq!(from_json?.redacted_summary()?, config.redacted_summary()?);
+        assert_eq!(from_toml?.redacted_summary()?, config.redacted_summary()?);
+        assert!(matches!(Config::load_from_json(&json_path), Err(BotError::Config(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_explicit_path_must_load() -> Result<()> {
+        let path = std::env::temp_dir().join(format!("config-{}.toml", crate::types::now())).to_string_lossy().into_owned();
+
+        assert!(matches!(Config::load_from(&path), Err(BotError::Config(_))));
+        std::fs::write(&path, "[network\n")?;
+        let invalid = Config::load_from(&path);
+        std::fs::remove_file(&path)?;
+        assert!(invalid.is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_watch_file_sends_valid_changes() -> Result<()> {
+        let dir = std::env::temp_dir().join(format!("config-watch-{}", crate::types::now()));
+        std::fs::create_dir_all(&dir)?;
+        let path = dir.join("config.toml").to_string_lossy().into_owned();
+        std::fs::write(&path, BASE_CONFIG)?;
+
+        let (notify_tx, mut config_rx) = watch::channel(Config::from_toml_str(BASE_CONFIG)?);
+        let _watcher = Config::watch_file(&path, notify_tx)?;
+        std::fs::write(&path, BASE_CONFIG.replace("[logging]", "[logging"))?;
+        std::fs::write(&path, BASE_CONFIG.replace("min_profit_threshold = 1.0", "min_profit_threshold = 7.5"))?;
+
+        let changed = tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            loop {
+                config_rx.changed().await.map_err(|e| BotError::Config(e.to_string()))?;
+                let threshold = config_rx.borrow_and_update().arbitrage.min_profit_threshold;
+                if threshold == Decimal::new(75, 1) {
+                    return Ok::<_, BotError>(threshold);
+                }
+            }
+        })
+        .await;
+        std::fs::remove_dir_all(&dir)?;
+
+        assert!(matches!(changed, Ok(Ok(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_log_format_and_output_parse() -> Result<()> {
+        let config = config_with(
+            "enable_metrics = true",
+            "enable_metrics = true\n        format = \"Json\"\n        output = { File = \"logs/bot.log\" }",
+        )?;
+        assert_eq!(config.logging.format, LogFormat::Json);
+        assert_eq!(config.logging.output, LogOutput::File("logs/bot.log".into()));
+        assert_eq!(Config::from_toml_str(BASE_CONFIG)?.logging.output, LogOutput::Stdout);
+        Ok(())
+    }
+
+    #[test]
+    fn test_partial_sync_section_uses_defaults() -> Result<()> {
+        let config = Config::from_toml_str(&format!("{}\n[sync]\nbatch_size = 20\n", BASE_CONFIG))?;
+
+        assert_eq!(config.sync.batch_size, 20);
+        assert_eq!(config.sync.event_lag_alert_ms, default_event_lag_alert_ms());
+        assert_eq!(config.sync.event_channel_capacity, default_event_channel_capacity());
+        assert_eq!(config.sync.max_retries, default_sync_max_retries());
+        Ok(())
+    }
+
+    #[test]
+    fn test_missing_network_section_is_config_error() {
+        let content = BASE_CONFIG.replace("[network]", "[unused]");
+        assert!(matches!(Config::from_toml_str(&content), Err(BotError::Config(_))));
+    }
+
+    #[test]
+    fn test_invalid_package_id_rejected() -> Result<()> {
+        let config = config_with("0x0868b71c", "0xnothex")?;
+        assert!(matches!(config.validate(), Err(BotError::Config(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_insecure_endpoints_rejected() -> Result<()> {
+        let config = config_with("https://fullnode", "http://fullnode")?;
+        assert!(matches!(config.validate(), Err(BotError::Config(_))));
+        let config = config_with("wss://fullnode", "ws://fullnode")?;
+        assert!(matches!(config.validate(), Err(BotError::Config(_))));
+        let mut config = Config::from_toml_str(BASE_CONFIG)?;
+        config.network.fallback_rpc_urls = vec!["http://127.0.0.1:9000".into()];
+        assert!(matches!(config.validate(), Err(BotError::Config(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_pool_address_rejected() -> Result<()> {
+        let mut config = Config::from_toml_str(BASE_CONFIG)?;
+        config.network.dexes[0].pools.push(pool("0xabc"));
+        config.validate()?;
+
+        config.network.dexes[0].pools.push(pool("pool-1"));
+        assert!(matches!(config.validate(), Err(BotError::Config(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_too_many_hops_rejected() -> Result<()> {
+        config_with("max_hops = 3", "max_hops = 6")?.arbitrage.validate()?;
+        let config = config_with("max_hops = 3", "max_hops = 7")?;
+        assert!(matches!(config.arbitrage.validate(), Err(BotError::Config(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_slippage_out_of_range_rejected() -> Result<()> {
+        for slippage in ["0.001", "10.5"] {
+            let config = config_with("slippage_tolerance_percent = 1.0", &format!("slippage_tolerance_percent = {}", slippage))?;
+            assert!(matches!(config.validate(), Err(BotError::Config(_))));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_batch_size_out_of_range_rejected() -> Result<()> {
+        let mut config = Config::from_toml_str(BASE_CONFIG)?;
+        for batch_size in [0, 101] {
+            config.sync.batch_size = batch_size;
+            assert!(matches!(config.validate(), Err(BotError::Config(_))));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_zero_stale_sync_interval_rejected() -> Result<()> {
+        let mut config = Config::from_toml_str(BASE_CONFIG)?;
+        config.sync.stale_sync_interval_seconds = 0;
+        assert!(matches!(config.validate(), Err(BotError::Config(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_live_mode_requires_private_key() -> Result<()> {
+        let config = config_with("dry_run = true", "dry_run = false")?;
+        assert!(matches!(config.validate(), Err(BotError::Config(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_no_dexes_rejected() -> Result<()> {
+        let mut config = Config::from_toml_str(BASE_CONFIG)?;
+        config.network.dexes.clear();
+        assert!(matches!(config.validate(), Err(BotError::Config(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_zero_min_profit_percent_accepted() -> Result<()> {
+        config_with("min_profit_percent = 0.5", "min_profit_percent = 0")?.validate()
+    }
+
+    #[test]
+    fn test_single_hop_rejected() -> Result<()> {
+        let config = config_with("max_hops = 3", "max_hops = 1")?;
+        assert!(matches!(config.arbitrage.validate(), Err(BotError::Config(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_whitelist_smaller_than_min_dex_count_rejected() -> Result<()> {
+        let mut config = Config::from_toml_str(BASE_CONFIG)?;
+        config.arbitrage.whitelist_dexes = vec![DexId::Cetus];
+        assert!(matches!(config.arbitrage.validate(), Err(BotError::Config(_))));
+
+        config.arbitrage.min_dex_count_per_path = 1;
+        config.arbitrage.validate()
+    }
+
+    #[test]
+    fn test_duplicate_whitelisted_dex_rejected() -> Result<()> {
+        let mut config = Config::from_toml_str(BASE_CONFIG)?;
+        config.arbitrage.whitelist_dexes = vec![DexId::Cetus, DexId::Turbos, DexId::Cetus];
+        assert!(matches!(config.arbitrage.validate(), Err(BotError::Config(_))));
+        Ok(())
+    }
+}
@@ -0,0 +1,99 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::types::Timestamp;
+
+/// Digests remembered before the oldest is evicted
+pub const DIGEST_CACHE_CAPACITY: usize = 10_000;
+
+/// How long a digest is remembered (ms)
+pub const DIGEST_TTL_MS: u64 = 60_000;
+
+/// Transaction digests seen recently, bounded by both count and age
+#[derive(Debug, Clone)]
+pub struct DigestCache {
+    seen: HashMap<String, Timestamp>,
+    /// Insertion order, oldest first
+    order: VecDeque<(String, Timestamp)>,
+    capacity: usize,
+    ttl_ms: u64,
+}
+
+impl Default for DigestCache {
+    fn default() -> Self {
+        Self::new(DIGEST_CACHE_CAPACITY, DIGEST_TTL_MS)
+    }
+}
+
+impl DigestCache {
+    pub fn new(capacity: usize, ttl_ms: u64) -> Self {
+        Self {
+            seen: HashMap::new(),
+            order: VecDeque::new(),
+            capacity,
+            ttl_ms,
+        }
+    }
+
+    /// Whether `digest` was inserted within the TTL as of `now`
+    pub fn contains(&self, digest: &str, now: Timestamp) -> bool {
+        self.seen
+            .get(digest)
+            .is_some_and(|seen_at| now.saturating_sub(*seen_at) < self.ttl_ms)
+    }
+
+    /// Remember `digest` as seen at `now`, evicting expired and excess entries
+    pub fn insert(&mut self, digest: String, now: Timestamp) {
+        self.seen.insert(digest.clone(), now);
+        self.order.push_back((digest, now));
+
+        while let Some((oldest, seen_at)) = self.order.front() {
+            let expired = now.saturating_sub(*seen_at) >= self.ttl_ms;
+            if !expired && self.order.len() <= self.capacity {
+                break;
+            }
+            // A re-inserted digest has a newer entry further back
+            if self.seen.get(oldest) == Some(seen_at) {
+                self.seen.remove(oldest);
+            }
+            self.order.pop_front();
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.seen.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.seen.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remembers_until_ttl() {
+        let mut cache = DigestCache::new(10, 1_000);
+        cache.insert("0xd1".into(), 5_000);
+
+        assert!(cache.contains("0xd1", 5_999));
+        assert!(!cache.contains("0xd1", 6_000));
+        assert!(!cache.contains("0xd2", 5_000));
+
+        cache.insert("0xd2".into(), 6_000);
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_evicts_oldest_beyond_capacity() {
+        let mut cache = DigestCache::new(2, 1_000);
+        for (i, digest) in ["0xd1", "0xd2", "0xd3"].into_iter().enumerate() {
+            cache.insert(digest.into(), i as u64);
+        }
+
+        assert!(!cache.contains("0xd1", 3));
+        assert!(cache.contains("0xd2", 3));
+        assert!(cache.contains("0xd3", 3));
+    }
+}
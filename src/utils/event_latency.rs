@@ -0,0 +1,94 @@
+use std::collections::VecDeque;
+
+use serde::Serialize;
+
+/// Number of recent latencies kept for percentile calculations
+pub const LATENCY_WINDOW: usize = 100;
+
+/// Rolling window of event processing latencies (ms)
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct EventLatencyTracker {
+    latencies: VecDeque<u64>,
+}
+
+impl EventLatencyTracker {
+    pub fn new() -> Self {
+        Self {
+            latencies: VecDeque::with_capacity(LATENCY_WINDOW),
+        }
+    }
+
+    /// Record a latency sample, evicting the oldest when the window is full
+    pub fn record(&mut self, latency_ms: u64) {
+        if self.latencies.len() == LATENCY_WINDOW {
+            self.latencies.pop_front();
+        }
+        self.latencies.push_back(latency_ms);
+    }
+
+    pub fn len(&self) -> usize {
+        self.latencies.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.latencies.is_empty()
+    }
+
+    pub fn p50_ms(&self) -> u64 {
+        self.percentile(50)
+    }
+
+    pub fn p95_ms(&self) -> u64 {
+        self.percentile(95)
+    }
+
+    pub fn p99_ms(&self) -> u64 {
+        self.percentile(99)
+    }
+
+    /// Nearest-rank percentile, 0 when no samples were recorded
+    fn percentile(&self, percentile: usize) -> u64 {
+        if self.latencies.is_empty() {
+            return 0;
+        }
+
+        let mut sorted: Vec<u64> = self.latencies.iter().copied().collect();
+        sorted.sort_unstable();
+
+        let rank = (percentile * sorted.len()).div_ceil(100);
+        sorted[rank.saturating_sub(1)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentiles() {
+        let mut tracker = EventLatencyTracker::new();
+        assert_eq!(tracker.p95_ms(), 0);
+
+        for latency in (1..=100).rev() {
+            tracker.record(latency);
+        }
+
+        assert_eq!(tracker.p50_ms(), 50);
+        assert_eq!(tracker.p95_ms(), 95);
+        assert_eq!(tracker.p99_ms(), 99);
+    }
+
+    #[test]
+    fn test_window_evicts_oldest() {
+        let mut tracker = EventLatencyTracker::new();
+        for _ in 0..LATENCY_WINDOW {
+            tracker.record(1000);
+        }
+        for _ in 0..LATENCY_WINDOW {
+            tracker.record(10);
+        }
+
+        assert_eq!(tracker.len(), LATENCY_WINDOW);
+        assert_eq!(tracker.p99_ms(), 10);
+    }
+}
@@ -0,0 +1,68 @@
+use std::{collections::VecDeque, sync::Mutex};
+
+use crate::types::Timestamp;
+
+/// Span of the rolling window events are counted over
+pub const RATE_WINDOW_MS: u64 = 60_000;
+
+/// Events counted per second over the last minute, shared between the
+/// event processor recording them and the sync orchestrator reading them
+#[derive(Debug, Default)]
+pub struct EventRateCounter {
+    /// Event counts by second, oldest first
+    buckets: Mutex<VecDeque<(u64, u64)>>,
+}
+
+impl EventRateCounter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count one event seen at `at`
+    pub fn record(&self, at: Timestamp) {
+        let second = at / 1000;
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        match buckets.back_mut() {
+            Some((last, count)) if *last == second => *count += 1,
+            _ => buckets.push_back((second, 1)),
+        }
+        Self::evict(&mut buckets, at);
+    }
+
+    /// Events seen in the minute before `now`
+    pub fn events_per_minute(&self, now: Timestamp) -> u64 {
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        Self::evict(&mut buckets, now);
+        buckets.iter().map(|(_, count)| count).sum()
+    }
+
+    /// Average events per second over the minute before `now`
+    pub fn events_per_second(&self, now: Timestamp) -> f64 {
+        self.events_per_minute(now) as f64 / (RATE_WINDOW_MS / 1000) as f64
+    }
+
+    fn evict(buckets: &mut VecDeque<(u64, u64)>, now: Timestamp) {
+        let window_start = now.saturating_sub(RATE_WINDOW_MS);
+        while buckets.front().is_some_and(|(second, _)| (second + 1) * 1000 <= window_start) {
+            buckets.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counts_events_within_the_last_minute() {
+        let counter = EventRateCounter::new();
+        for at in [1_000, 1_500, 30_000, 61_000] {
+            counter.record(at);
+        }
+
+        assert_eq!(counter.events_per_minute(61_000), 4);
+        assert_eq!(counter.events_per_minute(62_000), 2);
+        assert_eq!(counter.events_per_second(62_000), 2.0 / 60.0);
+        assert_eq!(counter.events_per_minute(200_000), 0);
+    }
+}
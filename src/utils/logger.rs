@@ -1,12 +1,38 @@
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
+use std::path::Path;
+
+use tracing::Subscriber;
+use tracing_subscriber::{
+    EnvFilter, Layer, fmt::{self, MakeWriter}, layer::SubscriberExt, registry::LookupSpan, util::SubscriberInitExt,
+};
+
+use crate::utils::config::{LogFormat, LogOutput, LoggingConfig};
+
+/// Install the global subscriber. `RUST_LOG` overrides the configured level.
+pub fn init(config: &LoggingConfig) {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(&config.level));
+    let layer = match &config.output {
+        LogOutput::Stdout => fmt_layer(config.format, std::io::stdout, true),
+        LogOutput::File(path) => {
+            let path = Path::new(path);
+            let directory = path.parent().filter(|dir| !dir.as_os_str().is_empty()).unwrap_or(Path::new("."));
+            let file_name = path.file_name().unwrap_or(path.as_os_str());
+            fmt_layer(config.format, tracing_appender::rolling::daily(directory, file_name), false)
+        }
+    };
 
-pub fn init() {
-    // TODO:
-    // - env filter (RUST_LOG)
-    // - format output nicely
-    
     tracing_subscriber::registry()
-        .with(EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")))
-        .with(tracing_subscriber::fmt::layer())
+        .with(filter)
+        .with(layer)
         .init();
-}
\ No newline at end of file
+}
+
+fn fmt_layer<S, W>(format: LogFormat, writer: W, ansi: bool) -> Box<dyn Layer<S> + Send + Sync>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    match format {
+        LogFormat::Text => fmt::layer().with_writer(writer).with_ansi(ansi).boxed(),
+        LogFormat::Json => fmt::layer().json().with_writer(writer).boxed(),
+    }
+}
@@ -1,39 +1,494 @@
 use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
 
-/// Calculate constant product AMM output amount
+use crate::types::{BotError, Result};
+
+/// Fee denominator: fees are expressed in basis points of 10_000
+pub const BPS_DENOMINATOR: u32 = 10_000;
+
+/// Calculate constant product AMM output amount after a `fee_bps` swap fee:
+/// `reserve_out * amount_in * (10000 - fee) / (reserve_in * 10000 + amount_in * (10000 - fee))`
 pub fn calculate_amm_output(
-    _amount_in: u64,
-    _reserve_in: u64,
-    _reserve_out: u64,
+    amount_in: u64,
+    reserve_in: u64,
+    reserve_out: u64,
+    fee_bps: u32,
+) -> u64 {
+    if reserve_in == 0 || reserve_out == 0 || fee_bps >= BPS_DENOMINATOR {
+        return 0;
+    }
+
+    let amount_in_with_fee = amount_in as u128 * (BPS_DENOMINATOR - fee_bps) as u128;
+    let denominator = reserve_in as u128 * BPS_DENOMINATOR as u128 + amount_in_with_fee;
+
+    // amount_in_with_fee <= denominator, so the result never exceeds reserve_out
+    mul_div(reserve_out, amount_in_with_fee, denominator) as u64
+}
+
+/// Calculate a two-coin StableSwap (Curve) output for `amount_in`, charging
+/// `fee_bps` on the output. `amp_factor` is Curve's `A`; the invariant is
+/// `A·n^n·Σx + D = A·D·n^n + D^(n+1) / (n^n·Πx)` with `n = 2`.
+/// Returns zero for empty pools or if an intermediate overflows.
+pub fn calculate_stable_swap_output(
+    amount_in: u64,
+    reserve_in: u64,
+    reserve_out: u64,
+    amp_factor: u64,
+    fee_bps: u32,
 ) -> u64 {
-    // TODO: Calculate constant product AMM
-    todo!("Calculate AMM output amount")
+    if reserve_in == 0 || reserve_out == 0 || amp_factor == 0 || fee_bps >= BPS_DENOMINATOR {
+        return 0;
+    }
+
+    let (x, y) = (reserve_in as u128, reserve_out as u128);
+    let output = stable_swap_invariant(x, y, amp_factor as u128)
+        .and_then(|d| stable_swap_y(x + amount_in as u128, d, amp_factor as u128))
+        // Round against the trader
+        .map(|new_y| y.saturating_sub(new_y).saturating_sub(1))
+        .unwrap_or(0);
+
+    let fee = output * fee_bps as u128 / BPS_DENOMINATOR as u128;
+    (output - fee) as u64
 }
 
-/// Calculate price impact for a trade
+/// Newton iteration for the StableSwap invariant `D` of a two-coin pool
+fn stable_swap_invariant(x: u128, y: u128, amp: u128) -> Option<u128> {
+    let sum = x + y;
+    let ann = amp.checked_mul(4)?;
+    let mut d = sum;
+
+    for _ in 0..255 {
+        // d_p = D^3 / (4xy), built up stepwise to stay in range
+        let d_p = d.checked_mul(d)?.checked_div(x * 2)?.checked_mul(d)?.checked_div(y * 2)?;
+        let previous = d;
+        let numerator = ann.checked_mul(sum)?.checked_add(d_p.checked_mul(2)?)?.checked_mul(d)?;
+        let denominator = (ann - 1).checked_mul(d)?.checked_add(d_p.checked_mul(3)?)?;
+        d = numerator.checked_div(denominator)?;
+
+        if d.abs_diff(previous) <= 1 {
+            return Some(d);
+        }
+    }
+    None
+}
+
+/// Newton iteration for the other reserve once one side moves to `x`,
+/// keeping the invariant `d` fixed
+fn stable_swap_y(x: u128, d: u128, amp: u128) -> Option<u128> {
+    let ann = amp.checked_mul(4)?;
+    let c = d.checked_mul(d)?.checked_div(x * 2)?.checked_mul(d)?.checked_div(ann.checked_mul(2)?)?;
+    let b = x + d / ann;
+    let mut y = d;
+
+    for _ in 0..255 {
+        let previous = y;
+        let numerator = y.checked_mul(y)?.checked_add(c)?;
+        let denominator = (y * 2 + b).checked_sub(d)?;
+        y = numerator.checked_div(denominator)?;
+
+        if y.abs_diff(previous) <= 1 {
+            return Some(y);
+        }
+    }
+    None
+}
+
+/// Calculate price impact for a trade as the percentage the execution price
+/// falls below the spot price (fees excluded). Empty pools give 100%.
 pub fn calculate_price_impact(
-    _amount_in: u64,
-    _reserve_in: u64,
-    _reserve_out: u64,
+    amount_in: u64,
+    reserve_in: u64,
+    reserve_out: u64,
 ) -> Decimal {
-    // TODO: Calculate price impact
-    todo!("Calculate price impact")
+    if reserve_in == 0 || reserve_out == 0 {
+        return Decimal::ONE_HUNDRED;
+    }
+
+    // spot = out / in, execution = out / (in + amount), so the shortfall
+    // relative to spot is amount / (in + amount)
+    let amount_in = Decimal::from(amount_in);
+    amount_in * Decimal::ONE_HUNDRED / (Decimal::from(reserve_in) + amount_in)
 }
 
-/// Calculate slippage
+/// Calculate slippage as the percentage `actual_output` fell short of
+/// `expected_output`; negative when the trade did better than expected
 pub fn calculate_slippage(
-    _expected_output: u64,
-    _actual_output: u64,
+    expected_output: u64,
+    actual_output: u64,
 ) -> Decimal {
-    // TODO: Calculate slippage
-    todo!("Calculate slippage")
+    if expected_output == 0 {
+        return Decimal::ZERO;
+    }
+
+    let shortfall = Decimal::from(expected_output) - Decimal::from(actual_output);
+    shortfall * Decimal::ONE_HUNDRED / Decimal::from(expected_output)
 }
 
-/// Apply slippage tolerance to get minimum output
+/// Apply slippage tolerance (percent, clamped to 0-100) to get minimum output
 pub fn apply_slippage_tolerance(
-    _expected_output: u64,
-    _slippage_tolerance: Decimal,
+    expected_output: u64,
+    slippage_tolerance: Decimal,
 ) -> u64 {
-    // TODO: Calculate minimum output
-    todo!("Apply slippage tolerance")
-}
\ No newline at end of file
+    let tolerance = slippage_tolerance.clamp(Decimal::ZERO, Decimal::ONE_HUNDRED);
+    let kept = Decimal::ONE - tolerance / Decimal::ONE_HUNDRED;
+
+    (Decimal::from(expected_output) * kept)
+        .floor()
+        .to_u64()
+        .unwrap_or(0)
+}
+
+/// Chain constant-product swaps over `hops` of `(reserve_in, reserve_out, fee_bps)`,
+/// feeding each hop's output into the next, and return the final output.
+/// Fails when a hop has an empty reserve or when the compounded price impact
+/// of all hops exceeds `max_price_impact_percent`.
+pub fn simulate_multi_hop(
+    hops: &[(u64, u64, u32)],
+    amount_in: u64,
+    max_price_impact_percent: Decimal,
+) -> Result<u64> {
+    let mut amount = amount_in;
+    let mut retained = Decimal::ONE;
+
+    for (i, &(reserve_in, reserve_out, fee_bps)) in hops.iter().enumerate() {
+        if reserve_in == 0 || reserve_out == 0 {
+            return Err(BotError::InsufficientLiquidity {
+                pool_id: format!("hop {}", i),
+            });
+        }
+
+        let impact = calculate_price_impact(amount, reserve_in, reserve_out);
+        retained *= Decimal::ONE - impact / Decimal::ONE_HUNDRED;
+        amount = calculate_amm_output(amount, reserve_in, reserve_out, fee_bps);
+    }
+
+    let cumulative_impact = (Decimal::ONE - retained) * Decimal::ONE_HUNDRED;
+    if cumulative_impact > max_price_impact_percent {
+        return Err(BotError::SlippageTooHigh {
+            expected: max_price_impact_percent,
+            actual: cumulative_impact,
+        });
+    }
+
+    Ok(amount)
+}
+
+/// Find the input in `min_amount..=max_amount` that maximises the profit of
+/// chaining swaps over `reserves`. Profit is concave in the input, so this
+/// binary searches for where its slope turns negative, in at most 64 steps.
+/// The slope is probed over a 0.01% gap so output rounding does not flip it.
+pub fn calculate_optimal_input_amount(
+    reserves: &[(u64, u64, u32)],
+    min_amount: u64,
+    max_amount: u64,
+) -> u64 {
+    let (mut low, mut high) = (min_amount, max_amount.max(min_amount));
+
+    for _ in 0..64 {
+        if low >= high {
+            break;
+        }
+        let mid = low + (high - low) / 2;
+        let gap = (mid / 10_000).max(1);
+
+        if profit_at_amount(reserves, mid.saturating_add(gap)) > profit_at_amount(reserves, mid) {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+
+    low
+}
+
+/// Largest input up to `upper` whose compounded price impact across
+/// `reserves` stays within `max_price_impact_percent` (zero if none does).
+/// Impact only grows with the input, so this binary searches the boundary.
+pub fn max_input_within_impact(
+    reserves: &[(u64, u64, u32)],
+    upper: u64,
+    max_price_impact_percent: Decimal,
+) -> u64 {
+    let within = |amount| simulate_multi_hop(reserves, amount, max_price_impact_percent).is_ok();
+    let (mut low, mut high) = (0, upper);
+
+    while low < high {
+        let mid = low + (high - low).div_ceil(2);
+        if within(mid) {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+    low
+}
+
+/// Output minus input of chaining swaps over `reserves`, ignoring the price
+/// impact limit. A path with an empty pool returns nothing, so the whole
+/// input counts as lost.
+pub fn profit_at_amount(reserves: &[(u64, u64, u32)], amount: u64) -> i128 {
+    let output = simulate_multi_hop(reserves, amount, Decimal::ONE_HUNDRED).unwrap_or(0);
+    output as i128 - amount as i128
+}
+
+/// `floor(a * b / c)` without overflowing, for `b <= c`
+fn mul_div(a: u64, b: u128, c: u128) -> u128 {
+    if let Some(product) = (a as u128).checked_mul(b) {
+        return product / c;
+    }
+
+    // 192-bit product split into high and low 128-bit halves
+    let low_part = a as u128 * (b & u64::MAX as u128);
+    let high_part = a as u128 * (b >> 64);
+    let (low, carry) = (high_part << 64).overflowing_add(low_part);
+    let high = (high_part >> 64) + carry as u128;
+
+    // Long division; the quotient fits in 64 bits since b <= c
+    let mut remainder = high % c;
+    let mut quotient = 0u128;
+    for bit in (0..128).rev() {
+        let overflow = remainder >> 127 == 1;
+        remainder = (remainder << 1) | ((low >> bit) & 1);
+        if overflow || remainder >= c {
+            remainder = remainder.wrapping_sub(c);
+            quotient |= 1 << bit;
+        }
+    }
+    quotient
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    const MAX_RESERVE: u64 = 1_000_000_000_000_000;
+
+    #[test]
+    fn test_amm_output_fee_tiers() {
+        // 1_000 into a 1M/1M pool
+        assert_eq!(calculate_amm_output(1_000, 1_000_000, 1_000_000, 0), 999);
+        assert_eq!(calculate_amm_output(1_000, 1_000_000, 1_000_000, 25), 996);
+        assert_eq!(calculate_amm_output(1_000, 1_000_000, 1_000_000, 100), 989);
+    }
+
+    #[test]
+    fn test_amm_output_zero_reserves() {
+        assert_eq!(calculate_amm_output(1_000, 0, 1_000_000, 30), 0);
+        assert_eq!(calculate_amm_output(1_000, 1_000_000, 0, 30), 0);
+        assert_eq!(calculate_amm_output(0, 1_000_000, 1_000_000, 30), 0);
+    }
+
+    #[test]
+    fn test_amm_output_beyond_u64_intermediates() {
+        // reserve_out * amount_in * 10000 needs more than 128 bits
+        let output = calculate_amm_output(u64::MAX, u64::MAX, u64::MAX, 0);
+        assert_eq!(output, u64::MAX / 2);
+
+        let output = calculate_amm_output(u64::MAX, 1, u64::MAX, 100);
+        assert!(output < u64::MAX);
+        assert!(output > u64::MAX - u64::MAX / 1_000_000_000);
+    }
+
+    #[test]
+    fn test_stable_swap_output() {
+        // Balanced pool trades close to 1:1
+        assert_eq!(calculate_stable_swap_output(1_000_000, 1_000_000_000, 1_000_000_000, 100, 0), 999_995);
+        assert_eq!(calculate_stable_swap_output(1_000_000, 1_000_000_000, 1_000_000_000, 100, 4), 999_596);
+        // Output side twice as scarce
+        assert_eq!(calculate_stable_swap_output(1_000_000, 2_000_000_000, 1_000_000_000, 100, 0), 995_814);
+    }
+
+    #[test]
+    fn test_stable_swap_flatter_than_constant_product() {
+        let (amount, reserve) = (100_000_000, 1_000_000_000);
+        let constant_product = calculate_amm_output(amount, reserve, reserve, 0);
+        let low_amp = calculate_stable_swap_output(amount, reserve, reserve, 1, 0);
+        let high_amp = calculate_stable_swap_output(amount, reserve, reserve, 100, 0);
+
+        assert_eq!(low_amp, 96_760_741);
+        assert_eq!(high_amp, 99_949_776);
+        assert!(constant_product < low_amp && low_amp < high_amp && high_amp < amount);
+    }
+
+    #[test]
+    fn test_stable_swap_empty_pool() {
+        assert_eq!(calculate_stable_swap_output(1_000, 0, 1_000_000, 100, 4), 0);
+        assert_eq!(calculate_stable_swap_output(1_000, 1_000_000, 0, 100, 4), 0);
+        assert_eq!(calculate_stable_swap_output(1_000, 1_000_000, 1_000_000, 0, 4), 0);
+    }
+
+    #[test]
+    fn test_price_impact() {
+        assert_eq!(calculate_price_impact(0, 1_000, 1_000), Decimal::ZERO);
+        assert_eq!(calculate_price_impact(1_000, 1_000, 5_000), Decimal::from(50));
+        assert_eq!(calculate_price_impact(10, 990, 2_000), Decimal::ONE);
+        assert_eq!(calculate_price_impact(10, 0, 1_000), Decimal::ONE_HUNDRED);
+        assert_eq!(calculate_price_impact(10, 1_000, 0), Decimal::ONE_HUNDRED);
+        assert_eq!(calculate_price_impact(u64::MAX, u64::MAX, u64::MAX), Decimal::from(50));
+    }
+
+    #[test]
+    fn test_slippage() {
+        assert_eq!(calculate_slippage(1_000, 990), Decimal::ONE);
+        assert_eq!(calculate_slippage(1_000, 1_010), -Decimal::ONE);
+        assert_eq!(calculate_slippage(0, 10), Decimal::ZERO);
+        assert_eq!(calculate_slippage(u64::MAX, 0), Decimal::ONE_HUNDRED);
+    }
+
+    #[test]
+    fn test_apply_slippage_tolerance() {
+        assert_eq!(apply_slippage_tolerance(1_000, Decimal::ZERO), 1_000);
+        assert_eq!(apply_slippage_tolerance(1_000, Decimal::new(25, 2)), 997);
+        assert_eq!(apply_slippage_tolerance(1_000, Decimal::ONE), 990);
+        assert_eq!(apply_slippage_tolerance(999, Decimal::new(5, 1)), 994);
+        assert_eq!(apply_slippage_tolerance(1_000, Decimal::from(150)), 0);
+        assert_eq!(apply_slippage_tolerance(u64::MAX, Decimal::ZERO), u64::MAX);
+        assert_eq!(apply_slippage_tolerance(0, Decimal::ONE), 0);
+    }
+
+    #[test]
+    fn test_two_hop_simulation() -> Result<()> {
+        // 10_000 -> 19_743 -> 10_233
+        let hops = [(1_000_000, 2_000_000, 30), (2_000_000, 1_050_000, 30)];
+        assert_eq!(simulate_multi_hop(&hops, 10_000, Decimal::from(5))?, 10_233);
+        Ok(())
+    }
+
+    #[test]
+    fn test_three_hop_simulation() -> Result<()> {
+        // Triangle pricing out at 1.2x: 1_000 -> 1_992 -> 3_968 -> 1_185
+        let hops = [
+            (1_000_000, 2_000_000, 30),
+            (2_000_000, 4_000_000, 30),
+            (4_000_000, 1_200_000, 30),
+        ];
+        assert_eq!(simulate_multi_hop(&hops, 1_000, Decimal::from(5))?, 1_185);
+        Ok(())
+    }
+
+    #[test]
+    fn test_multi_hop_empty_reserve() {
+        let hops = [(1_000_000, 2_000_000, 30), (0, 1_000_000, 30)];
+        assert!(matches!(
+            simulate_multi_hop(&hops, 1_000, Decimal::from(5)),
+            Err(BotError::InsufficientLiquidity { .. })
+        ));
+    }
+
+    #[test]
+    fn test_multi_hop_price_impact_limit() {
+        // The hops move the price ~9.1% and ~8.3%, together ~16.7%
+        let hops = [(100_000, 100_000, 0), (100_000, 100_000, 0)];
+        assert!(simulate_multi_hop(&hops, 10_000, Decimal::from(20)).is_ok());
+        assert!(matches!(
+            simulate_multi_hop(&hops, 10_000, Decimal::from(10)),
+            Err(BotError::SlippageTooHigh { .. })
+        ));
+    }
+
+    #[test]
+    fn test_optimal_input_beats_sampled_amounts() {
+        // Second pool prices the token ~10% higher than the first
+        let reserves = [
+            (1_000_000_000_000, 2_000_000_000_000, 30),
+            (2_000_000_000_000, 1_100_000_000_000, 30),
+        ];
+        let (min_amount, max_amount) = (1_000, 500_000_000_000);
+
+        let optimal = calculate_optimal_input_amount(&reserves, min_amount, max_amount);
+        let best = profit_at_amount(&reserves, optimal);
+
+        assert!(best > 0);
+        for sample in (min_amount..max_amount).step_by(1_000_000_000) {
+            assert!(best >= profit_at_amount(&reserves, sample));
+        }
+    }
+
+    #[test]
+    fn test_optimal_input_unprofitable_path_stays_at_minimum() {
+        let reserves = [(1_000_000, 2_000_000, 30), (2_000_000, 1_000_000, 30)];
+        assert_eq!(calculate_optimal_input_amount(&reserves, 100, 1_000_000), 100);
+    }
+
+    #[test]
+    fn test_max_input_within_impact() {
+        // Single hop impact is amount / (reserve + amount): 5% at 52_631
+        let reserves = [(1_000_000, 1_000_000, 0)];
+        assert_eq!(max_input_within_impact(&reserves, u64::MAX, Decimal::from(5)), 52_631);
+        assert_eq!(max_input_within_impact(&reserves, 10_000, Decimal::from(5)), 10_000);
+
+        let empty = [(0, 1_000_000, 0)];
+        assert_eq!(max_input_within_impact(&empty, 10_000, Decimal::from(5)), 0);
+    }
+
+    #[test]
+    fn test_profit_at_amount_empty_pool_loses_input() {
+        let reserves = [(1_000_000, 2_000_000, 30), (0, 1_000_000, 30)];
+        assert_eq!(profit_at_amount(&reserves, 1_000), -1_000);
+    }
+
+    proptest! {
+        #[test]
+        fn prop_output_below_reserve_out(
+            amount_in in 1..u64::MAX / 2,
+            reserve_in in 1..MAX_RESERVE,
+            reserve_out in 1..MAX_RESERVE,
+            fee_bps in 0..1_000u32,
+        ) {
+            prop_assert!(calculate_amm_output(amount_in, reserve_in, reserve_out, fee_bps) < reserve_out);
+        }
+
+        #[test]
+        fn prop_output_monotonic_in_amount(
+            amount_in in 1..u64::MAX / 4,
+            extra in 0..u64::MAX / 4,
+            reserve_in in 1..MAX_RESERVE,
+            reserve_out in 1..MAX_RESERVE,
+            fee_bps in 0..1_000u32,
+        ) {
+            let smaller = calculate_amm_output(amount_in, reserve_in, reserve_out, fee_bps);
+            let larger = calculate_amm_output(amount_in + extra, reserve_in, reserve_out, fee_bps);
+            prop_assert!(smaller <= larger);
+        }
+
+        #[test]
+        fn prop_constant_product_never_decreases(
+            amount_in in 1..u64::MAX / 2,
+            reserve_in in 1..MAX_RESERVE,
+            reserve_out in 1..MAX_RESERVE,
+            fee_bps in 0..1_000u32,
+        ) {
+            let output = calculate_amm_output(amount_in, reserve_in, reserve_out, fee_bps);
+            let before = reserve_in as u128 * reserve_out as u128;
+            let after = (reserve_in as u128 + amount_in as u128) * (reserve_out - output) as u128;
+            prop_assert!(before <= after);
+        }
+
+        #[test]
+        fn prop_stable_swap_output_below_reserve_out(
+            amount_in in 1..MAX_RESERVE,
+            reserve_in in 1_000..MAX_RESERVE,
+            reserve_out in 1_000..MAX_RESERVE,
+            amp_factor in 1..5_000u64,
+        ) {
+            prop_assert!(calculate_stable_swap_output(amount_in, reserve_in, reserve_out, amp_factor, 4) < reserve_out);
+        }
+
+        #[test]
+        fn prop_mul_div_matches_wide_division(
+            a in any::<u64>(),
+            b in any::<u128>(),
+            extra in any::<u128>(),
+        ) {
+            let c = b.saturating_add(extra).max(1);
+            let expected = (a as u128).checked_mul(b).map(|product| product / c);
+            if let Some(expected) = expected {
+                prop_assert_eq!(mul_div(a, b, c), expected);
+            }
+            prop_assert!(mul_div(a, b, c) <= a as u128);
+            prop_assert_eq!(mul_div(a, c, c), a as u128);
+        }
+    }
+}
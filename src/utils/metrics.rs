@@ -0,0 +1,263 @@
+//! Prometheus metrics and the HTTP endpoint exposing them
+
+use std::{convert::Infallible, net::SocketAddr, sync::Arc};
+
+use http_body_util::Full;
+use hyper::{Request, Response, StatusCode, body::{Bytes, Incoming}, server::conn::http1, service::service_fn};
+use hyper_util::rt::TokioIo;
+use prometheus::{
+    Encoder, Gauge, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, Opts, Registry, TextEncoder,
+};
+use tokio::net::TcpListener;
+use tracing::{debug, info};
+
+use crate::{arbitrage::arbitrage_engine::StateHandle, sync::periodic::SyncOrchestrator, types::{BotError, DexId, ExecutionResult, ExecutionStatus, Result}};
+
+/// Header carrying the secret for `/state`
+pub const STATE_SECRET_HEADER: &str = "x-state-secret";
+
+/// Buckets for opportunity ages, from same-tick to well past expiry
+const OPPORTUNITY_AGE_BUCKETS_MS: [f64; 9] = [10.0, 50.0, 100.0, 250.0, 500.0, 1_000.0, 2_500.0, 5_000.0, 10_000.0];
+
+/// Every metric the bot records, registered in its own registry
+pub struct MetricsRegistry {
+    registry: Registry,
+    opportunities_found: IntCounter,
+    executions: IntCounterVec,
+    net_profit_usd: Gauge,
+    pool_sync_latency: HistogramVec,
+    websocket_reconnections: IntCounterVec,
+    opportunity_age: HistogramVec,
+    rpc_request_latency: HistogramVec,
+    rpc_requests: IntCounterVec,
+}
+
+fn metrics_error(e: prometheus::Error) -> BotError {
+    BotError::Unknown(format!("Metrics: {}", e))
+}
+
+impl MetricsRegistry {
+    pub fn new() -> Result<Self> {
+        let metrics = Self {
+            registry: Registry::new(),
+            opportunities_found: IntCounter::new(
+                "arbitrage_opportunities_found_total",
+                "Opportunities received from the detector",
+            )
+            .map_err(metrics_error)?,
+            executions: IntCounterVec::new(
+                Opts::new("arbitrage_executions_total", "Finished executions by outcome"),
+                &["status"],
+            )
+            .map_err(metrics_error)?,
+            net_profit_usd: Gauge::new("arbitrage_net_profit_usd", "Net profit of successful executions in USD")
+                .map_err(metrics_error)?,
+            pool_sync_latency: HistogramVec::new(
+                HistogramOpts::new("pool_sync_latency_seconds", "Delay from receiving a swap event to applying it"),
+                &["dex"],
+            )
+            .map_err(metrics_error)?,
+            websocket_reconnections: IntCounterVec::new(
+                Opts::new("websocket_reconnections_total", "WebSocket sessions re-established"),
+                &["dex"],
+            )
+            .map_err(metrics_error)?,
+            opportunity_age: HistogramVec::new(
+                HistogramOpts::new("opportunity_age_ms", "Opportunity age when the engine decided on it")
+                    .buckets(OPPORTUNITY_AGE_BUCKETS_MS.to_vec()),
+                &["outcome"],
+            )
+            .map_err(metrics_error)?,
+            rpc_request_latency: HistogramVec::new(
+                HistogramOpts::new("rpc_request_latency_seconds", "Sui RPC request latency, failovers included"),
+                &["method"],
+            )
+            .map_err(metrics_error)?,
+            rpc_requests: IntCounterVec::new(
+                Opts::new("rpc_requests_total", "Sui RPC requests by outcome"),
+                &["method", "status"],
+            )
+            .map_err(metrics_error)?,
+        };
+
+        metrics.registry.register(Box::new(metrics.opportunities_found.clone())).map_err(metrics_error)?;
+        metrics.registry.register(Box::new(metrics.executions.clone())).map_err(metrics_error)?;
+        metrics.registry.register(Box::new(metrics.net_profit_usd.clone())).map_err(metrics_error)?;
+        metrics.registry.register(Box::new(metrics.pool_sync_latency.clone())).map_err(metrics_error)?;
+        metrics.registry.register(Box::new(metrics.websocket_reconnections.clone())).map_err(metrics_error)?;
+        metrics.registry.register(Box::new(metrics.opportunity_age.clone())).map_err(metrics_error)?;
+        metrics.registry.register(Box::new(metrics.rpc_request_latency.clone())).map_err(metrics_error)?;
+        metrics.registry.register(Box::new(metrics.rpc_requests.clone())).map_err(metrics_error)?;
+        Ok(metrics)
+    }
+
+    pub fn record_opportunity_found(&self) {
+        self.opportunities_found.inc();
+    }
+
+    /// Count a finished execution; pending results are not counted
+    pub fn record_execution(&self, result: &ExecutionResult) {
+        let status = match result.status {
+            ExecutionStatus::Success => "success",
+            ExecutionStatus::Failed => "failed",
+            ExecutionStatus::Simulated => "simulated",
+            ExecutionStatus::Pending => return,
+        };
+        self.executions.with_label_values(&[status]).inc();
+    }
+
+    /// Add the USD value of one successful execution's profit
+    pub fn add_net_profit_usd(&self, profit_usd: f64) {
+        self.net_profit_usd.add(profit_usd);
+    }
+
+    pub fn observe_pool_sync_latency(&self, dex_id: DexId, latency_ms: u64) {
+        self.pool_sync_latency
+            .with_label_values(&[dex_id.name()])
+            .observe(latency_ms as f64 / 1_000.0);
+    }
+
+    pub fn record_websocket_reconnection(&self, dex_id: DexId) {
+        self.websocket_reconnections.with_label_values(&[dex_id.name()]).inc();
+    }
+
+    /// Age of an opportunity at the point it was skipped, rejected or executed
+    pub fn observe_opportunity_age(&self, outcome: &str, age_ms: u64) {
+        self.opportunity_age.with_label_values(&[outcome]).observe(age_ms as f64);
+    }
+
+    pub fn observe_rpc_request(&self, method: &str, latency_us: u64, succeeded: bool) {
+        self.rpc_request_latency
+            .with_label_values(&[method])
+            .observe(latency_us as f64 / 1_000_000.0);
+        let status = if succeeded { "success" } else { "failed" };
+        self.rpc_requests.with_label_values(&[method, status]).inc();
+    }
+
+    /// All metrics in the Prometheus text exposition format
+    pub fn render(&self) -> Result<String> {
+        let mut buffer = Vec::new();
+        TextEncoder::new()
+            .encode(&self.registry.gather(), &mut buffer)
+            .map_err(metrics_error)?;
+        String::from_utf8(buffer).map_err(|e| BotError::Unknown(format!("Metrics are not UTF-8: {}", e)))
+    }
+}
+
+pub fn register_metrics() -> Result<Arc<MetricsRegistry>> {
+    MetricsRegistry::new().map(Arc::new)
+}
+
+/// Engine state served on `/state` to requests that present `secret`
+#[derive(Debug, Clone)]
+pub struct StateEndpoint {
+    pub handle: StateHandle,
+    pub secret: String,
+}
+
+impl StateEndpoint {
+    async fn respond(&self, request: &Request<Incoming>) -> (StatusCode, String) {
+        let presented = request.headers().get(STATE_SECRET_HEADER).and_then(|value| value.to_str().ok());
+        if presented != Some(self.secret.as_str()) {
+            return (StatusCode::UNAUTHORIZED, String::new());
+        }
+        match self.handle.export().await.and_then(|state| Ok(serde_json::to_string(&state)?)) {
+            Ok(body) => (StatusCode::OK, body),
+            Err(e) => (StatusCode::SERVICE_UNAVAILABLE, e.to_string()),
+        }
+    }
+}
+
+/// HTTP endpoint serving `/metrics` for Prometheus to scrape, `/state`
+/// when given a `StateEndpoint` and `/sync-history` when given the sync
+/// orchestrator
+pub struct MetricsServer;
+
+impl MetricsServer {
+    /// Listen on `port` (0 picks a free one) and serve in the background.
+    /// Returns the bound address.
+    pub async fn start(
+        port: u16,
+        metrics: Arc<MetricsRegistry>,
+        state: Option<StateEndpoint>,
+        sync_orchestrator: Option<Arc<SyncOrchestrator>>,
+    ) -> Result<SocketAddr> {
+        let listener = TcpListener::bind(("0.0.0.0", port)).await?;
+        let address = listener.local_addr()?;
+        info!("Serving metrics on http://{}/metrics", address);
+        let state = state.map(Arc::new);
+
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let (metrics, state, sync_orchestrator) = (metrics.clone(), state.clone(), sync_orchestrator.clone());
+                tokio::spawn(async move {
+                    let service = service_fn(move |request| {
+                        let (metrics, state, sync_orchestrator) = (metrics.clone(), state.clone(), sync_orchestrator.clone());
+                        async move {
+                            Ok::<_, Infallible>(respond(&metrics, state.as_deref(), sync_orchestrator.as_deref(), &request).await)
+                        }
+                    });
+                    if let Err(e) = http1::Builder::new().serve_connection(TokioIo::new(stream), service).await {
+                        debug!("Metrics connection failed: {}", e);
+                    }
+                });
+            }
+        });
+        Ok(address)
+    }
+}
+
+async fn respond(
+    metrics: &MetricsRegistry,
+    state: Option<&StateEndpoint>,
+    sync_orchestrator: Option<&SyncOrchestrator>,
+    request: &Request<Incoming>,
+) -> Response<Full<Bytes>> {
+    let (status, body) = match (request.uri().path(), state, sync_orchestrator) {
+        ("/metrics", _, _) => match metrics.render() {
+            Ok(body) => (StatusCode::OK, body),
+            Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+        },
+        ("/state", Some(state), _) => state.respond(request).await,
+        ("/sync-history", _, Some(sync_orchestrator)) => match serde_json::to_string(&sync_orchestrator.get_sync_history()) {
+            Ok(body) => (StatusCode::OK, body),
+            Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+        },
+        _ => (StatusCode::NOT_FOUND, String::new()),
+    };
+    let mut response = Response::new(Full::new(Bytes::from(body)));
+    *response.status_mut() = status;
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_serves_recorded_metrics() -> Result<()> {
+        let metrics = register_metrics()?;
+        metrics.record_opportunity_found();
+        metrics.record_websocket_reconnection(DexId::Cetus);
+        metrics.observe_pool_sync_latency(DexId::Cetus, 250);
+
+        let address = MetricsServer::start(0, metrics, None, None).await?;
+        let url = format!("http://127.0.0.1:{}", address.port());
+        let response = reqwest::get(format!("{}/metrics", url))
+            .await
+            .map_err(|e| BotError::Network(e.to_string()))?;
+        let body = response.text().await.map_err(|e| BotError::Network(e.to_string()))?;
+
+        assert!(body.contains("arbitrage_opportunities_found_total 1"));
+        assert!(body.contains(r#"websocket_reconnections_total{dex="Cetus"} 1"#));
+        assert!(body.contains(r#"pool_sync_latency_seconds_sum{dex="Cetus"} 0.25"#));
+
+        for path in ["other", "state", "sync-history"] {
+            let missing = reqwest::get(format!("{}/{}", url, path))
+                .await
+                .map_err(|e| BotError::Network(e.to_string()))?;
+            assert_eq!(missing.status(), reqwest::StatusCode::NOT_FOUND);
+        }
+        Ok(())
+    }
+}
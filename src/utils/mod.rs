@@ -1,3 +1,10 @@
+pub mod alerting;
+pub mod clmm;
 pub mod config;
+pub mod digest_cache;
+pub mod event_latency;
+pub mod event_rate;
 pub mod logger;
-pub mod math;
\ No newline at end of file
+pub mod math;
+pub mod metrics;
+pub mod pnl;
\ No newline at end of file
@@ -0,0 +1,127 @@
+//! Running profit and loss, persisted across restarts
+
+use std::collections::HashMap;
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::types::{Result, Timestamp, now};
+
+/// Length of the daily P&L window
+pub const PNL_DAY_MS: u64 = 86_400_000;
+
+/// Profit of successful trades, for the current day and since first start
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PnlTracker {
+    pub daily_profit: Decimal,
+    pub lifetime_profit: Decimal,
+    pub trade_count: u64,
+    pub daily_trade_count: u64,
+    /// When the current daily window started
+    pub daily_reset_time: Timestamp,
+    /// Lifetime profit per start token; `lifetime_profit` sums these as-is
+    #[serde(default)]
+    pub profit_by_token: HashMap<String, Decimal>,
+}
+
+impl Default for PnlTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PnlTracker {
+    pub fn new() -> Self {
+        Self {
+            daily_profit: Decimal::ZERO,
+            lifetime_profit: Decimal::ZERO,
+            trade_count: 0,
+            daily_trade_count: 0,
+            daily_reset_time: now(),
+            profit_by_token: HashMap::new(),
+        }
+    }
+
+    /// Add a successful trade, starting a new day first if the current one is over
+    pub fn record_trade(&mut self, profit: Decimal, token_symbol: &str) {
+        if now().saturating_sub(self.daily_reset_time) >= PNL_DAY_MS {
+            self.reset_daily();
+        }
+        self.daily_profit += profit;
+        self.lifetime_profit += profit;
+        self.trade_count += 1;
+        self.daily_trade_count += 1;
+        *self.profit_by_token.entry(token_symbol.to_string()).or_insert(Decimal::ZERO) += profit;
+    }
+
+    pub fn reset_daily(&mut self) {
+        self.daily_profit = Decimal::ZERO;
+        self.daily_trade_count = 0;
+        self.daily_reset_time = now();
+    }
+
+    pub fn save_to_file(&self, path: &str) -> Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn load_from_file(path: &str) -> Result<Self> {
+        Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+    }
+
+    /// Pretty print summary
+    pub fn summary(&self) -> String {
+        let mut tokens: Vec<String> = self.profit_by_token
+            .iter()
+            .map(|(symbol, profit)| format!("{} {}", profit, symbol))
+            .collect();
+        tokens.sort();
+        format!(
+            "P&L today: {} over {} trades | lifetime: {} over {} trades [{}]",
+            self.daily_profit,
+            self.daily_trade_count,
+            self.lifetime_profit,
+            self.trade_count,
+            tokens.join(", ")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_daily_window_resets() {
+        let mut pnl = PnlTracker::new();
+        pnl.record_trade(Decimal::new(15, 1), "SUI");
+        pnl.record_trade(Decimal::new(-5, 1), "SUI");
+        assert_eq!(pnl.daily_profit, Decimal::ONE);
+
+        pnl.daily_reset_time = now() - PNL_DAY_MS;
+        pnl.record_trade(Decimal::TWO, "USDC");
+
+        assert_eq!(pnl.daily_profit, Decimal::TWO);
+        assert_eq!(pnl.daily_trade_count, 1);
+        assert_eq!(pnl.lifetime_profit, Decimal::from(3));
+        assert_eq!(pnl.trade_count, 3);
+        assert_eq!(pnl.profit_by_token.get("SUI"), Some(&Decimal::ONE));
+    }
+
+    #[test]
+    fn test_survives_save_and_load() -> Result<()> {
+        let path = std::env::temp_dir().join(format!("pnl-{}.json", now()));
+        let path = path.to_string_lossy();
+        let mut pnl = PnlTracker::new();
+        pnl.record_trade(Decimal::new(25, 1), "SUI");
+
+        pnl.save_to_file(&path)?;
+        let loaded = PnlTracker::load_from_file(&path)?;
+        std::fs::remove_file(path.as_ref())?;
+
+        assert_eq!(loaded.lifetime_profit, pnl.lifetime_profit);
+        assert_eq!(loaded.daily_reset_time, pnl.daily_reset_time);
+        assert_eq!(loaded.profit_by_token, pnl.profit_by_token);
+        Ok(())
+    }
+}